@@ -126,4 +126,36 @@ pub fn bench_bitwarden_routes(c: &mut Criterion) {
     }));
 }
 
-criterion_group!(routing, bench_rust_lang_routes, bench_bitwarden_routes);
+// A table of `8 * resources` routes of the shape of a large REST API: static
+// prefixes, dynamic `<id>` parameters, nested resources, and trailing segments.
+fn generated_routes_table(resources: usize) -> String {
+    let mut table = String::new();
+    for i in 0..resources {
+        let r = format!("/api/v1/resource{}", i);
+        table += &format!("GET {}\n", r);
+        table += &format!("POST {} application/json\n", r);
+        table += &format!("GET {}/<id>\n", r);
+        table += &format!("PUT {}/<id> application/json\n", r);
+        table += &format!("DELETE {}/<id>\n", r);
+        table += &format!("GET {}/<id>/children?<page>\n", r);
+        table += &format!("GET {}/<id>/children/<child>\n", r);
+        table += &format!("GET /static/resource{}/<path..>\n", i);
+    }
+
+    table
+}
+
+pub fn bench_generated_routes(c: &mut Criterion) {
+    let table = generated_routes_table(50);
+    let routes = parse_routes_table(&table);
+    let client = client(routes.clone());
+    let requests = generate_matching_requests(&client, &routes);
+    c.bench_function("generated-400.routes", |b| b.iter(|| {
+        for request in requests.clone() {
+            let response = request.dispatch();
+            assert_eq!(response.status(), Status::Ok);
+        }
+    }));
+}
+
+criterion_group!(routing, bench_rust_lang_routes, bench_bitwarden_routes, bench_generated_routes);
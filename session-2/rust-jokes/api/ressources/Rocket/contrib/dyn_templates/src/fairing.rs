@@ -46,7 +46,24 @@ impl Fairing for TemplateFairing {
         };
 
         if let Some(ctxt) = Context::initialize(&path, &self.callback) {
-            Ok(rocket.manage(ContextManager::new(ctxt)))
+            let rocket = rocket.manage(ContextManager::new(ctxt));
+            #[cfg(feature = "health")]
+            let rocket = rocket::health::Health::register(rocket, "templates", |rocket| {
+                Box::pin(async move {
+                    let root = match rocket.state::<ContextManager>() {
+                        Some(cm) => cm.context().root.clone(),
+                        None => return Err("missing template context".into()),
+                    };
+
+                    // `tokio::fs` checks the directory off of the async runtime.
+                    match rocket::tokio::fs::metadata(&root).await {
+                        Ok(metadata) if metadata.is_dir() => Ok(()),
+                        _ => Err("template directory is missing".into()),
+                    }
+                })
+            });
+
+            Ok(rocket)
         } else {
             error_!("Template initialization failed. Aborting launch.");
             Err(rocket)
@@ -114,6 +114,10 @@
 //! [attached](rocket::Rocket::attach()) to the running Rocket instance. Failure
 //! to do so will result in an ignite-time error.
 //!
+//! With the `health` feature enabled, the template fairing also registers a
+//! `templates` [health check](rocket::health), which fails if the template
+//! directory goes missing.
+//!
 //! ## Rendering
 //!
 //! Templates are rendered with the `render` method. The method takes in the
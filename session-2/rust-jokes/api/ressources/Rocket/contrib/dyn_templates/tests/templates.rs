@@ -98,6 +98,17 @@ fn test_sentinel() {
     Client::debug_with(routes![always_ok_sentinel]).expect("no sentinel abort");
 }
 
+#[test]
+#[cfg(feature = "health")]
+fn test_health_check() {
+    use rocket::{local::blocking::Client, health::Health, http::Status};
+
+    let client = Client::debug(rocket().attach(Health::new())).unwrap();
+    let response = client.get("/health/ready").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.into_string().unwrap().contains(r#""templates":{"status":"up"}"#));
+}
+
 #[cfg(feature = "tera")]
 mod tera_tests {
     use super::*;
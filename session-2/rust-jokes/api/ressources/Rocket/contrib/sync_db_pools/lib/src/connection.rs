@@ -79,12 +79,18 @@ impl<K: 'static, C: Poolable> ConnectionPool<K, C> {
 
                 let pool_size = config.pool_size;
                 match C::pool(db, &rocket) {
-                    Ok(pool) => Ok(rocket.manage(ConnectionPool::<K, C> {
-                        config,
-                        pool: Some(pool),
-                        semaphore: Arc::new(Semaphore::new(pool_size as usize)),
-                        _marker: PhantomData,
-                    })),
+                    Ok(pool) => {
+                        let rocket = rocket.manage(ConnectionPool::<K, C> {
+                            config,
+                            pool: Some(pool),
+                            semaphore: Arc::new(Semaphore::new(pool_size as usize)),
+                            _marker: PhantomData,
+                        });
+
+                        #[cfg(feature = "health")]
+                        let rocket = Self::register_health(rocket, db);
+                        Ok(rocket)
+                    }
                     Err(Error::Config(e)) => dberr!("config", db, "{}", e, rocket),
                     Err(Error::Pool(e)) => dberr!("pool init", db, "{}", e, rocket),
                     Err(Error::Custom(e)) => dberr!("pool manager", db, "{:?}", e, rocket),
@@ -93,6 +99,33 @@ impl<K: 'static, C: Poolable> ConnectionPool<K, C> {
         })
     }
 
+    // Registers a health check that validates a pooled connection on a
+    // blocking thread. An idle connection is validated as it's checked out; if
+    // there's none and every connection is busy, the pool is considered up.
+    // Otherwise, a new connection is opened and validated, waiting for at most
+    // the configured `timeout`.
+    #[cfg(feature = "health")]
+    fn register_health(rocket: Rocket<rocket::Build>, db: &'static str) -> Rocket<rocket::Build> {
+        rocket::health::Health::register(rocket, db, |rocket| Box::pin(async move {
+            let (pool, duration) = match rocket.state::<Self>() {
+                Some(Self { pool: Some(pool), config, .. }) => {
+                    (pool.clone(), std::time::Duration::from_secs(config.timeout as u64))
+                }
+                _ => return Err("missing database pool".into()),
+            };
+
+            run_blocking(move || {
+                if pool.try_get().is_some() || pool.state().connections >= pool.max_size() {
+                    return Ok(());
+                }
+
+                pool.get_timeout(duration)
+                    .map(|_| ())
+                    .map_err(|e| format!("no valid database connection: {}", e))
+            }).await
+        }))
+    }
+
     async fn get(&self) -> Result<Connection<K, C>, ()> {
         let duration = std::time::Duration::from_secs(self.config.timeout as u64);
         let permit = match timeout(duration, self.semaphore.clone().acquire_owned()).await {
@@ -118,3 +118,123 @@ mod sentinel_and_runtime_test {
         assert!(matches!(err.kind(), SentinelAborts(vec) if vec.len() == 1));
     }
 }
+
+#[cfg(feature = "health")]
+mod health_test {
+    use rocket::{Rocket, Build};
+    use r2d2::{ManageConnection, Pool};
+    use rocket_sync_db_pools::{database, Poolable, PoolResult};
+
+    struct TestManager;
+    struct TestConnection;
+
+    impl ManageConnection for TestManager {
+        type Connection = TestConnection;
+        type Error = std::convert::Infallible;
+
+        fn connect(&self) -> Result<Self::Connection, Self::Error> {
+            Ok(TestConnection)
+        }
+
+        fn is_valid(&self, _conn: &mut Self::Connection) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn has_broken(&self, _conn: &mut Self::Connection) -> bool {
+            false
+        }
+    }
+
+    impl Poolable for TestConnection {
+        type Manager = TestManager;
+        type Error = ();
+
+        fn pool(_db_name: &str, _rocket: &Rocket<Build>) -> PoolResult<Self> {
+            Ok(Pool::builder().max_size(1).build(TestManager)?)
+        }
+    }
+
+    struct InvalidManager;
+    struct InvalidConnection;
+
+    impl ManageConnection for InvalidManager {
+        type Connection = InvalidConnection;
+        type Error = std::io::Error;
+
+        fn connect(&self) -> Result<Self::Connection, Self::Error> {
+            Ok(InvalidConnection)
+        }
+
+        fn is_valid(&self, _conn: &mut Self::Connection) -> Result<(), Self::Error> {
+            Err(std::io::Error::new(std::io::ErrorKind::Other, "connection is invalid"))
+        }
+
+        fn has_broken(&self, _conn: &mut Self::Connection) -> bool {
+            false
+        }
+    }
+
+    impl Poolable for InvalidConnection {
+        type Manager = InvalidManager;
+        type Error = ();
+
+        fn pool(_db_name: &str, _rocket: &Rocket<Build>) -> PoolResult<Self> {
+            Ok(Pool::builder().max_size(1).build(InvalidManager)?)
+        }
+    }
+
+    #[database("test_db")]
+    struct TestDb(TestConnection);
+
+    #[database("invalid_db")]
+    struct InvalidDb(InvalidConnection);
+
+    #[rocket::async_test]
+    async fn test_busy_pool_is_ready() {
+        use rocket::figment::Figment;
+        use rocket::{health::Health, http::Status, local::asynchronous::Client};
+
+        let config = Figment::from(rocket::Config::debug_default())
+            .merge(("databases.test_db.url", ""))
+            .merge(("databases.test_db.timeout", 1));
+
+        let rocket = rocket::custom(config).attach(TestDb::fairing()).attach(Health::new());
+        let client = Client::debug(rocket).await.unwrap();
+        let expected = r#""test_db":{"status":"up"}"#;
+
+        let response = client.get("/health/ready").dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.into_string().await.unwrap().contains(expected));
+
+        // Checking the pool doesn't wait on, or fail for, its only connection.
+        let db = TestDb::get_one(client.rocket()).await.unwrap();
+        let response = client.get("/health/ready").dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.into_string().await.unwrap().contains(expected));
+        db.run(|_| ()).await;
+    }
+
+    #[rocket::async_test]
+    async fn test_invalid_connection_is_down() {
+        use rocket::figment::Figment;
+        use rocket::{health::Health, http::Status, local::asynchronous::Client};
+
+        let config = Figment::from(rocket::Config::debug_default())
+            .merge(("databases.invalid_db.url", ""))
+            .merge(("databases.invalid_db.timeout", 1));
+
+        let rocket = rocket::custom(config).attach(InvalidDb::fairing()).attach(Health::new());
+        let client = Client::debug(rocket).await.unwrap();
+
+        let response = client.get("/health/ready").dispatch().await;
+        assert_eq!(response.status(), Status::ServiceUnavailable);
+        let body = response.into_string().await.unwrap();
+        assert!(body.contains(r#""invalid_db":{"status":"down""#));
+        assert!(body.contains("connection is invalid"));
+
+        // Nor does the pool hand out an invalid connection.
+        if let Some(db) = InvalidDb::get_one(client.rocket()).await {
+            db.run(|_| panic!("got an invalid connection")).await;
+        }
+    }
+}
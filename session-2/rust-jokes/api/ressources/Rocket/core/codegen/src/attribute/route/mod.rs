@@ -1,4 +1,5 @@
 mod parse;
+mod openapi;
 
 use std::hash::Hash;
 
@@ -308,6 +309,9 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
     // Extract the sentinels from the route.
     let sentinels = sentinels_expr(&route);
 
+    // Describe the route's operation, if it's marked with `#[openapi]`.
+    let operation = openapi::describe_expr(&route);
+
     // Gather info about the function.
     let (vis, handler_fn) = (&route.handler.vis, &route.handler);
     let handler_fn_name = &handler_fn.sig.ident;
@@ -317,6 +321,7 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
     let method = route.attr.method;
     let uri = route.attr.uri.to_string();
     let rank = Optional(route.attr.rank);
+    let timeout = Optional(route.attr.timeout);
     let format = Optional(route.attr.format.as_ref());
 
     Ok(quote! {
@@ -352,7 +357,9 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
                     handler: monomorphized_function,
                     format: #format,
                     rank: #rank,
+                    timeout: #timeout,
                     sentinels: #sentinels,
+                    operation: #operation,
                 }
             }
 
@@ -404,6 +411,7 @@ fn incomplete_route(
         data: method_attribute.data,
         format: method_attribute.format,
         rank: method_attribute.rank,
+        timeout: method_attribute.timeout,
     };
 
     codegen_route(Route::from(attribute, function)?)
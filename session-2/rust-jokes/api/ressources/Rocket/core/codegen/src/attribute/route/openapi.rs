@@ -0,0 +1,100 @@
+use devise::{Spanned, Result, FromMeta};
+use devise::ext::{SpanDiagnosticExt, TypeExt as _};
+use proc_macro2::{TokenStream, Span};
+
+use crate::syn_ext::{AttrsExt, TypeExt as _};
+use super::parse::Route;
+
+/// The parsed `#[openapi(..)]` attribute.
+#[derive(Debug, FromMeta)]
+pub struct OpenApi {
+    pub tag: Option<String>,
+    pub deprecated: Option<bool>,
+}
+
+impl OpenApi {
+    /// Removes the `#[openapi]` attribute from `handler`, returning it, parsed,
+    /// if there was one.
+    pub fn take_from(handler: &mut syn::ItemFn) -> Result<Option<OpenApi>> {
+        let is_openapi = |attr: &syn::Attribute| attr.path.is_ident("openapi");
+        let attr = match handler.attrs.iter().position(is_openapi) {
+            Some(i) => handler.attrs.remove(i),
+            None => return Ok(None),
+        };
+
+        if let Some(duplicate) = handler.attrs.iter().find(|a| is_openapi(a)) {
+            return Err(duplicate.span().error("duplicate `#[openapi]` attribute"));
+        }
+
+        if handler.sig.generics.type_params().next().is_some() {
+            return Err(handler.sig.generics.span()
+                .error("`#[openapi]` cannot describe handlers with type generics"));
+        }
+
+        match attr.tokens.is_empty() {
+            true => Ok(Some(OpenApi { tag: None, deprecated: None })),
+            false => OpenApi::from_attr(&attr).map(Some),
+        }
+    }
+}
+
+/// Returns an expression for the route's operation describer, if it's
+/// described.
+pub fn describe_expr(route: &Route) -> TokenStream {
+    define_spanned_export!(Span::call_site() => _None, _Some, _openapi);
+
+    let openapi = match route.openapi {
+        Some(ref openapi) => openapi,
+        None => return quote!(#_None),
+    };
+
+    let name = route.handler.sig.ident.to_string();
+    let doc = route.handler.attrs.doc_comment();
+    let tag = openapi.tag.as_ref().map(|tag| quote!(.tag(#tag)));
+    let deprecated = openapi.deprecated.unwrap_or(false);
+
+    let path_params = route.param_guards().map(|guard| {
+        let (name, ty) = (guard.name.as_str(), &guard.ty);
+        quote_spanned!(ty.span() => .path_param::<#ty>(__gen, #name))
+    });
+
+    let query_params = route.query_guards().map(|guard| {
+        let (name, ty) = (guard.name.as_str(), &guard.ty);
+        match guard.trailing {
+            true => quote_spanned!(ty.span() => .query_params::<#ty>(__gen)),
+            false => quote_spanned!(ty.span() => .query_param::<#ty>(__gen, #name)),
+        }
+    });
+
+    let request_body = route.data_guard.as_ref().map(|guard| {
+        let ty = &guard.ty;
+        quote_spanned!(ty.span() => .request_body::<#ty>(__gen))
+    });
+
+    // Types like `impl Responder` can't be named, so they aren't described.
+    let responses = match route.handler.sig.output {
+        syn::ReturnType::Default => Some(quote!(.responses::<()>(__gen))),
+        syn::ReturnType::Type(_, ref ty) if ty.is_concrete(&[]) => {
+            let ty = ty.with_stripped_lifetimes();
+            Some(quote_spanned!(ty.span() => .responses::<#ty>(__gen)))
+        }
+        syn::ReturnType::Type(..) => None,
+    };
+
+    quote! {
+        #_Some({
+            fn describe(__gen: &mut #_openapi::Generator) -> #_openapi::Operation {
+                #_openapi::Operation::new(#name)
+                    .doc(#doc)
+                    #tag
+                    .deprecated(#deprecated)
+                    #(#path_params)*
+                    #(#query_params)*
+                    #request_body
+                    #responses
+            }
+
+            describe
+        })
+    }
+}
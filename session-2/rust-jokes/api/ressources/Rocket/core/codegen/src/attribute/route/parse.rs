@@ -1,3 +1,5 @@
+use std::convert::TryFrom;
+
 use devise::{Spanned, SpanWrapped, Result, FromMeta};
 use devise::ext::{SpanDiagnosticExt, TypeExt};
 use indexmap::{IndexSet, IndexMap};
@@ -10,6 +12,7 @@ use crate::syn_ext::FnArgExt;
 use crate::name::Name;
 use crate::http::ext::IntoOwned;
 use crate::http::uri::{Origin, fmt};
+use super::openapi::OpenApi;
 
 /// This structure represents the parsed `route` attribute and associated items.
 #[derive(Debug)]
@@ -28,6 +31,8 @@ pub struct Route {
     pub handler: syn::ItemFn,
     /// The parsed arguments to the user's function.
     pub arguments: Arguments,
+    /// The `#[openapi]` attribute, if the route is described.
+    pub openapi: Option<OpenApi>,
 }
 
 type ArgumentMap = IndexMap<Name, (syn::Ident, syn::Type)>;
@@ -47,6 +52,7 @@ pub struct Attribute {
     pub data: Option<SpanWrapped<Dynamic>>,
     pub format: Option<MediaType>,
     pub rank: Option<isize>,
+    pub timeout: Option<Timeout>,
 }
 
 /// The parsed `#[method(..)]` (e.g, `get`, `put`, etc.) attribute.
@@ -57,6 +63,27 @@ pub struct MethodAttribute {
     pub data: Option<SpanWrapped<Dynamic>>,
     pub format: Option<MediaType>,
     pub rank: Option<isize>,
+    pub timeout: Option<Timeout>,
+}
+
+/// The parsed `timeout` route parameter: a number of seconds.
+#[derive(Debug, Copy, Clone)]
+pub struct Timeout(pub u32);
+
+impl FromMeta for Timeout {
+    fn from_meta(meta: &devise::MetaItem) -> Result<Self> {
+        let secs = usize::from_meta(meta)?;
+        u32::try_from(secs)
+            .map(Timeout)
+            .map_err(|_| meta.value_span().error("timeout is out of range for `u32`"))
+    }
+}
+
+impl quote::ToTokens for Timeout {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let secs = self.0;
+        tokens.extend(quote!(#secs));
+    }
 }
 
 #[derive(Debug)]
@@ -121,10 +148,16 @@ impl Route {
         }
     }
 
-    pub fn from(attr: Attribute, handler: syn::ItemFn) -> Result<Route> {
+    pub fn from(attr: Attribute, mut handler: syn::ItemFn) -> Result<Route> {
         // Collect diagnostics as we proceed.
         let mut diags = Diagnostics::new();
 
+        // Remove and parse the `#[openapi]` attribute, if any.
+        let openapi = OpenApi::take_from(&mut handler)
+            .map_err(|e| diags.push(e))
+            .ok()
+            .flatten();
+
         // Emit a warning if a `data` param was supplied for non-payload methods.
         if let Some(ref data) = attr.data {
             if !attr.method.0.supports_payload() {
@@ -210,7 +243,7 @@ impl Route {
 
         diags.head_err_or(Route {
             attr, path_params, query_params, data_guard, request_guards,
-            handler, arguments,
+            handler, arguments, openapi,
         })
     }
 }
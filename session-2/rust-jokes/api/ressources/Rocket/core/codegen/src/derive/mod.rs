@@ -2,4 +2,6 @@ mod form_field;
 pub mod from_form;
 pub mod from_form_field;
 pub mod responder;
+pub mod schema;
+pub mod responses;
 pub mod uri_display;
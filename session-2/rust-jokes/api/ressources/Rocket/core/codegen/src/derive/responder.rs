@@ -10,10 +10,10 @@ use crate::http_codegen::{ContentType, Status};
 type WherePredicates = Punctuated<syn::WherePredicate, syn::Token![,]>;
 
 #[derive(Debug, Default, FromMeta)]
-struct ItemAttr {
-    bound: Option<SpanWrapped<String>>,
-    content_type: Option<SpanWrapped<ContentType>>,
-    status: Option<SpanWrapped<Status>>,
+pub struct ItemAttr {
+    pub bound: Option<SpanWrapped<String>>,
+    pub content_type: Option<SpanWrapped<ContentType>>,
+    pub status: Option<SpanWrapped<Status>>,
 }
 
 #[derive(Default, FromMeta)]
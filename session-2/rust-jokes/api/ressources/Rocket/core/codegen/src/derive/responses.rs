@@ -0,0 +1,77 @@
+use devise::{*, ext::{TypeExt, SpanDiagnosticExt}};
+use proc_macro2::TokenStream;
+
+use crate::exports::*;
+use crate::syn_ext::AttrsExt;
+use crate::derive::responder::ItemAttr;
+
+/// Returns an expression for the responses of a responder derived from
+/// `fields`: those of the first field, moved to the status and relabeled with
+/// the content-type in the parent's `#[response]` attribute, if any.
+fn fields_responses(fields: Fields<'_>) -> Result<TokenStream> {
+    let attr = ItemAttr::one_from_attrs("response", fields.parent.attrs())?
+        .unwrap_or_default();
+
+    let first = fields.iter().next().expect("have at least one field");
+    let ty = first.ty.with_stripped_lifetimes();
+    let responses = quote_spanned! { first.span() =>
+        <#ty as #_openapi::Responses>::responses(__gen)
+    };
+
+    let doc = fields.parent.attrs().doc_comment();
+    let responses = match attr.status {
+        Some(status) => {
+            let doc = Some(doc).filter(|doc| !doc.is_empty());
+            let doc = doc.map_or_else(|| quote!(#_None), |doc| quote!(#_Some(#doc)));
+            quote!(#_openapi::with_status(#responses, #_Some(#status), #doc))
+        }
+        None if !doc.is_empty() => {
+            quote!(#_openapi::private::describe_responses(#responses, #doc))
+        }
+        None => responses,
+    };
+
+    Ok(match attr.content_type {
+        Some(content_type) => {
+            let media_type = content_type.0.media_type();
+            let media_type = format!("{}/{}", media_type.top(), media_type.sub());
+            quote!(#_openapi::with_media_type(#responses, #media_type))
+        }
+        None => responses,
+    })
+}
+
+pub fn derive_responses(input: proc_macro::TokenStream) -> TokenStream {
+    DeriveGenerator::build_for(input, quote!(impl #_openapi::Responses))
+        .support(Support::Struct | Support::Enum | Support::Lifetime | Support::Type)
+        .validator(ValidatorBuild::new()
+            .fields_validate(|_, fields| match fields.is_empty() {
+                true => Err(fields.span().error("need at least one field")),
+                false => Ok(())
+            })
+        )
+        .type_bound(quote!(#_openapi::Responses))
+        .inner_mapper(MapperBuild::new()
+            .with_output(|_, output| quote! {
+                fn responses(
+                    __gen: &mut #_openapi::Generator
+                ) -> #_openapi::private::Map<::std::string::String, #_openapi::private::Value> {
+                    #output
+                }
+            })
+            .try_struct_map(|_, data| fields_responses(data.fields()))
+            .try_enum_map(|_, data| {
+                let responses = data.variants()
+                    .map(|variant| fields_responses(variant.fields()))
+                    .collect::<Result<Vec<_>>>()?;
+
+                let union = quote!(#_openapi::private::union);
+                Ok(quote! {
+                    let mut __responses = #_openapi::private::Map::new();
+                    #(__responses = #union(__responses, #responses);)*
+                    __responses
+                })
+            })
+        )
+        .to_tokens()
+}
@@ -0,0 +1,263 @@
+use devise::{*, ext::SpanDiagnosticExt};
+use proc_macro2::TokenStream;
+use syn::ext::IdentExt as _;
+
+use crate::exports::*;
+use crate::syn_ext::AttrsExt;
+use crate::derive::form_field::{FieldAttr, FieldExt, VariantExt};
+
+const NO_EMPTY_FIELDS: &str = "fieldless structs are not supported";
+const NO_EMPTY_ENUMS: &str = "empty enums are not supported";
+const RENAME_RULES: &[&str] = &[
+    "lowercase", "UPPERCASE", "PascalCase", "camelCase", "snake_case",
+    "SCREAMING_SNAKE_CASE", "kebab-case", "SCREAMING-KEBAB-CASE",
+];
+
+/// The `#[serde(..)]` parameters that affect a schema. All others are ignored.
+#[derive(Default)]
+struct SerdeAttr {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    skip: bool,
+    default: bool,
+}
+
+impl SerdeAttr {
+    fn from_attrs(attrs: &[syn::Attribute]) -> Result<Self> {
+        let mut serde = SerdeAttr::default();
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("serde")) {
+            let list = match attr.parse_meta()? {
+                syn::Meta::List(list) => list,
+                _ => continue,
+            };
+
+            for item in list.nested {
+                let meta = match item {
+                    syn::NestedMeta::Meta(meta) => meta,
+                    _ => continue,
+                };
+
+                let value = match &meta {
+                    syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(s), .. }) => {
+                        Some(s)
+                    }
+                    _ => None,
+                };
+
+                let key = meta.path().get_ident().map(|i| i.to_string()).unwrap_or_default();
+                match (key.as_str(), value) {
+                    ("skip" | "skip_serializing" | "skip_deserializing", _) => serde.skip = true,
+                    ("default" | "skip_serializing_if", _) => serde.default = true,
+                    ("rename", Some(name)) => serde.rename = Some(name.value()),
+                    ("rename_all", Some(rule)) => {
+                        if !RENAME_RULES.contains(&rule.value().as_str()) {
+                            return Err(rule.span().error("unknown `rename_all` rule")
+                                .help(format!("expected one of: {}", RENAME_RULES.join(", "))));
+                        }
+
+                        serde.rename_all = Some(rule.value());
+                    }
+                    _ => { /* not relevant to the schema */ }
+                }
+            }
+        }
+
+        Ok(serde)
+    }
+}
+
+/// Applies the `rename_all` rule `rule` to `ident`, a variant name in
+/// `PascalCase` if `variant`, and a field name in `snake_case` otherwise.
+fn rename(ident: &str, rule: &str, variant: bool) -> String {
+    let words: Vec<String> = match variant {
+        true => ident.chars().fold(vec![], |mut words, c| {
+            match words.last_mut() {
+                Some(word) if !c.is_uppercase() => word.push(c),
+                _ => words.push(c.to_string()),
+            }
+
+            words
+        }),
+        false => ident.split('_').map(|w| w.to_string()).collect(),
+    };
+
+    let capitalize = |word: &String| {
+        let mut chars = word.chars();
+        chars.next()
+            .map(|c| c.to_uppercase().chain(chars.flat_map(|c| c.to_lowercase())).collect())
+            .unwrap_or_default()
+    };
+
+    let joined = |sep: &str| words.join(sep);
+    match rule {
+        "lowercase" => ident.to_lowercase(),
+        "UPPERCASE" => ident.to_uppercase(),
+        "PascalCase" => words.iter().map(capitalize).collect::<Vec<String>>().concat(),
+        "camelCase" => {
+            let pascal = words.iter().map(capitalize).collect::<Vec<String>>().concat();
+            let mut chars = pascal.chars();
+            chars.next()
+                .map(|c| c.to_lowercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        "snake_case" => joined("_").to_lowercase(),
+        "SCREAMING_SNAKE_CASE" => joined("_").to_uppercase(),
+        "kebab-case" => joined("-").to_lowercase(),
+        "SCREAMING-KEBAB-CASE" => joined("-").to_uppercase(),
+        _ => ident.to_string(),
+    }
+}
+
+/// Returns the name of `field`, as `serde` would name it, or as `FromForm`
+/// would if there are no relevant `serde` attributes.
+fn field_name(field: &Field<'_>, serde: &SerdeAttr, container: &SerdeAttr) -> Result<String> {
+    if let Some(ref name) = serde.rename {
+        return Ok(name.clone());
+    }
+
+    if let Some(ref rule) = container.rename_all {
+        return Ok(rename(&field.ident().unraw().to_string(), rule, false));
+    }
+
+    Ok(field.first_field_name()?.as_str().to_string())
+}
+
+/// Returns the name of `variant`, as `serde` would name it, or as
+/// `FromFormField` would if there are no relevant `serde` attributes.
+fn variant_name(variant: &Variant<'_>, serde: &SerdeAttr, container: &SerdeAttr) -> Result<String> {
+    if let Some(ref name) = serde.rename {
+        return Ok(name.clone());
+    }
+
+    if let Some(ref rule) = container.rename_all {
+        return Ok(rename(&variant.ident.unraw().to_string(), rule, true));
+    }
+
+    Ok(variant.first_form_field_value()?.as_str().to_string())
+}
+
+/// Returns an expression for the schema of `fields`: an object if they're
+/// named, the schema of the field if there's one unnamed field, and an array
+/// otherwise.
+fn fields_schema(fields: Fields<'_>, container: &SerdeAttr) -> Result<TokenStream> {
+    if fields.are_unnamed() {
+        let schemas = fields.iter().map(|field| {
+            let ty = field.stripped_ty();
+            quote_spanned!(field.span() => __gen.schema::<#ty>())
+        });
+
+        return match fields.count() {
+            1 => Ok(quote!(#(#schemas)*)),
+            _ => Ok(quote!(#_openapi::private::tuple(::std::vec![#(#schemas),*]))),
+        };
+    }
+
+    let mut properties = vec![];
+    for field in fields.iter() {
+        let serde = SerdeAttr::from_attrs(&field.attrs)?;
+        if serde.skip {
+            continue;
+        }
+
+        let name = field_name(&field, &serde, container)?;
+        let has_default = FieldAttr::from_attrs("field", &field.attrs)?
+            .iter()
+            .any(|attr| attr.default.is_some() || attr.default_with.is_some());
+
+        let optional = has_default || serde.default || container.default;
+        let ty = field.stripped_ty();
+        let doc = field.attrs.doc_comment();
+        properties.push(quote_spanned! { field.span() =>
+            (
+                #name,
+                __gen.schema::<#ty>(),
+                !#optional && <#ty as #_openapi::Schema>::required(),
+                #doc,
+            )
+        });
+    }
+
+    Ok(quote!(#_openapi::private::object(::std::vec![#(#properties),*])))
+}
+
+/// Returns an expression for the schema of `data`: a string enumeration if no
+/// variant has fields and, otherwise, one of the externally tagged variants.
+fn enum_schema(data: Enum<'_>, container: &SerdeAttr) -> Result<TokenStream> {
+    let mut unit_names = vec![];
+    let mut variants = vec![];
+    for variant in data.variants() {
+        let serde = SerdeAttr::from_attrs(&variant.attrs)?;
+        if serde.skip {
+            continue;
+        }
+
+        let name = variant_name(&variant, &serde, container)?;
+        let doc = variant.attrs.doc_comment();
+        if variant.fields().is_empty() {
+            unit_names.push(name.clone());
+            variants.push(quote! {
+                #_openapi::private::describe(#_openapi::private::strings(&[#name]), #doc)
+            });
+        } else {
+            let fields = fields_schema(variant.fields(), &SerdeAttr::default())?;
+            variants.push(quote! {
+                #_openapi::private::object(::std::vec![(#name, #fields, true, #doc)])
+            });
+        }
+    }
+
+    if unit_names.len() == variants.len() {
+        return Ok(quote!(#_openapi::private::strings(&[#(#unit_names),*])));
+    }
+
+    Ok(quote!(#_openapi::private::one_of(::std::vec![#(#variants),*])))
+}
+
+pub fn derive_schema(input: proc_macro::TokenStream) -> TokenStream {
+    DeriveGenerator::build_for(input, quote!(impl #_openapi::Schema))
+        .support(Support::Struct | Support::Enum | Support::Type | Support::Lifetime)
+        .validator(ValidatorBuild::new()
+            .enum_validate(|_, data| match data.variants().count() {
+                0 => Err(data.brace_token.span.error(NO_EMPTY_ENUMS)),
+                _ => Ok(())
+            })
+            .struct_validate(|_, data| match data.fields().is_empty() {
+                true => Err(data.span().error(NO_EMPTY_FIELDS)),
+                false => Ok(())
+            })
+        )
+        .type_bound(quote!(#_openapi::Schema))
+        .inner_mapper(MapperBuild::new()
+            .try_input_map(|_, input| {
+                let container = SerdeAttr::from_attrs(input.attrs())?;
+                let schema = match input {
+                    Input::Struct(data) => fields_schema(data.fields(), &container)?,
+                    Input::Enum(data) => enum_schema(data, &container)?,
+                    Input::Union(_) => unreachable!("unsupported"),
+                };
+
+                // Types with type parameters have a schema for each instance.
+                let name = match input.generics().type_params().next() {
+                    Some(_) => quote!(#_None),
+                    None => {
+                        let name = input.ident().unraw().to_string();
+                        quote!(#_Some(#_Cow::Borrowed(#name)))
+                    }
+                };
+
+                let doc = input.attrs().doc_comment();
+                Ok(quote! {
+                    fn name() -> #_Option<#_Cow<'static, str>> {
+                        #name
+                    }
+
+                    fn schema(
+                        __gen: &mut #_openapi::Generator
+                    ) -> #_openapi::private::Value {
+                        #_openapi::private::describe(#schema, #doc)
+                    }
+                })
+            })
+        )
+        .to_tokens()
+}
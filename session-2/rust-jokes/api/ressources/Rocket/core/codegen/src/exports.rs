@@ -71,6 +71,7 @@ define_exported_paths! {
     _route => ::rocket::route,
     _catcher => ::rocket::catcher,
     _sentinel => ::rocket::sentinel,
+    _openapi => ::rocket::openapi,
     _log => ::rocket::log,
     _form => ::rocket::form::prelude,
     _http => ::rocket::http,
@@ -155,6 +155,7 @@ macro_rules! route_attribute {
         ///          | TRAILING_PARAM
         ///
         /// parameter := 'rank' '=' INTEGER
+        ///            | 'timeout' '=' INTEGER
         ///            | 'format' '=' '"' MEDIA_TYPE '"'
         ///            | 'data' '=' '"' SINGLE_PARAM '"'
         ///
@@ -174,6 +175,11 @@ macro_rules! route_attribute {
         /// generic-route := METHOD ',' 'uri' '=' route
         /// ```
         ///
+        /// A `timeout` is the number of seconds the route's handler may run
+        /// before it is cancelled and the request fails with a `503 Service
+        /// Unavailable` error. It overrides the configured `timeout`; a
+        /// `timeout` of `0` disables timing out for the route.
+        ///
         /// # Typing Requirements
         ///
         /// Every identifier, except for `_`, that appears in a dynamic
@@ -258,8 +264,8 @@ macro_rules! route_attribute {
         ///   2. A static structure used by [`routes!`] to generate a [`Route`].
         ///
         ///      The static structure (and resulting [`Route`]) is populated
-        ///      with the name (the function's name), path, query, rank, and
-        ///      format from the route attribute. The handler is set to the
+        ///      with the name (the function's name), path, query, rank,
+        ///      timeout, and format from the route attribute. The handler is set to the
         ///      generated handler.
         ///
         ///   3. A macro used by [`uri!`] to type-check and generate an
@@ -853,6 +859,114 @@ pub fn derive_responder(input: TokenStream) -> TokenStream {
     emit!(derive::responder::derive_responder(input))
 }
 
+/// Derive for the [`Schema`] trait.
+///
+/// The [`Schema`] derive can be applied to structs with at least one field and
+/// to enums with at least one variant:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::openapi::Schema;
+///
+/// /// A user of the service.
+/// #[derive(Schema)]
+/// struct User<'r> {
+///     /// The user's name.
+///     name: &'r str,
+///     #[field(name = "years")]
+///     age: Option<u8>,
+///     role: Role,
+/// }
+///
+/// #[derive(Schema)]
+/// #[serde(rename_all = "lowercase")]
+/// enum Role {
+///     Admin,
+///     Member,
+/// }
+/// ```
+///
+/// # Semantics
+///
+/// The derive generates an implementation of the [`Schema`] trait describing
+/// the value as `serde` would (de)serialize it:
+///
+///   * A struct with named fields is an object with a property for each
+///     field. Fields are required unless their type isn't, as with `Option`,
+///     or they have a default.
+///   * A struct with one unnamed field has the field's schema. One with more
+///     is an array.
+///   * An enum whose variants have no fields is a string with one value per
+///     variant. Otherwise, it's one of the variants, externally tagged: a
+///     string for variants without fields and an object with a single
+///     property, named after the variant, for variants with fields.
+///
+/// Every field type must implement [`Schema`]. Doc comments on the item, its
+/// fields, and its variants become descriptions. The schema is named after
+/// the item, and so stored once and referenced, unless the item has type
+/// generics; a bound of `Schema` is added for each of them.
+///
+/// # Names
+///
+/// Fields and variants are named by the `serde` attribute parameters
+/// `rename` and, on the item, `rename_all`. Otherwise, they're named by the
+/// first `#[field(name = ...)]` or `#[field(value = ...)]` attribute, as with
+/// [`FromForm`] and [`FromFormField`], and by their identifier in the absence
+/// of either. Fields and variants with the `serde` parameter `skip`,
+/// `skip_serializing`, or `skip_deserializing` are omitted. Fields with the
+/// `serde` parameter `default` or `skip_serializing_if`, or the `field`
+/// parameter `default` or `default_with`, are optional, as are all fields of
+/// an item with the `serde` parameter `default`. All other `serde` parameters
+/// are ignored.
+///
+/// [`Schema`]: ../rocket/openapi/trait.Schema.html
+/// [`FromForm`]: ../rocket/form/trait.FromForm.html
+/// [`FromFormField`]: ../rocket/form/trait.FromFormField.html
+#[proc_macro_derive(Schema, attributes(field, serde))]
+pub fn derive_schema(input: TokenStream) -> TokenStream {
+    emit!(derive::schema::derive_schema(input))
+}
+
+/// Derive for the [`Responses`] trait.
+///
+/// The [`Responses`] derive describes the responses of a type that derives
+/// [`Responder`] and accepts the same item and variant attributes:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::openapi::Responses;
+/// use rocket::serde::json::Json;
+/// use rocket::http::ContentType;
+///
+/// #[derive(Responder, Responses)]
+/// enum Reply {
+///     Found(Json<Vec<String>>),
+///     /// There's nothing here.
+///     #[response(status = 404, content_type = "html")]
+///     Missing(String, ContentType),
+/// }
+/// ```
+///
+/// # Semantics
+///
+/// The derive generates an implementation of the [`Responses`] trait for the
+/// decorated enum or structure. Each variant or the structure contributes the
+/// responses of its _first_ field; the remaining fields are headers and don't
+/// affect the description. As such, the type of the first field must implement
+/// [`Responses`]. A `status` in the `response` attribute moves all of the
+/// field's responses to that status, and a `content_type` relabels their
+/// content with its media type. A doc comment describes the responses. If
+/// variants describe a response with the same status, the first one wins.
+///
+/// A bound of `Responses` is added for each type generic.
+///
+/// [`Responses`]: ../rocket/openapi/trait.Responses.html
+/// [`Responder`]: ../rocket/response/trait.Responder.html
+#[proc_macro_derive(Responses, attributes(response))]
+pub fn derive_responses(input: TokenStream) -> TokenStream {
+    emit!(derive::responses::derive_responses(input))
+}
+
 /// Derive for the [`UriDisplay<Query>`] trait.
 ///
 /// The [`UriDisplay<Query>`] derive can be applied to enums and structs. When
@@ -30,6 +30,10 @@ pub trait FnArgExt {
     fn wild(&self) -> Option<&syn::PatWild>;
 }
 
+pub trait AttrsExt {
+    fn doc_comment(&self) -> String;
+}
+
 #[derive(Debug)]
 pub struct Child<'a> {
     pub parent: Option<Cow<'a, syn::Type>>,
@@ -137,6 +141,25 @@ impl FnArgExt for syn::FnArg {
     }
 }
 
+impl AttrsExt for [syn::Attribute] {
+    /// Returns the lines of the `#[doc]` attributes, i.e, the doc comment,
+    /// without the leading space of each line.
+    fn doc_comment(&self) -> String {
+        let lines = self.iter()
+            .filter(|attr| attr.path.is_ident("doc"))
+            .filter_map(|attr| match attr.parse_meta() {
+                Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                    lit: syn::Lit::Str(s), ..
+                })) => Some(s.value()),
+                _ => None,
+            })
+            .map(|line| line.strip_prefix(' ').map(|l| l.to_string()).unwrap_or(line))
+            .collect::<Vec<_>>();
+
+        lines.join("\n").trim().to_string()
+    }
+}
+
 fn known_macro_inner_ty(t: &syn::TypeMacro, known: &[&str]) -> Option<syn::Type> {
     if !known.iter().any(|k| t.mac.path.last_ident().map_or(false, |i| i == k)) {
         return None;
@@ -47,7 +47,9 @@ pub mod private {
 
     pub use crate::parse::Indexed;
     pub use smallvec::{SmallVec, Array};
-    pub use crate::listener::{Incoming, Listener, Connection, bind_tcp};
+    pub use crate::listener::{Incoming, Listener, Connection, RawCertificate, bind_tcp};
+    #[cfg(unix)]
+    pub use crate::listener::{UnixListener, bind_unix};
     pub use cookie;
 }
 
@@ -34,8 +34,18 @@ pub trait Listener {
 pub trait Connection: AsyncRead + AsyncWrite {
     /// The remote address, i.e. the client's socket address.
     fn remote_addr(&self) -> Option<SocketAddr>;
+
+    /// The verified certificate chain presented by the client, if any, with the
+    /// client's end-entity certificate first.
+    fn peer_certificates(&self) -> Option<Vec<RawCertificate>> {
+        None
+    }
 }
 
+/// A DER-encoded X.509 certificate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawCertificate(pub Vec<u8>);
+
 pin_project_lite::pin_project! {
     /// This is a generic version of hyper's AddrIncoming that is intended to be
     /// usable with listeners other than a plain TCP stream, e.g. TLS and/or Unix
@@ -188,3 +198,116 @@ impl Connection for TcpStream {
         self.peer_addr().ok()
     }
 }
+
+/// A Unix domain socket listener that removes its socket file when dropped.
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct UnixListener {
+    listener: tokio::net::UnixListener,
+    path: std::path::PathBuf,
+}
+
+/// Binds a Unix domain socket listener to the socket file at `path` and
+/// returns it. If `mode` is `Some`, the socket file has permissions `mode`
+/// before it appears at `path`: it's bound in a new directory only the owner
+/// can access, next to `path`, set to `mode`, and then renamed to `path`.
+///
+/// A socket file left behind by a process that didn't exit cleanly would
+/// otherwise cause binding to fail. If a socket file exists at `path` but no
+/// process is accepting connections on it, it is removed before binding. If a
+/// process _is_ accepting connections, an `AddrInUse` error is returned.
+#[cfg(unix)]
+pub async fn bind_unix(path: &std::path::Path, mode: Option<u32>) -> io::Result<UnixListener> {
+    use std::os::unix::fs::FileTypeExt;
+
+    let metadata = std::fs::symlink_metadata(path).ok();
+    if metadata.map(|m| m.file_type().is_socket()).unwrap_or(false) {
+        match tokio::net::UnixStream::connect(path).await {
+            Ok(_) => {
+                let msg = format!("socket {} is in use", path.display());
+                return Err(io::Error::new(io::ErrorKind::AddrInUse, msg));
+            }
+            Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => {
+                debug!("removing stale socket file {}", path.display());
+                std::fs::remove_file(path)?;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    let listener = match mode {
+        Some(mode) => bind_unix_private(path, mode)?,
+        None => tokio::net::UnixListener::bind(path)?,
+    };
+
+    Ok(UnixListener { listener, path: path.to_path_buf() })
+}
+
+// Binds a socket in a new, owner-only directory next to `path`, sets its
+// permissions to `mode`, and renames it to `path` so that no one else can
+// connect before the permissions are set.
+#[cfg(unix)]
+fn bind_unix_private(
+    path: &std::path::Path,
+    mode: u32
+) -> io::Result<tokio::net::UnixListener> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let name = path.file_name().ok_or_else(|| {
+        let msg = format!("socket path {} has no file name", path.display());
+        io::Error::new(io::ErrorKind::InvalidInput, msg)
+    })?;
+
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let dir_name = format!(".{}.{}.{}", name.to_string_lossy(), std::process::id(), n);
+    let dir = path.with_file_name(dir_name);
+    std::fs::DirBuilder::new().mode(0o700).create(&dir)?;
+
+    let tmp = dir.join(name);
+    let result = tokio::net::UnixListener::bind(&tmp).and_then(|listener| {
+        std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(mode))?;
+        std::fs::rename(&tmp, path)?;
+        Ok(listener)
+    });
+
+    let _ = std::fs::remove_file(&tmp);
+    let _ = std::fs::remove_dir(&dir);
+    result
+}
+
+#[cfg(unix)]
+impl Listener for UnixListener {
+    type Connection = tokio::net::UnixStream;
+
+    fn local_addr(&self) -> Option<SocketAddr> {
+        None
+    }
+
+    fn poll_accept(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>
+    ) -> Poll<io::Result<Self::Connection>> {
+        self.listener.poll_accept(cx).map_ok(|(stream, _addr)| stream)
+    }
+}
+
+#[cfg(unix)]
+impl Drop for UnixListener {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            debug!("failed to remove socket file {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Unix domain socket peers have no IP socket address, so this is always
+/// `None`.
+#[cfg(unix)]
+impl Connection for tokio::net::UnixStream {
+    fn remote_addr(&self) -> Option<SocketAddr> {
+        None
+    }
+}
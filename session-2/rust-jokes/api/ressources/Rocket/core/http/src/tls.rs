@@ -11,7 +11,7 @@ use tokio::net::{TcpListener, TcpStream};
 use tokio_rustls::{TlsAcceptor, Accept, server::TlsStream};
 use tokio_rustls::rustls;
 
-use crate::listener::{Connection, Listener};
+use crate::listener::{Connection, Listener, RawCertificate};
 
 fn load_certs(reader: &mut dyn io::BufRead) -> io::Result<Vec<Certificate>> {
     pemfile::certs(reader)
@@ -46,6 +46,32 @@ fn load_private_key(reader: &mut dyn io::BufRead) -> io::Result<PrivateKey> {
         .map(|_| key)
 }
 
+fn load_ca_certs(reader: &mut dyn io::BufRead) -> io::Result<rustls::RootCertStore> {
+    let mut roots = rustls::RootCertStore::empty();
+    for cert in load_certs(reader)? {
+        roots.add(&cert).map_err(|e| {
+            io::Error::new(io::ErrorKind::Other, format!("invalid CA certificate: {}", e))
+        })?;
+    }
+
+    if roots.is_empty() {
+        let msg = "no valid CA certificates found; is the file malformed?";
+        return Err(io::Error::new(io::ErrorKind::Other, msg));
+    }
+
+    Ok(roots)
+}
+
+/// Client certificate authentication settings for [`bind_tls()`].
+pub struct ClientAuth<R> {
+    /// PEM-encoded CA certificates that client certificates must chain to.
+    pub ca_certs: R,
+    /// Whether clients _must_ present a valid certificate. When `false`,
+    /// clients may connect without a certificate, but any certificate that is
+    /// presented must be valid.
+    pub mandatory: bool,
+}
+
 pub struct TlsListener {
     listener: TcpListener,
     acceptor: TlsAcceptor,
@@ -94,11 +120,14 @@ impl Listener for TlsListener {
     }
 }
 
-pub async fn bind_tls<C: io::BufRead + Send, K: io::BufRead + Send>(
+pub async fn bind_tls<C, K, A>(
     address: SocketAddr,
     mut cert_chain: C,
     mut private_key: K,
-) -> io::Result<TlsListener> {
+    client_auth: Option<ClientAuth<A>>,
+) -> io::Result<TlsListener>
+    where C: io::BufRead + Send, K: io::BufRead + Send, A: io::BufRead + Send
+{
     let cert_chain = load_certs(&mut cert_chain).map_err(|e| {
         let msg = format!("malformed TLS certificate chain: {}", e);
         io::Error::new(e.kind(), msg)
@@ -109,9 +138,23 @@ pub async fn bind_tls<C: io::BufRead + Send, K: io::BufRead + Send>(
         io::Error::new(e.kind(), msg)
     })?;
 
+    let client_auth = match client_auth {
+        Some(ClientAuth { mut ca_certs, mandatory }) => {
+            let roots = load_ca_certs(&mut ca_certs).map_err(|e| {
+                let msg = format!("malformed TLS CA certificates: {}", e);
+                io::Error::new(e.kind(), msg)
+            })?;
+
+            match mandatory {
+                true => rustls::AllowAnyAuthenticatedClient::new(roots),
+                false => rustls::AllowAnyAnonymousOrAuthenticatedClient::new(roots),
+            }
+        }
+        None => rustls::NoClientAuth::new(),
+    };
+
     let listener = TcpListener::bind(address).await?;
 
-    let client_auth = rustls::NoClientAuth::new();
     let mut tls_config = ServerConfig::new(client_auth);
     let cache = rustls::ServerSessionMemoryCache::new(1024);
     tls_config.set_persistence(cache);
@@ -129,6 +172,13 @@ impl Connection for TlsStream<TcpStream> {
     fn remote_addr(&self) -> Option<SocketAddr> {
         self.get_ref().0.remote_addr()
     }
+
+    fn peer_certificates(&self) -> Option<Vec<RawCertificate>> {
+        use rustls::Session;
+
+        let certs = self.get_ref().1.get_peer_certificates()?;
+        Some(certs.into_iter().map(|cert| RawCertificate(cert.0)).collect())
+    }
 }
 
 #[cfg(test)]
@@ -172,4 +222,56 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn verify_load_ca_certs() -> io::Result<()> {
+        let ca_cert = tls_example_key!("ca_cert.pem");
+        assert!(load_ca_certs(&mut Cursor::new(ca_cert)).is_ok());
+
+        let key = tls_example_key!("rsa_sha256_key.pem");
+        assert!(load_ca_certs(&mut Cursor::new(key)).is_err());
+
+        Ok(())
+    }
+
+    async fn handshake(client_cert: bool, mandatory: bool) -> io::Result<Option<usize>> {
+        use rocket::futures::future::poll_fn;
+        use tokio_rustls::{TlsConnector, webpki::DNSNameRef};
+
+        let ca_cert = tls_example_key!("ca_cert.pem");
+        let cert = tls_example_key!("rsa_sha256_cert.pem");
+        let key = tls_example_key!("rsa_sha256_key.pem");
+
+        let client_auth = ClientAuth { ca_certs: Cursor::new(ca_cert), mandatory };
+        let addr = ([127, 0, 0, 1], 0).into();
+        let mut listener = bind_tls(addr, &cert[..], &key[..], Some(client_auth)).await?;
+        let addr = listener.local_addr().unwrap();
+
+        let mut config = rustls::ClientConfig::new();
+        config.root_store.add_pem_file(&mut Cursor::new(ca_cert)).unwrap();
+        if client_cert {
+            let certs = load_certs(&mut Cursor::new(cert))?;
+            let key = load_private_key(&mut Cursor::new(key))?;
+            config.set_single_client_cert(certs, key).unwrap();
+        }
+
+        let connector = TlsConnector::from(Arc::new(config));
+        let domain = DNSNameRef::try_from_ascii_str("localhost").unwrap();
+        let client = tokio::spawn(async move {
+            let stream = TcpStream::connect(addr).await?;
+            connector.connect(domain.to_owned().as_ref(), stream).await
+        });
+
+        let conn = poll_fn(|cx| Pin::new(&mut listener).poll_accept(cx)).await?;
+        let _ = client.await.unwrap();
+        Ok(conn.peer_certificates().map(|certs| certs.len()))
+    }
+
+    #[rocket::async_test]
+    async fn verify_client_certificates() {
+        assert_eq!(handshake(true, true).await.unwrap(), Some(1));
+        assert_eq!(handshake(true, false).await.unwrap(), Some(1));
+        assert_eq!(handshake(false, false).await.unwrap(), None);
+        assert!(handshake(false, true).await.is_err());
+    }
 }
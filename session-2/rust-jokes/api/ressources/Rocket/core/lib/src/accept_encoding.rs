@@ -0,0 +1,75 @@
+/// The content codings listed in `Accept-Encoding` headers and their q-values.
+///
+/// Used by [`FileServer`](crate::fs::FileServer) to pick precompressed files
+/// and, with the `compression` feature, by the `Compression` fairing to pick an
+/// encoding.
+pub(crate) struct AcceptEncoding<'a> {
+    codings: Vec<(&'a str, f32)>,
+}
+
+impl<'a> AcceptEncoding<'a> {
+    /// Parses the `Accept-Encoding` header values `values`. Empty items and
+    /// items with a malformed q-value are ignored.
+    pub fn parse<I: IntoIterator<Item = &'a str>>(values: I) -> Self {
+        let codings = values.into_iter()
+            .flat_map(|value| value.split(','))
+            .filter_map(parse_coding)
+            .collect();
+
+        AcceptEncoding { codings }
+    }
+
+    /// Returns the q-value of the coding known by any of `names`, compared
+    /// case-insensitively. If the coding isn't listed, returns the q-value of
+    /// the wildcard `*`, if it's listed, and `0` otherwise. When a coding is
+    /// listed more than once, the last listing wins.
+    pub fn quality(&self, names: &[&str]) -> f32 {
+        let (mut explicit, mut wildcard) = (None, None);
+        for (coding, q) in &self.codings {
+            if *coding == "*" {
+                wildcard = Some(*q);
+            } else if names.iter().any(|name| coding.eq_ignore_ascii_case(name)) {
+                explicit = Some(*q);
+            }
+        }
+
+        explicit.or(wildcard).unwrap_or(0.0)
+    }
+}
+
+/// Parses a single `coding[;q=value]` item, returning `None` if it's empty or
+/// its q-value is malformed.
+fn parse_coding(item: &str) -> Option<(&str, f32)> {
+    let mut parts = item.split(';').map(|s| s.trim());
+    let coding = parts.next().filter(|c| !c.is_empty())?;
+
+    let mut q = 1.0;
+    for param in parts {
+        if let Some((key, value)) = param.split_once('=') {
+            if key.trim().eq_ignore_ascii_case("q") {
+                q = value.trim().parse::<f32>().ok().filter(|q| (0.0..=1.0).contains(q))?;
+            }
+        }
+    }
+
+    Some((coding, q))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AcceptEncoding;
+
+    #[test]
+    fn test_quality() {
+        let accept = AcceptEncoding::parse(vec!["GZip;q=0.5, br; Q=0.8", "*;q=0.1, ,x;q=2"]);
+        assert_eq!(accept.quality(&["gzip"]), 0.5);
+        assert_eq!(accept.quality(&["br"]), 0.8);
+        assert_eq!(accept.quality(&["deflate"]), 0.1);
+        assert_eq!(accept.quality(&["x"]), 0.1);
+
+        let accept = AcceptEncoding::parse(Some("x-gzip, gzip;q=0"));
+        assert_eq!(accept.quality(&["gzip", "x-gzip"]), 0.0);
+        assert_eq!(accept.quality(&["x-gzip"]), 1.0);
+        assert_eq!(accept.quality(&["br"]), 0.0);
+    }
+}
@@ -0,0 +1,132 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize, Serializer, Deserializer};
+
+use crate::data::{ByteUnit, ToByteUnit};
+
+/// The access log configuration: the format of entries and where they're
+/// written.
+///
+/// Without a `path`, entries are written to standard output, unaffected by
+/// [`log_level`](crate::Config::log_level). The
+/// [`AccessLog`](crate::access_log::AccessLog) fairing fails to ignite if the
+/// format is invalid or the file can't be opened.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::access_log::{Config, Format};
+///
+/// let figment = rocket::Config::figment()
+///     .merge(("access_log.format", "{method} {uri} {status}"))
+///     .merge(("access_log.path", "/var/log/app/access.log"))
+///     .merge(("access_log.max_files", 10));
+///
+/// let config: Config = figment.extract_inner("access_log").unwrap();
+/// assert_eq!(config.format, Format::Custom("{method} {uri} {status}".into()));
+/// assert_eq!(config.path.unwrap(), std::path::Path::new("/var/log/app/access.log"));
+/// assert_eq!(config.max_files, 10);
+/// assert_eq!(config.max_size, Config::default().max_size);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// The format of entries. **(default: `combined`)**
+    pub format: Format,
+    /// The file entries are written to, or `None` to write them to stdout.
+    /// **(default: `None`)**
+    pub path: Option<PathBuf>,
+    /// The size after which the file is rotated; never rotated when `0`.
+    /// **(default: `100MiB`)**
+    pub max_size: ByteUnit,
+    /// The number of rotated files to keep. **(default: `5`)**
+    pub max_files: usize,
+}
+
+/// The format of access log entries.
+///
+/// Deserializes from `"common"`, `"combined"`, or, for any other string, a
+/// custom template. See the [module docs](crate::access_log#formats) for
+/// details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// The Common Log Format: `"common"`.
+    Common,
+    /// The Combined Log Format: `"combined"`.
+    Combined,
+    /// A template of `{field}` placeholders and literal text.
+    Custom(String),
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            format: Format::Combined,
+            path: None,
+            max_size: 100.mebibytes(),
+            max_files: 5,
+        }
+    }
+}
+
+impl Format {
+    /// The template of the Common Log Format.
+    pub const COMMON: &'static str =
+        r#"{client_ip} - - [{time}] "{method} {uri}" {status} {bytes}"#;
+
+    /// The template of the Combined Log Format.
+    pub const COMBINED: &'static str =
+        r#"{client_ip} - - [{time}] "{method} {uri}" {status} {bytes} "{referer}" "{user_agent}""#;
+
+    /// Returns the template of `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::access_log::Format;
+    ///
+    /// assert_eq!(Format::Common.template(), Format::COMMON);
+    /// assert_eq!(Format::Custom("{status}".into()).template(), "{status}");
+    /// ```
+    pub fn template(&self) -> &str {
+        match self {
+            Format::Common => Format::COMMON,
+            Format::Combined => Format::COMBINED,
+            Format::Custom(template) => template,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        match self {
+            Format::Common => "common",
+            Format::Combined => "combined",
+            Format::Custom(template) => template,
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl Serialize for Format {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Format {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(de)?;
+        let format = match &*string.to_ascii_lowercase() {
+            "common" => Format::Common,
+            "combined" => Format::Combined,
+            _ => Format::Custom(string),
+        };
+
+        Ok(format)
+    }
+}
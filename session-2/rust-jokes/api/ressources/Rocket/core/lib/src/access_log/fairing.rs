@@ -0,0 +1,131 @@
+use crate::{Rocket, Build, Request, Response, Data};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::request::{RequestId, Started};
+use crate::access_log::Config;
+use crate::access_log::output::{Output, Sink, RotatingFile};
+use crate::access_log::template::{Template, Entry};
+
+/// A [`Fairing`] that writes an entry to an access log for every request.
+///
+/// See the [module docs](crate::access_log) for formats and configuration
+/// details.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::access_log::AccessLog;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(AccessLog::new())
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AccessLog {
+    _private: (),
+}
+
+/// The access log's template and output, managed by the fairing.
+struct Logger {
+    template: Template,
+    output: Output,
+}
+
+impl AccessLog {
+    /// Returns an `AccessLog` fairing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::access_log::AccessLog;
+    ///
+    /// let access_log = AccessLog::new();
+    /// ```
+    pub fn new() -> Self {
+        AccessLog { _private: () }
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for AccessLog {
+    fn info(&self) -> Info {
+        Info {
+            name: "Access Log",
+            kind: Kind::Ignite | Kind::Request | Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let config = match crate::config::extract_or_default::<Config>(&rocket, "access_log") {
+            Some(config) => config,
+            None => return Err(rocket),
+        };
+
+        let template = match Template::parse(config.format.template()) {
+            Ok(template) => template,
+            Err(e) => {
+                error!("invalid access log format: {}", e);
+                return Err(rocket);
+            }
+        };
+
+        let sink = match config.path {
+            Some(path) => {
+                let max_size = config.max_size.as_u64();
+                match RotatingFile::open(path.clone(), max_size, config.max_files) {
+                    Ok(file) => Sink::File(file),
+                    Err(e) => {
+                        error!("failed to open access log {}: {}", path.display(), e);
+                        return Err(rocket);
+                    }
+                }
+            }
+            None => Sink::Stdout,
+        };
+
+        let output = match Output::spawn(sink) {
+            Ok(output) => output,
+            Err(e) => {
+                error!("failed to start the access log writer: {}", e);
+                return Err(rocket);
+            }
+        };
+
+        Ok(rocket.manage(Logger { template, output }))
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data<'_>) {
+        Started::record(req);
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let logger = match req.rocket().state::<Logger>() {
+            Some(logger) => logger,
+            None => return,
+        };
+
+        let (start, method) = match Started::of(req) {
+            Some(started) => started,
+            None => return,
+        };
+
+        let uri = req.uri().to_string();
+        let entry = Entry {
+            client_ip: req.client_ip(),
+            time: time::OffsetDateTime::now_utc(),
+            method: method.as_str(),
+            uri: &uri,
+            status: res.status().code,
+            bytes: res.body_mut().size().await,
+            duration: start.elapsed(),
+            referer: req.headers().get_one("Referer"),
+            user_agent: req.headers().get_one("User-Agent"),
+            request_id: req.local_cache(|| RequestId::resolve(req)).as_str(),
+        };
+
+        if let Err(e) = logger.output.write_line(logger.template.render(&entry)) {
+            error!("failed to write to access log: {}", e);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Access logs in the Common, Combined, or a custom log format.
+//!
+//! The [`AccessLog`] fairing writes one line per request to stdout or to a
+//! file, which it rotates as it grows. Entries are written once the response
+//! is ready, independently of Rocket's own logging and [`LogLevel`], by a
+//! dedicated thread, so that responses never wait on the log. Should the thread
+//! fall thousands of entries behind, new entries are dropped with an error.
+//!
+//! [`LogLevel`]: crate::config::LogLevel
+//!
+//! # Enabling
+//!
+//! This module is only available when the `access-log` feature is enabled:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket = { version = "0.5.0-rc.1", features = ["access-log"] }
+//! ```
+//!
+//! # Usage
+//!
+//! Attach the fairing:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::access_log::AccessLog;
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build().attach(AccessLog::new())
+//! }
+//! ```
+//!
+//! # Formats
+//!
+//! Entries are formatted with a [`Format`], which is either `common`, the
+//! Common Log Format:
+//!
+//! ```text
+//! 127.0.0.1 - - [06/Oct/2021:13:55:36 +0000] "GET /hello?name=Bob" 200 11
+//! ```
+//!
+//! `combined`, the Combined Log Format, which adds the `Referer` and
+//! `User-Agent` of the request:
+//!
+//! ```text
+//! 127.0.0.1 - - [06/Oct/2021:13:55:36 +0000] "GET /hello?name=Bob" 200 11 "-" "curl/7.79"
+//! ```
+//!
+//! or a custom template, in which `{field}` placeholders are replaced by the
+//! following values, and `{{` and `}}` by `{` and `}`:
+//!
+//!   * `client_ip`: the client's IP address, as reported by
+//!     [`Request::client_ip()`](crate::Request::client_ip()).
+//!   * `time`: when the response was ready, as in `06/Oct/2021:13:55:36 +0000`.
+//!   * `method`: the request method.
+//!   * `uri`: the request URI.
+//!   * `status`: the response status code.
+//!   * `bytes`: the size of the response body, if known in advance.
+//!   * `duration_ms`: the milliseconds between the arrival of the request and
+//!     the response being ready, as in `1.500`.
+//!   * `referer`: the `Referer` header of the request.
+//!   * `user_agent`: the `User-Agent` header of the request.
+//!   * `request_id`: the [`RequestId`](crate::request::RequestId).
+//!
+//! Values that aren't known or are empty, including a `bytes` of `0`, are
+//! written as `-`. Quotes, backslashes, and control characters in values sent
+//! by the client are escaped. As Rocket doesn't expose the HTTP version of
+//! requests, the request line of the predefined formats omits it.
+//!
+//! # Configuration
+//!
+//! The format, the file to write to, if any, and when and how to rotate it
+//! are read from the `access_log` configuration parameter into a [`Config`].
+//! The defaults are:
+//!
+//! ```toml
+//! [default.access_log]
+//! format = "combined"
+//! # path = "/var/log/app/access.log" (default: stdout)
+//! max_size = "100MiB"
+//! max_files = 5
+//! ```
+//!
+//! A custom format is configured as a template:
+//!
+//! ```toml
+//! [default.access_log]
+//! format = "{request_id} {method} {uri} {status} {duration_ms}ms"
+//! ```
+//!
+//! When the file at `path` would grow past `max_size`, it's renamed with a
+//! suffix of `.1` and a new file is started. Previously rotated files are
+//! renamed in turn, from `.1` to `.2` and so on, and are removed past
+//! `max_files`.
+
+mod config;
+mod template;
+mod output;
+mod fairing;
+
+pub use self::config::{Config, Format};
+pub use self::fairing::AccessLog;
@@ -0,0 +1,164 @@
+use std::io::{self, Write};
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+
+/// The number of entries that may be waiting to be written before new entries
+/// are dropped.
+const BACKLOG: usize = 4096;
+
+/// Where access log entries are written.
+pub(crate) enum Sink {
+    Stdout,
+    File(RotatingFile),
+}
+
+/// A file that, once it would grow past `max_size`, is renamed to `path.1`,
+/// after `path.1` is renamed to `path.2`, and so on, up to `path.max_files`.
+pub(crate) struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size: u64,
+    max_files: usize,
+}
+
+/// The access log's output: a dedicated thread that writes entries to a
+/// [`Sink`], so that responding to a request never waits on I/O. Dropping the
+/// output waits for pending entries to be written.
+pub(crate) struct Output {
+    tx: Option<SyncSender<String>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Sink {
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        match self {
+            Sink::Stdout => writeln!(io::stdout().lock(), "{}", line),
+            Sink::File(file) => file.write_line(line),
+        }
+    }
+}
+
+impl Output {
+    /// Spawns the thread writing entries to `sink`.
+    pub fn spawn(mut sink: Sink) -> io::Result<Output> {
+        let (tx, rx) = mpsc::sync_channel::<String>(BACKLOG);
+        let thread = thread::Builder::new()
+            .name("rocket-access-log".into())
+            .spawn(move || {
+                for line in rx {
+                    if let Err(e) = sink.write_line(&line) {
+                        error!("failed to write to access log: {}", e);
+                    }
+                }
+            })?;
+
+        Ok(Output { tx: Some(tx), thread: Some(thread) })
+    }
+
+    /// Queues `line` to be written, followed by a newline. If the writer has
+    /// fallen `BACKLOG` entries behind, `line` is dropped and an error is
+    /// returned.
+    pub fn write_line(&self, line: String) -> io::Result<()> {
+        let tx = self.tx.as_ref().expect("sender is only taken on drop");
+        tx.try_send(line).map_err(|e| match e {
+            TrySendError::Full(_) => io::Error::new(io::ErrorKind::WouldBlock,
+                "writer is behind; entry dropped"),
+            TrySendError::Disconnected(_) => io::Error::new(io::ErrorKind::BrokenPipe,
+                "writer has stopped"),
+        })
+    }
+}
+
+impl Drop for Output {
+    fn drop(&mut self) {
+        // Closing the channel stops the thread once it's written every entry.
+        self.tx.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn open(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+impl RotatingFile {
+    /// Opens or creates the file at `path` for appending.
+    pub fn open(path: PathBuf, max_size: u64, max_files: usize) -> io::Result<RotatingFile> {
+        let file = open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(RotatingFile { path, file, size, max_size, max_files })
+    }
+
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", n));
+        path.into()
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        let ignore_missing = |result: io::Result<()>| match result {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        };
+
+        match self.max_files {
+            0 => ignore_missing(fs::remove_file(&self.path))?,
+            n => {
+                for i in (1..n).rev() {
+                    ignore_missing(fs::rename(self.rotated(i), self.rotated(i + 1)))?;
+                }
+
+                ignore_missing(fs::rename(&self.path, self.rotated(1)))?;
+            }
+        }
+
+        self.file = open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.max_size > 0 && self.size > 0 && self.size + len > self.max_size {
+            self.rotate()?;
+        }
+
+        writeln!(self.file, "{}", line)?;
+        self.size += len;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("access.log");
+        let mut file = RotatingFile::open(path.clone(), 8, 2).unwrap();
+        for line in &["one", "two", "three", "four", "five"] {
+            file.write_line(line).unwrap();
+        }
+
+        let read = |name: &str| fs::read_to_string(dir.path().join(name)).unwrap();
+        assert_eq!(read("access.log"), "five\n");
+        assert_eq!(read("access.log.1"), "four\n");
+        assert_eq!(read("access.log.2"), "three\n");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 3);
+
+        // Existing files are appended to, and are removed without `max_files`.
+        let mut file = RotatingFile::open(path, 10, 0).unwrap();
+        file.write_line("six").unwrap();
+        assert_eq!(read("access.log"), "five\nsix\n");
+        file.write_line("seven").unwrap();
+        assert_eq!(read("access.log"), "seven\n");
+        assert_eq!(read("access.log.1"), "four\n");
+    }
+}
@@ -0,0 +1,204 @@
+use std::fmt::Write;
+use std::net::IpAddr;
+use std::time::Duration;
+
+use time::OffsetDateTime;
+
+/// A field of an access log entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    ClientIp,
+    Time,
+    Method,
+    Uri,
+    Status,
+    Bytes,
+    DurationMs,
+    Referer,
+    UserAgent,
+    RequestId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Field(Field),
+}
+
+/// A parsed access log template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Template(Vec<Segment>);
+
+/// The values an access log entry is rendered from.
+pub(crate) struct Entry<'a> {
+    pub client_ip: Option<IpAddr>,
+    pub time: OffsetDateTime,
+    pub method: &'a str,
+    pub uri: &'a str,
+    pub status: u16,
+    pub bytes: Option<usize>,
+    pub duration: Duration,
+    pub referer: Option<&'a str>,
+    pub user_agent: Option<&'a str>,
+    pub request_id: &'a str,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Field> {
+        let field = match name {
+            "client_ip" => Field::ClientIp,
+            "time" => Field::Time,
+            "method" => Field::Method,
+            "uri" => Field::Uri,
+            "status" => Field::Status,
+            "bytes" => Field::Bytes,
+            "duration_ms" => Field::DurationMs,
+            "referer" => Field::Referer,
+            "user_agent" => Field::UserAgent,
+            "request_id" => Field::RequestId,
+            _ => return None,
+        };
+
+        Some(field)
+    }
+}
+
+impl Template {
+    /// Parses `template`, returning an error message if it's invalid.
+    pub fn parse(template: &str) -> Result<Template, String> {
+        let mut segments = vec![];
+        let mut literal = String::new();
+        let mut chars = template.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.as_str().starts_with('{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.as_str().starts_with('}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let rest = chars.as_str();
+                    let end = rest.find('}').ok_or("unclosed `{` in template")?;
+                    let field = Field::parse(&rest[..end])
+                        .ok_or_else(|| format!("unknown field `{}` in template", &rest[..end]))?;
+
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+
+                    segments.push(Segment::Field(field));
+                    chars = rest[(end + 1)..].chars();
+                }
+                '}' => return Err("unmatched `}` in template".into()),
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Template(segments))
+    }
+
+    /// Renders `entry` with this template. Unknown values are rendered as `-`.
+    /// Quotes, backslashes, and control characters in values from the request
+    /// are escaped.
+    pub fn render(&self, entry: &Entry<'_>) -> String {
+        let mut out = String::with_capacity(256);
+        for segment in &self.0 {
+            let field = match segment {
+                Segment::Literal(literal) => { out.push_str(literal); continue; }
+                Segment::Field(field) => field,
+            };
+
+            match field {
+                Field::ClientIp => {
+                    escape(&mut out, entry.client_ip.map(|ip| ip.to_string()).as_deref())
+                }
+                Field::Time => out.push_str(&entry.time.format("%d/%b/%Y:%H:%M:%S %z")),
+                Field::Method => out.push_str(entry.method),
+                Field::Uri => escape(&mut out, Some(entry.uri)),
+                Field::Status => out.push_str(&entry.status.to_string()),
+                Field::Bytes => {
+                    let bytes = entry.bytes.filter(|bytes| *bytes > 0);
+                    escape(&mut out, bytes.map(|bytes| bytes.to_string()).as_deref())
+                }
+                Field::DurationMs => {
+                    let millis = entry.duration.as_secs_f64() * 1000.0;
+                    out.push_str(&format!("{:.3}", millis))
+                }
+                Field::Referer => escape(&mut out, entry.referer),
+                Field::UserAgent => escape(&mut out, entry.user_agent),
+                Field::RequestId => escape(&mut out, Some(entry.request_id)),
+            }
+        }
+
+        out
+    }
+}
+
+// Writes `value`, or `-` if there is none, to `out` with quotes, backslashes,
+// and control characters escaped.
+fn escape(out: &mut String, value: Option<&str>) {
+    let value = match value {
+        Some(value) if !value.is_empty() => value,
+        _ => return out.push('-'),
+    };
+
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => { let _ = write!(out, "\\x{:02x}", c as u32); }
+            c => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access_log::Format;
+
+    fn entry() -> Entry<'static> {
+        Entry {
+            client_ip: Some([127, 0, 0, 1].into()),
+            time: OffsetDateTime::from_unix_timestamp(1633528536),
+            method: "GET",
+            uri: "/a?b=\"c\"",
+            status: 200,
+            bytes: Some(2326),
+            duration: Duration::from_micros(1500),
+            referer: None,
+            user_agent: Some("curl/7.79"),
+            request_id: "abc",
+        }
+    }
+
+    #[test]
+    fn test_formats() {
+        let common = Template::parse(Format::Common.template()).unwrap().render(&entry());
+        let expected = r#"127.0.0.1 - - [06/Oct/2021:13:55:36 +0000] "GET /a?b=\"c\"" 200 2326"#;
+        assert_eq!(common, expected);
+
+        let combined = Template::parse(Format::Combined.template()).unwrap().render(&entry());
+        assert_eq!(combined, format!(r#"{} "-" "curl/7.79""#, expected));
+    }
+
+    #[test]
+    fn test_custom_template() {
+        let template = "{{{request_id}}} {method}:{duration_ms}ms {bytes}";
+        let template = Template::parse(template).unwrap();
+        let entry = Entry { bytes: None, client_ip: None, ..entry() };
+        assert_eq!(template.render(&entry), "{abc} GET:1.500ms -");
+
+        assert_eq!(Template::parse("").unwrap().render(&entry), "");
+        assert!(Template::parse("{method").is_err());
+        assert!(Template::parse("{host}").is_err());
+        assert!(Template::parse("method}").is_err());
+    }
+}
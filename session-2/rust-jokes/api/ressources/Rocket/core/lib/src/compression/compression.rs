@@ -0,0 +1,252 @@
+use async_compression::Level;
+use async_compression::tokio::bufread::{BrotliEncoder, DeflateEncoder, GzipEncoder};
+use tokio::io::BufReader;
+
+use crate::{Request, Response};
+use crate::fairing::{Fairing, Info, Kind};
+use crate::http::{MediaType, Status, uncased::UncasedStr};
+use crate::compression::Encoding;
+
+/// A [`Fairing`] that compresses outgoing response bodies.
+///
+/// # Usage
+///
+/// To use `Compression`, construct an instance, optionally configure it, and
+/// attach it to your application's instance of `Rocket`:
+///
+/// ```rust
+/// use rocket::compression::{Compression, Level};
+/// use rocket::http::MediaType;
+///
+/// // Compress with the default settings.
+/// let compression = Compression::default();
+///
+/// // Compress as quickly as possible, only bodies of at least 4KiB, and never
+/// // compress PDFs.
+/// let compression = Compression::default()
+///     .level(Level::Fastest)
+///     .min_size(4096)
+///     .exclude(MediaType::PDF);
+///
+/// rocket::build()
+///     // ...
+///     .attach(compression)
+/// # ;
+/// ```
+///
+/// # Behavior
+///
+/// The fairing compresses both sized and streamed response bodies, streaming
+/// the compressed bytes as they're produced. A compressed body has no preset
+/// size and is thus sent with chunked transfer encoding. The encoding is
+/// chosen via [`Encoding::negotiate()`] from the request's `Accept-Encoding`
+/// header and written to the response's `Content-Encoding` header.
+///
+/// A response is left untouched if any of the following hold:
+///
+///   * The response has no body or a sized body smaller than
+///     [`min_size`](Compression::min_size()).
+///   * The response already has a `Content-Encoding` or `Content-Range`
+///     header or its status is informational, `204 No Content`, `206 Partial
+///     Content`, or `304 Not Modified`.
+///   * The response's `Content-Type` matches an excluded media type. By
+///     default, media types that are already compressed, such as images,
+///     audio, video, fonts, and archives, are excluded, as is
+///     `text/event-stream`, which must not be buffered.
+///
+/// Every other response gets `Accept-Encoding` added to its `Vary` header,
+/// whether or not it is compressed, so that caches don't serve a compressed
+/// body to a client that can't decode it. When a response with a strong
+/// `ETag` is compressed, the `ETag` is made weak, as the compressed bytes
+/// differ from those the tag was computed over.
+pub struct Compression {
+    level: Level,
+    min_size: usize,
+    exclusions: Vec<MediaType>,
+}
+
+impl Default for Compression {
+    /// Returns a `Compression` fairing with the default compression level, a
+    /// minimum body size of `1024` bytes, and the default exclusions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compression::Compression;
+    ///
+    /// let compression = Compression::default();
+    /// ```
+    fn default() -> Self {
+        Compression {
+            level: Level::Default,
+            min_size: 1024,
+            exclusions: vec![
+                MediaType::PNG, MediaType::GIF, MediaType::JPEG, MediaType::WEBP,
+                MediaType::AVIF, MediaType::WEBM, MediaType::WEBA, MediaType::OGG,
+                MediaType::FLAC, MediaType::AAC, MediaType::MPEG, MediaType::MOV,
+                MediaType::MP4, MediaType::WOFF, MediaType::WOFF2, MediaType::GZIP,
+                MediaType::ZIP, MediaType::EventStream,
+            ],
+        }
+    }
+}
+
+impl Compression {
+    /// Sets the compression level to `level`. The default is
+    /// [`Level::Default`], which defers to each algorithm's own default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compression::{Compression, Level};
+    ///
+    /// let compression = Compression::default().level(Level::Best);
+    /// ```
+    pub fn level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Sets the minimum size, in bytes, of a sized body for it to be
+    /// compressed. Streamed bodies are always compressed. The default is
+    /// `1024`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compression::Compression;
+    ///
+    /// // Compress every eligible body, no matter how small.
+    /// let compression = Compression::default().min_size(0);
+    /// ```
+    pub fn min_size(mut self, min_size: usize) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Excludes responses with media type `media_type` from compression. A
+    /// `*` in the top-level or sub-level type matches any type. Parameters
+    /// are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compression::Compression;
+    /// use rocket::http::MediaType;
+    ///
+    /// let compression = Compression::default()
+    ///     .exclude(MediaType::PDF)
+    ///     .exclude(MediaType::new("image", "*"));
+    /// ```
+    pub fn exclude(mut self, media_type: MediaType) -> Self {
+        self.exclusions.push(media_type);
+        self
+    }
+
+    /// Returns `true` if responses of media type `media_type` are excluded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compression::Compression;
+    /// use rocket::http::MediaType;
+    ///
+    /// let compression = Compression::default();
+    /// assert!(compression.is_excluded(&MediaType::PNG));
+    /// assert!(!compression.is_excluded(&MediaType::HTML));
+    ///
+    /// let compression = compression.exclude(MediaType::new("text", "*"));
+    /// assert!(compression.is_excluded(&MediaType::HTML));
+    /// ```
+    pub fn is_excluded(&self, media_type: &MediaType) -> bool {
+        fn matches(pattern: &UncasedStr, value: &UncasedStr) -> bool {
+            pattern == "*" || pattern == value
+        }
+
+        self.exclusions.iter().any(|excluded| {
+            matches(excluded.top(), media_type.top()) && matches(excluded.sub(), media_type.sub())
+        })
+    }
+
+    /// Returns `true` if `response` is eligible for compression, ignoring the
+    /// size of its body.
+    fn is_eligible(&self, response: &Response<'_>) -> bool {
+        let status = response.status();
+        if response.body().is_none()
+            || status.code < 200
+            || status == Status::NoContent
+            || status == Status::PartialContent
+            || status == Status::NotModified
+        {
+            return false;
+        }
+
+        let headers = response.headers();
+        if headers.contains("Content-Encoding") || headers.contains("Content-Range") {
+            return false;
+        }
+
+        match response.content_type() {
+            Some(content_type) => !self.is_excluded(content_type.media_type()),
+            None => true,
+        }
+    }
+}
+
+/// Adds `Accept-Encoding` to the `Vary` header of `response` unless it's
+/// already listed or the response varies on everything.
+fn add_vary(response: &mut Response<'_>) {
+    let listed = response.headers().get("Vary")
+        .flat_map(|value| value.split(','))
+        .map(|name| UncasedStr::new(name.trim()))
+        .any(|name| name == "*" || name == "Accept-Encoding");
+
+    if !listed {
+        response.adjoin_raw_header("Vary", "Accept-Encoding");
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Compression {
+    fn info(&self) -> Info {
+        Info {
+            name: "Compression",
+            kind: Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        if !self.is_eligible(res) {
+            return;
+        }
+
+        if let Some(size) = res.body_mut().size().await {
+            if size < self.min_size {
+                return;
+            }
+        }
+
+        add_vary(res);
+        let encoding = match Encoding::negotiate(req.headers().get("Accept-Encoding")) {
+            Some(encoding) => encoding,
+            None => return,
+        };
+
+        let body = res.body_mut().take();
+        let max_chunk_size = body.max_chunk_size();
+        let reader = BufReader::new(body);
+        let level = self.level;
+        match encoding {
+            Encoding::Brotli => res.set_streamed_body(BrotliEncoder::with_quality(reader, level)),
+            Encoding::Gzip => res.set_streamed_body(GzipEncoder::with_quality(reader, level)),
+            Encoding::Deflate => res.set_streamed_body(DeflateEncoder::with_quality(reader, level)),
+        }
+
+        res.body_mut().set_max_chunk_size(max_chunk_size);
+        res.set_raw_header("Content-Encoding", encoding.as_str());
+        if let Some(etag) = res.headers().get_one("ETag").filter(|t| !t.starts_with("W/")) {
+            let weak = format!("W/{}", etag);
+            res.set_raw_header("ETag", weak);
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+
+use crate::accept_encoding::AcceptEncoding;
+
+/// A content encoding supported by the [`Compression`] fairing.
+///
+/// Variants are declared in order of preference: when a client accepts
+/// several encodings with the same q-value, the one declared first is chosen.
+///
+/// [`Compression`]: crate::compression::Compression
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// The `br` encoding.
+    Brotli,
+    /// The `gzip` encoding.
+    Gzip,
+    /// The `deflate` encoding.
+    Deflate,
+}
+
+impl Encoding {
+    /// All supported encodings, in order of preference.
+    pub const ALL: [Encoding; 3] = [Encoding::Brotli, Encoding::Gzip, Encoding::Deflate];
+
+    /// Returns the name of the encoding as it appears in the `Accept-Encoding`
+    /// and `Content-Encoding` headers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compression::Encoding;
+    ///
+    /// assert_eq!(Encoding::Brotli.as_str(), "br");
+    /// assert_eq!(Encoding::Gzip.as_str(), "gzip");
+    /// assert_eq!(Encoding::Deflate.as_str(), "deflate");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Encoding::Brotli => "br",
+            Encoding::Gzip => "gzip",
+            Encoding::Deflate => "deflate",
+        }
+    }
+
+    /// Returns the most preferred encoding acceptable to a client that sent
+    /// `Accept-Encoding` header values `accept`, or `None` if none of the
+    /// supported encodings are acceptable.
+    ///
+    /// An encoding is acceptable if it, or the wildcard `*`, is listed with a
+    /// nonzero q-value. The encoding with the highest q-value wins. Ties are
+    /// broken in favor of the encoding declared first in [`Encoding::ALL`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compression::Encoding;
+    ///
+    /// let encoding = Encoding::negotiate(["gzip, deflate, br"]);
+    /// assert_eq!(encoding, Some(Encoding::Brotli));
+    ///
+    /// let encoding = Encoding::negotiate(["br;q=0.5, gzip;q=0.8"]);
+    /// assert_eq!(encoding, Some(Encoding::Gzip));
+    ///
+    /// let encoding = Encoding::negotiate(["*, br;q=0"]);
+    /// assert_eq!(encoding, Some(Encoding::Gzip));
+    ///
+    /// let encoding = Encoding::negotiate(["identity"]);
+    /// assert_eq!(encoding, None);
+    /// ```
+    pub fn negotiate<'a, I>(accept: I) -> Option<Encoding>
+        where I: IntoIterator<Item = &'a str>
+    {
+        let accept = AcceptEncoding::parse(accept);
+        let mut best: Option<(Encoding, f32)> = None;
+        for encoding in Encoding::ALL.iter() {
+            let q = accept.quality(encoding.names());
+            if q > 0.0 && !matches!(best, Some((_, best_q)) if q <= best_q) {
+                best = Some((*encoding, q));
+            }
+        }
+
+        best.map(|(encoding, _)| encoding)
+    }
+
+    /// The names of the encoding in `Accept-Encoding` headers.
+    fn names(&self) -> &'static [&'static str] {
+        match self {
+            Encoding::Brotli => &["br"],
+            Encoding::Gzip => &["gzip", "x-gzip"],
+            Encoding::Deflate => &["deflate"],
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Encoding::{self, *};
+
+    fn negotiate(accept: &str) -> Option<Encoding> {
+        Encoding::negotiate(Some(accept))
+    }
+
+    #[test]
+    fn test_negotiate_preference() {
+        assert_eq!(negotiate("gzip"), Some(Gzip));
+        assert_eq!(negotiate("deflate"), Some(Deflate));
+        assert_eq!(negotiate("br"), Some(Brotli));
+        assert_eq!(negotiate("deflate, gzip"), Some(Gzip));
+        assert_eq!(negotiate("deflate, gzip, br"), Some(Brotli));
+        assert_eq!(negotiate("*"), Some(Brotli));
+        assert_eq!(negotiate("x-gzip"), Some(Gzip));
+        assert_eq!(negotiate("GZip, DEFLATE"), Some(Gzip));
+        assert_eq!(Encoding::negotiate(vec!["deflate", "gzip"]), Some(Gzip));
+    }
+
+    #[test]
+    fn test_negotiate_qvalues() {
+        assert_eq!(negotiate("br;q=0.1, gzip;q=0.5, deflate"), Some(Deflate));
+        assert_eq!(negotiate("br;q=0.9, gzip"), Some(Gzip));
+        assert_eq!(negotiate("br; q=1.0, gzip;q=1"), Some(Brotli));
+        assert_eq!(negotiate("br;Q=0.2, gzip;q=0.1"), Some(Brotli));
+        assert_eq!(negotiate("*;q=0.5, deflate;q=0.6"), Some(Deflate));
+        assert_eq!(negotiate("*;q=0.5, br;q=0"), Some(Gzip));
+    }
+
+    #[test]
+    fn test_negotiate_none() {
+        assert_eq!(negotiate(""), None);
+        assert_eq!(negotiate("identity"), None);
+        assert_eq!(negotiate("compress, zstd"), None);
+        assert_eq!(negotiate("gzip;q=0"), None);
+        assert_eq!(negotiate("*;q=0"), None);
+        assert_eq!(negotiate("*;q=0, identity"), None);
+        assert_eq!(negotiate("gzip;q=2"), None);
+        assert_eq!(negotiate("gzip;q=abc"), None);
+        assert_eq!(Encoding::negotiate(None), None);
+    }
+}
@@ -0,0 +1,56 @@
+//! Automatic response body compression.
+//!
+//! The [`Compression`] fairing compresses outgoing response bodies with the
+//! best encoding the client accepts, as indicated by the request's
+//! [Accept-Encoding] header and its q-values. The following encodings are
+//! supported, listed in order of preference when a client accepts several
+//! encodings equally:
+//!
+//! | Encoding          | `Content-Encoding` | Variant              |
+//! |-------------------|--------------------|----------------------|
+//! | [Brotli]          | `br`               | [`Encoding::Brotli`] |
+//! | [Gzip]            | `gzip`             | [`Encoding::Gzip`]   |
+//! | [Deflate]         | `deflate`          | [`Encoding::Deflate`]|
+//!
+//! [Accept-Encoding]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Accept-Encoding
+//! [Brotli]: https://en.wikipedia.org/wiki/Brotli
+//! [Gzip]: https://en.wikipedia.org/wiki/Gzip
+//! [Deflate]: https://en.wikipedia.org/wiki/DEFLATE
+//!
+//! # Enabling
+//!
+//! This module is only available when the `compression` feature is enabled.
+//! Enable it in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket]
+//! version = "0.5.0-rc.1"
+//! features = ["compression"]
+//! ```
+//!
+//! # Usage
+//!
+//! Attach an instance of [`Compression`] to compress all eligible responses:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::compression::Compression;
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build().attach(Compression::default())
+//! }
+//! ```
+//!
+//! The compression [`Level`], the minimum size of a body to compress, and the
+//! media types to leave untouched can all be configured. See [`Compression`]
+//! for details.
+
+mod compression;
+mod encoding;
+
+pub use self::compression::Compression;
+pub use self::encoding::Encoding;
+
+#[doc(inline)]
+pub use async_compression::Level;
@@ -0,0 +1,218 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use serde::{Deserialize, Serialize, Serializer};
+use serde::de::{self, Deserializer};
+
+/// The address to serve on: an IP address or a Unix domain socket path.
+///
+/// # Deserialization
+///
+/// An `Address` deserializes from a string. If the string begins with
+/// `unix:`, the remainder of the string is the path to a Unix domain socket
+/// file, and the value is [`Address::Unix`]. Otherwise, the string must be a
+/// valid IPv4 or IPv6 address, and the value is [`Address::Ip`]. An `Address`
+/// serializes to the same string it deserializes from.
+///
+/// When serving on a Unix domain socket, the [`Config::port`] is ignored, and
+/// the socket file's permissions can be set via [`Config::socket_mode`].
+/// Unix domain sockets are only supported on Unix platforms; launching with
+/// an [`Address::Unix`] on any other platform results in a bind error.
+///
+/// [`Config::port`]: crate::Config::port
+/// [`Config::socket_mode`]: crate::Config::socket_mode
+///
+/// # Examples
+///
+/// ```rust
+/// # use rocket::figment::{Figment, providers::{Format, Toml}};
+/// use std::net::Ipv4Addr;
+/// use rocket::config::{Config, Address};
+///
+/// // If these are the contents of `Rocket.toml`...
+/// # let toml = Toml::string(r#"
+/// [default]
+/// address = "127.0.0.1"
+/// # "#).nested();
+///
+/// // The config parses as follows:
+/// # let config = Config::from(Figment::from(Config::debug_default()).merge(toml));
+/// assert_eq!(config.address, Ipv4Addr::LOCALHOST);
+///
+/// // If these are the contents of `Rocket.toml`...
+/// # let toml = Toml::string(r#"
+/// [default]
+/// address = "unix:/run/app.sock"
+/// # "#).nested();
+///
+/// // The config parses as follows:
+/// # let config = Config::from(Figment::from(Config::debug_default()).merge(toml));
+/// assert_eq!(config.address, Address::Unix("/run/app.sock".into()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Address {
+    /// An IPv4 or IPv6 address to serve on along with [`Config::port`].
+    ///
+    /// [`Config::port`]: crate::Config::port
+    Ip(IpAddr),
+    /// The path to a Unix domain socket file to serve on.
+    Unix(PathBuf),
+}
+
+impl Address {
+    /// The prefix that identifies a Unix domain socket address: `unix:`.
+    pub const UNIX_PREFIX: &'static str = "unix:";
+
+    /// Returns the IP address if `self` is an [`Address::Ip`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::net::Ipv4Addr;
+    /// use rocket::config::Address;
+    ///
+    /// let address = Address::from(Ipv4Addr::LOCALHOST);
+    /// assert_eq!(address.ip(), Some(Ipv4Addr::LOCALHOST.into()));
+    ///
+    /// let address = Address::Unix("/run/app.sock".into());
+    /// assert_eq!(address.ip(), None);
+    /// ```
+    pub fn ip(&self) -> Option<IpAddr> {
+        match self {
+            Address::Ip(ip) => Some(*ip),
+            Address::Unix(_) => None,
+        }
+    }
+
+    /// Returns the socket file path if `self` is an [`Address::Unix`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::Path;
+    /// use std::net::Ipv4Addr;
+    /// use rocket::config::Address;
+    ///
+    /// let address = Address::Unix("/run/app.sock".into());
+    /// assert_eq!(address.unix(), Some(Path::new("/run/app.sock")));
+    ///
+    /// let address = Address::from(Ipv4Addr::LOCALHOST);
+    /// assert_eq!(address.unix(), None);
+    /// ```
+    pub fn unix(&self) -> Option<&Path> {
+        match self {
+            Address::Ip(_) => None,
+            Address::Unix(path) => Some(path),
+        }
+    }
+}
+
+impl std::str::FromStr for Address {
+    type Err = std::net::AddrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix(Address::UNIX_PREFIX) {
+            Some(path) => Ok(Address::Unix(path.into())),
+            None => s.parse().map(Address::Ip),
+        }
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Address::Ip(ip) => ip.fmt(f),
+            Address::Unix(path) => write!(f, "{}{}", Address::UNIX_PREFIX, path.display()),
+        }
+    }
+}
+
+impl From<IpAddr> for Address {
+    fn from(ip: IpAddr) -> Self {
+        Address::Ip(ip)
+    }
+}
+
+impl From<Ipv4Addr> for Address {
+    fn from(ip: Ipv4Addr) -> Self {
+        Address::Ip(ip.into())
+    }
+}
+
+impl From<Ipv6Addr> for Address {
+    fn from(ip: Ipv6Addr) -> Self {
+        Address::Ip(ip.into())
+    }
+}
+
+/// Converts an [`Address::Ip`] into its IP address. Fails with the address
+/// itself for an [`Address::Unix`].
+///
+/// # Example
+///
+/// ```rust
+/// use std::convert::TryFrom;
+/// use std::net::{IpAddr, Ipv4Addr};
+/// use rocket::config::Address;
+///
+/// let ip = IpAddr::try_from(Address::from(Ipv4Addr::LOCALHOST));
+/// assert_eq!(ip, Ok(Ipv4Addr::LOCALHOST.into()));
+///
+/// let address = Address::Unix("/run/app.sock".into());
+/// assert_eq!(IpAddr::try_from(address.clone()), Err(address));
+/// ```
+impl std::convert::TryFrom<Address> for IpAddr {
+    type Error = Address;
+
+    fn try_from(address: Address) -> Result<Self, Self::Error> {
+        match address {
+            Address::Ip(ip) => Ok(ip),
+            address@Address::Unix(_) => Err(address),
+        }
+    }
+}
+
+impl PartialEq<IpAddr> for Address {
+    fn eq(&self, other: &IpAddr) -> bool {
+        self.ip() == Some(*other)
+    }
+}
+
+impl PartialEq<Ipv4Addr> for Address {
+    fn eq(&self, other: &Ipv4Addr) -> bool {
+        self.ip() == Some((*other).into())
+    }
+}
+
+impl PartialEq<Ipv6Addr> for Address {
+    fn eq(&self, other: &Ipv6Addr) -> bool {
+        self.ip() == Some((*other).into())
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Address;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("an IP address or `unix:` prefixed socket path")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        de.deserialize_str(Visitor)
+    }
+}
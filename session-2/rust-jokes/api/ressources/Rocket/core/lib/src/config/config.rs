@@ -1,5 +1,5 @@
 use std::path::PathBuf;
-use std::net::{IpAddr, Ipv4Addr};
+use std::net::Ipv4Addr;
 
 use figment::{Figment, Profile, Provider, Metadata, error::Result};
 use figment::providers::{Serialized, Env, Toml, Format};
@@ -7,9 +7,10 @@ use figment::value::{Map, Dict};
 use serde::{Deserialize, Serialize};
 use yansi::Paint;
 
-use crate::config::{TlsConfig, LogLevel, Shutdown, Ident};
+use crate::config::{Address, TlsConfig, LogLevel, LogFormat, Shutdown, Ident};
 use crate::request::{self, Request, FromRequest};
 use crate::data::Limits;
+use crate::{Rocket, Build};
 
 #[cfg(feature = "secrets")]
 use crate::config::SecretKey;
@@ -62,14 +63,25 @@ pub struct Config {
     /// the extracting Figment's selected `Profile`.
     #[serde(skip)]
     pub profile: Profile,
-    /// IP address to serve on. **(default: `127.0.0.1`)**
-    pub address: IpAddr,
-    /// Port to serve on. **(default: `8000`)**
+    /// IP address or `unix:` prefixed Unix domain socket path to serve on.
+    /// See [`Address`] for details. Use [`Address::ip()`] to get the IP
+    /// address, if any. **(default: `127.0.0.1`)**
+    pub address: Address,
+    /// Port to serve on. Ignored for Unix domain sockets. **(default: `8000`)**
     pub port: u16,
+    /// Permissions, e.g. `0o660`, to set on the Unix domain socket file when
+    /// serving on one. The file has these permissions from the moment it's
+    /// created. When `None`, permissions are determined by the process umask.
+    /// **(default: `None`)**
+    pub socket_mode: Option<u32>,
     /// Number of threads to use for executing futures. **(default: `num_cores`)**
     pub workers: usize,
     /// Keep-alive timeout in seconds; disabled when `0`. **(default: `5`)**
     pub keep_alive: u32,
+    /// Request handler timeout in seconds; disabled when `0`. A route's own
+    /// [`timeout`](crate::Route::timeout), if any, takes precedence.
+    /// **(default: `0`)**
+    pub timeout: u32,
     /// Streaming read size limits. **(default: [`Limits::default()`])**
     pub limits: Limits,
     /// The TLS configuration, if any. **(default: `None`)**
@@ -77,6 +89,11 @@ pub struct Config {
     /// How, if at all, to identify the server via the `Server` header.
     /// **(default: `"Rocket"`)**
     pub ident: Ident,
+    /// The header carrying a request's [`RequestId`], both on requests and on
+    /// responses. **(default: `"X-Request-Id"`)**
+    ///
+    /// [`RequestId`]: crate::request::RequestId
+    pub request_id_header: String,
     /// The secret key for signing and encrypting. **(default: `0`)**
     ///
     /// **Note:** This field _always_ serializes as a 256-bit array of `0`s to
@@ -90,6 +107,9 @@ pub struct Config {
     pub temp_dir: PathBuf,
     /// Max level to log. **(default: _debug_ `normal` / _release_ `critical`)**
     pub log_level: LogLevel,
+    /// How to format log messages. See [`LogFormat`] for the fields of `json`
+    /// records. **(default: `pretty`)**
+    pub log_format: LogFormat,
     /// Graceful shutdown configuration. **(default: [`Shutdown::default()`])**
     pub shutdown: Shutdown,
     /// Whether to use colors and emoji when logging. **(default: `true`)**
@@ -160,15 +180,19 @@ impl Config {
             profile: Self::DEBUG_PROFILE,
             address: Ipv4Addr::new(127, 0, 0, 1).into(),
             port: 8000,
+            socket_mode: None,
             workers: num_cpus::get(),
             keep_alive: 5,
+            timeout: 0,
             limits: Limits::default(),
             tls: None,
             ident: Ident::default(),
+            request_id_header: "X-Request-Id".into(),
             #[cfg(feature = "secrets")]
             secret_key: SecretKey::zero(),
             temp_dir: std::env::temp_dir(),
             log_level: LogLevel::Normal,
+            log_format: LogFormat::Pretty,
             shutdown: Shutdown::default(),
             cli_colors: true,
             __non_exhaustive: (),
@@ -309,9 +333,15 @@ impl Config {
         launch_info!("{}Configured for {}.", Paint::emoji("🔧 "), figment.profile());
 
         launch_info_!("address: {}", Paint::default(&self.address).bold());
-        launch_info_!("port: {}", Paint::default(&self.port).bold());
+        if let Address::Ip(_) = self.address {
+            launch_info_!("port: {}", Paint::default(&self.port).bold());
+        } else if let Some(mode) = self.socket_mode {
+            launch_info_!("socket mode: {}", Paint::default(format!("{:o}", mode)).bold());
+        }
+
         launch_info_!("workers: {}", Paint::default(self.workers).bold());
         launch_info_!("ident: {}", Paint::default(&self.ident).bold());
+        launch_info_!("request id header: {}", Paint::default(&self.request_id_header).bold());
 
         let ka = self.keep_alive;
         if ka > 0 {
@@ -320,6 +350,11 @@ impl Config {
             launch_info_!("keep-alive: {}", Paint::default("disabled").bold());
         }
 
+        match self.timeout {
+            0 => launch_info_!("timeout: {}", Paint::default("disabled").bold()),
+            t => launch_info_!("timeout: {}", Paint::default(format!("{}s", t)).bold()),
+        }
+
         launch_info_!("limits: {}", Paint::default(&self.limits).bold());
         match self.tls_enabled() {
             true => launch_info_!("tls: {}", Paint::default("enabled").bold()),
@@ -337,6 +372,7 @@ impl Config {
 
         launch_info_!("temp dir: {}", Paint::default(&self.temp_dir.display()).bold());
         launch_info_!("log level: {}", Paint::default(self.log_level).bold());
+        launch_info_!("log format: {}", Paint::default(self.log_format).bold());
         launch_info_!("cli colors: {}", Paint::default(&self.cli_colors).bold());
         launch_info_!("shutdown: {}", Paint::default(&self.shutdown).bold());
 
@@ -398,18 +434,28 @@ impl Config {
     /// The stringy parameter name for setting/extracting [`Config::port`].
     pub const PORT: &'static str = "port";
 
+    /// The stringy parameter name for setting/extracting [`Config::socket_mode`].
+    pub const SOCKET_MODE: &'static str = "socket_mode";
+
     /// The stringy parameter name for setting/extracting [`Config::workers`].
     pub const WORKERS: &'static str = "workers";
 
     /// The stringy parameter name for setting/extracting [`Config::keep_alive`].
     pub const KEEP_ALIVE: &'static str = "keep_alive";
 
+    /// The stringy parameter name for setting/extracting [`Config::timeout`].
+    pub const TIMEOUT: &'static str = "timeout";
+
     /// The stringy parameter name for setting/extracting [`Config::limits`].
     pub const LIMITS: &'static str = "limits";
 
     /// The stringy parameter name for setting/extracting [`Config::tls`].
     pub const TLS: &'static str = "tls";
 
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::request_id_header`].
+    pub const REQUEST_ID_HEADER: &'static str = "request_id_header";
+
     /// The stringy parameter name for setting/extracting [`Config::secret_key`].
     pub const SECRET_KEY: &'static str = "secret_key";
 
@@ -419,6 +465,9 @@ impl Config {
     /// The stringy parameter name for setting/extracting [`Config::log_level`].
     pub const LOG_LEVEL: &'static str = "log_level";
 
+    /// The stringy parameter name for setting/extracting [`Config::log_format`].
+    pub const LOG_FORMAT: &'static str = "log_format";
+
     /// The stringy parameter name for setting/extracting [`Config::shutdown`].
     pub const SHUTDOWN: &'static str = "shutdown";
 }
@@ -458,6 +507,27 @@ impl<'r> FromRequest<'r> for &'r Config {
     }
 }
 
+/// Extracts the configuration parameter `key` as a `T`, or `T::default()` if
+/// it's not set. If extraction fails, prints the error and returns `None`.
+/// Used by fairings to read their own configuration on ignite.
+#[cfg_attr(not(any(
+    feature = "ratelimit", feature = "cors", feature = "sessions", feature = "csrf",
+    feature = "openapi", feature = "upload", feature = "metrics",
+    feature = "access-log", feature = "health",
+)), allow(dead_code))]
+pub(crate) fn extract_or_default<T>(rocket: &Rocket<Build>, key: &str) -> Option<T>
+    where T: serde::de::DeserializeOwned + Default
+{
+    match rocket.figment().extract_inner::<T>(key) {
+        Ok(value) => Some(value),
+        Err(e) if e.missing() => Some(T::default()),
+        Err(e) => {
+            pretty_print_error(e);
+            None
+        }
+    }
+}
+
 #[doc(hidden)]
 pub fn pretty_print_error(error: figment::Error) {
     use figment::error::{Kind, OneOf};
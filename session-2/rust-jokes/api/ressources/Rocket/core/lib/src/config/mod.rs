@@ -114,6 +114,7 @@
 #[macro_use]
 mod ident;
 mod config;
+mod address;
 mod tls;
 mod shutdown;
 
@@ -121,11 +122,22 @@ mod shutdown;
 mod secret_key;
 
 #[doc(hidden)] pub use config::pretty_print_error;
+#[cfg_attr(not(any(
+    feature = "ratelimit", feature = "cors", feature = "sessions", feature = "csrf",
+    feature = "openapi", feature = "upload", feature = "metrics",
+    feature = "access-log", feature = "health",
+)), allow(unused_imports))]
+pub(crate) use config::extract_or_default;
 
 pub use config::Config;
-pub use crate::log::LogLevel;
+pub use address::Address;
+pub use crate::log::{LogLevel, LogFormat};
 pub use shutdown::Shutdown;
 pub use tls::TlsConfig;
+
+#[cfg(feature = "mtls")]
+#[cfg_attr(nightly, doc(cfg(feature = "mtls")))]
+pub use tls::MutualTls;
 pub use ident::Ident;
 
 #[cfg(feature = "secrets")]
@@ -142,7 +154,7 @@ mod tests {
     use figment::{Figment, Profile};
     use pretty_assertions::assert_eq;
 
-    use crate::config::{Config, TlsConfig, Shutdown, Ident};
+    use crate::config::{Config, Address, TlsConfig, Shutdown, Ident};
     use crate::log::LogLevel;
     use crate::data::{Limits, ToByteUnit};
 
@@ -199,6 +211,7 @@ mod tests {
                 port = 1234
                 workers = 20
                 keep_alive = 10
+                timeout = 30
                 log_level = "off"
                 cli_colors = 0
             "#)?;
@@ -210,6 +223,7 @@ mod tests {
                 workers: 20,
                 ident: ident!("Something Cool"),
                 keep_alive: 10,
+                timeout: 30,
                 log_level: LogLevel::Off,
                 cli_colors: false,
                 ..Config::default()
@@ -279,6 +293,42 @@ mod tests {
                 ..Config::default()
             });
 
+            #[cfg(feature = "mtls")] {
+                use crate::config::MutualTls;
+
+                jail.create_file("Rocket.toml", r#"
+                    [global.tls]
+                    certs = "cert.pem"
+                    key = "key.pem"
+
+                    [global.tls.mutual]
+                    ca_certs = "ca.pem"
+                    mandatory = true
+                "#)?;
+
+                let config = Config::from(Config::figment());
+                let mtls = MutualTls::from_path(jail.directory().join("ca.pem"));
+                assert_eq!(config, Config {
+                    tls: Some(TlsConfig::from_paths(
+                        jail.directory().join("cert.pem"), jail.directory().join("key.pem")
+                    ).with_mutual(mtls.mandatory(true))),
+                    ..Config::default()
+                });
+            }
+
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                address = "unix:/run/app.sock"
+                socket_mode = 0o660
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config, Config {
+                address: Address::Unix("/run/app.sock".into()),
+                socket_mode: Some(0o660),
+                ..Config::default()
+            });
+
             jail.set_env("ROCKET_CONFIG", "Other.toml");
             jail.create_file("Other.toml", r#"
                 [default]
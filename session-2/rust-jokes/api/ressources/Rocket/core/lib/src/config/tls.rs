@@ -1,6 +1,9 @@
 use figment::value::magic::{Either, RelativePathBuf};
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "tls")]
+use crate::http::private::tls::ClientAuth;
+
 /// TLS configuration: a certificate chain and a private key.
 ///
 /// Both `certs` and `key` can be configured as a path or as raw bytes. `certs`
@@ -31,6 +34,54 @@ pub struct TlsConfig {
     /// Path or raw bytes to DER-encoded ASN.1 key in either PKCS#8 or PKCS#1
     /// format.
     pub(crate) key: Either<RelativePathBuf, Vec<u8>>,
+    /// Mutual TLS configuration, if any.
+    #[serde(default)]
+    #[cfg(feature = "mtls")]
+    #[cfg_attr(nightly, doc(cfg(feature = "mtls")))]
+    pub(crate) mutual: Option<MutualTls>,
+}
+
+/// Mutual TLS configuration: CA certificates and whether client certificates
+/// are mandatory.
+///
+/// When configured, clients are asked to present a certificate that chains to
+/// one of the certificates in `ca_certs`. If `mandatory` is `true`, connections
+/// from clients that don't present a valid certificate are rejected during the
+/// TLS handshake. Otherwise, such clients may connect, but a request guard of
+/// [`Certificate`](crate::mtls::Certificate) will fail. `ca_certs` can be
+/// configured as a path or as raw bytes. `mandatory` defaults to `false`.
+///
+/// The following example illustrates manual configuration:
+///
+/// ```rust
+/// use rocket::Config;
+///
+/// let figment = rocket::Config::figment()
+///     .merge(("tls.certs", "strings/are/paths/certs.pem"))
+///     .merge(("tls.key", "strings/are/paths/key.pem"))
+///     .merge(("tls.mutual.ca_certs", "strings/are/paths/ca_certs.pem"))
+///     .merge(("tls.mutual.mandatory", true));
+///
+/// let config = rocket::Config::from(figment);
+/// let tls_config = config.tls.as_ref().unwrap();
+/// let mutual = tls_config.mutual().unwrap();
+/// assert!(mutual.ca_certs().is_left());
+/// assert!(mutual.mandatory);
+/// ```
+///
+/// When a path is configured in a file source, such as `Rocket.toml`, relative
+/// paths are interpreted as being relative to the source file's directory.
+#[cfg(feature = "mtls")]
+#[cfg_attr(nightly, doc(cfg(feature = "mtls")))]
+#[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
+pub struct MutualTls {
+    /// Path or raw bytes to the PEM-encoded X.509 certificates of the CAs
+    /// that client certificates must chain to.
+    pub(crate) ca_certs: Either<RelativePathBuf, Vec<u8>>,
+    /// Whether clients _must_ present a valid certificate. **(default:
+    /// `false`)**
+    #[serde(default)]
+    pub mandatory: bool,
 }
 
 impl TlsConfig {
@@ -50,7 +101,9 @@ impl TlsConfig {
     {
         TlsConfig {
             certs: Either::Left(certs.as_ref().to_path_buf().into()),
-            key: Either::Left(key.as_ref().to_path_buf().into())
+            key: Either::Left(key.as_ref().to_path_buf().into()),
+            #[cfg(feature = "mtls")]
+            mutual: None,
         }
     }
 
@@ -71,7 +124,9 @@ impl TlsConfig {
     pub fn from_bytes(certs: &[u8], key: &[u8]) -> Self {
         TlsConfig {
             certs: Either::Right(certs.to_vec().into()),
-            key: Either::Right(key.to_vec().into())
+            key: Either::Right(key.to_vec().into()),
+            #[cfg(feature = "mtls")]
+            mutual: None,
         }
     }
 
@@ -121,32 +176,160 @@ impl TlsConfig {
             Either::Right(bytes) => either::Either::Right(&bytes),
         }
     }
+
+    /// Configures mutual TLS with `config`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{TlsConfig, MutualTls};
+    ///
+    /// let mtls_config = MutualTls::from_path("/ssl/ca_cert.pem").mandatory(true);
+    /// let tls_config = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem")
+    ///     .with_mutual(mtls_config);
+    ///
+    /// assert!(tls_config.mutual().unwrap().mandatory);
+    /// ```
+    #[cfg(feature = "mtls")]
+    #[cfg_attr(nightly, doc(cfg(feature = "mtls")))]
+    pub fn with_mutual(mut self, config: MutualTls) -> Self {
+        self.mutual = Some(config);
+        self
+    }
+
+    /// Returns the mutual TLS configuration, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{TlsConfig, MutualTls};
+    ///
+    /// let tls_config = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem");
+    /// assert!(tls_config.mutual().is_none());
+    ///
+    /// let tls_config = tls_config.with_mutual(MutualTls::from_path("/ssl/ca.pem"));
+    /// assert!(tls_config.mutual().is_some());
+    /// ```
+    #[cfg(feature = "mtls")]
+    #[cfg_attr(nightly, doc(cfg(feature = "mtls")))]
+    pub fn mutual(&self) -> Option<&MutualTls> {
+        self.mutual.as_ref()
+    }
+}
+
+#[cfg(feature = "mtls")]
+impl MutualTls {
+    /// Constructs a `MutualTls` from a path to a `ca_certs` file of CA
+    /// certificates. Client certificates are not mandatory. This method does
+    /// no validation; it simply creates a structure suitable for passing into
+    /// [`TlsConfig::with_mutual()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::MutualTls;
+    ///
+    /// let mtls_config = MutualTls::from_path("/ssl/ca_cert.pem");
+    /// assert!(!mtls_config.mandatory);
+    /// ```
+    pub fn from_path<C: AsRef<std::path::Path>>(ca_certs: C) -> Self {
+        MutualTls {
+            ca_certs: Either::Left(ca_certs.as_ref().to_path_buf().into()),
+            mandatory: false,
+        }
+    }
+
+    /// Constructs a `MutualTls` from a byte buffer of CA certificates. Client
+    /// certificates are not mandatory. This method does no validation; it
+    /// simply creates a structure suitable for passing into
+    /// [`TlsConfig::with_mutual()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::MutualTls;
+    ///
+    /// # let ca_certs_buf = &[];
+    /// let mtls_config = MutualTls::from_bytes(ca_certs_buf);
+    /// assert!(!mtls_config.mandatory);
+    /// ```
+    pub fn from_bytes(ca_certs: &[u8]) -> Self {
+        MutualTls {
+            ca_certs: Either::Right(ca_certs.to_vec()),
+            mandatory: false,
+        }
+    }
+
+    /// Sets whether client certificates are mandatory to `mandatory`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::MutualTls;
+    ///
+    /// let mtls_config = MutualTls::from_path("/ssl/ca_cert.pem").mandatory(true);
+    /// assert!(mtls_config.mandatory);
+    /// ```
+    pub fn mandatory(mut self, mandatory: bool) -> Self {
+        self.mandatory = mandatory;
+        self
+    }
+
+    /// Returns the value of the `ca_certs` parameter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::Path;
+    /// use rocket::config::MutualTls;
+    ///
+    /// let mtls_config = MutualTls::from_path("/etc/ssl/ca_cert.pem");
+    /// let ca_certs_path = mtls_config.ca_certs().left().unwrap();
+    /// assert_eq!(ca_certs_path, Path::new("/etc/ssl/ca_cert.pem"));
+    /// ```
+    pub fn ca_certs(&self) -> either::Either<std::path::PathBuf, &[u8]> {
+        match &self.ca_certs {
+            Either::Left(path) => either::Either::Left(path.relative()),
+            Either::Right(bytes) => either::Either::Right(bytes),
+        }
+    }
 }
 
 #[cfg(feature = "tls")]
 type Reader = Box<dyn std::io::BufRead + Sync + Send>;
 
+#[cfg(feature = "tls")]
+fn to_reader(value: &Either<RelativePathBuf, Vec<u8>>) -> std::io::Result<Reader> {
+    use std::{io::{self, Error}, fs};
+    use yansi::Paint;
+
+    match value {
+        Either::Left(path) => {
+            let path = path.relative();
+            let file = fs::File::open(&path).map_err(move |e| {
+                Error::new(e.kind(), format!("error reading TLS file `{}`: {}",
+                        Paint::white(figment::Source::File(path)), e))
+            })?;
+
+            Ok(Box::new(io::BufReader::new(file)))
+        }
+        Either::Right(vec) => Ok(Box::new(io::Cursor::new(vec.clone()))),
+    }
+}
+
 #[cfg(feature = "tls")]
 impl TlsConfig {
     pub(crate) fn to_readers(&self) -> std::io::Result<(Reader, Reader)> {
-        use std::{io::{self, Error}, fs};
-        use yansi::Paint;
-
-        fn to_reader(value: &Either<RelativePathBuf, Vec<u8>>) -> io::Result<Reader> {
-            match value {
-                Either::Left(path) => {
-                    let path = path.relative();
-                    let file = fs::File::open(&path).map_err(move |e| {
-                        Error::new(e.kind(), format!("error reading TLS file `{}`: {}",
-                                Paint::white(figment::Source::File(path)), e))
-                    })?;
-
-                    Ok(Box::new(io::BufReader::new(file)))
-                }
-                Either::Right(vec) => Ok(Box::new(io::Cursor::new(vec.clone()))),
-            }
+        Ok((to_reader(&self.certs)?, to_reader(&self.key)?))
+    }
+
+    pub(crate) fn client_auth(&self) -> std::io::Result<Option<ClientAuth<Reader>>> {
+        #[cfg(feature = "mtls")]
+        if let Some(ref mutual) = self.mutual {
+            let ca_certs = to_reader(&mutual.ca_certs)?;
+            return Ok(Some(ClientAuth { ca_certs, mandatory: mutual.mandatory }));
         }
 
-        Ok((to_reader(&self.certs)?, to_reader(&self.key)?))
+        Ok(None)
     }
 }
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+use crate::http::Method;
+
+/// The CORS configuration: which origins may make cross-origin requests, with
+/// which methods and headers, and what the browser may expose to them.
+///
+/// An origin is allowed if it's listed in `allowed_origins` or matches any of
+/// `allowed_origin_regexes` in full. The [`Cors`](crate::cors::Cors) fairing
+/// fails to ignite if a regex is invalid or if `allow_credentials` is set
+/// while `allowed_origins` contains `"*"`.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::cors::Config;
+///
+/// let figment = rocket::Config::figment()
+///     .merge(("cors.allowed_origins", ["https://rocket.rs"]))
+///     .merge(("cors.allowed_origin_regexes", [r"https://.*\.rocket\.rs"]))
+///     .merge(("cors.max_age", 3600));
+///
+/// let config: Config = figment.extract_inner("cors").unwrap();
+/// assert_eq!(config.allowed_origins, ["https://rocket.rs"]);
+/// assert_eq!(config.max_age, Some(3600));
+/// assert!(!config.allow_credentials);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// Origins allowed to make cross-origin requests, compared exactly, or
+    /// `"*"` to allow any origin. **(default: `["*"]`)**
+    pub allowed_origins: Vec<String>,
+    /// Regular expressions which, when matching an origin in its entirety,
+    /// allow it to make cross-origin requests. **(default: empty)**
+    pub allowed_origin_regexes: Vec<String>,
+    /// Methods allowed in cross-origin requests, in addition to being
+    /// supported by a route, or `None` to allow any method a route supports.
+    /// **(default: `None`)**
+    pub allowed_methods: Option<Vec<Method>>,
+    /// Request headers allowed in cross-origin requests, compared
+    /// case-insensitively, or `None` to allow any header. **(default:
+    /// `None`)**
+    pub allowed_headers: Option<Vec<String>>,
+    /// Response headers, beyond the CORS-safelisted ones, that browsers may
+    /// expose to cross-origin requests. **(default: empty)**
+    pub exposed_headers: Vec<String>,
+    /// Whether cross-origin requests may include credentials such as cookies.
+    /// Requires listing the allowed origins: it can't be set while
+    /// `allowed_origins` contains `"*"`. **(default: `false`)**
+    pub allow_credentials: bool,
+    /// The number of seconds browsers may cache a preflight response for, if
+    /// any. **(default: `None`)**
+    pub max_age: Option<u64>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            allowed_origins: vec!["*".into()],
+            allowed_origin_regexes: vec![],
+            allowed_methods: None,
+            allowed_headers: None,
+            exposed_headers: vec![],
+            allow_credentials: false,
+            max_age: None,
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{Rocket, Build, Request, Response, Data, Route};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::route::{self, Handler, Outcome};
+use crate::http::{Header, Method, Status};
+use crate::cors::Config;
+use crate::cors::policy::Policy;
+
+/// A [`Fairing`] that implements Cross-Origin Resource Sharing.
+///
+/// See the [module docs](crate::cors) for usage, configuration, and behavior
+/// details.
+///
+/// # Example
+///
+/// Allow cross-origin requests as configured in `Rocket.toml`:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::cors::Cors;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(Cors::new())
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Cors {
+    _private: (),
+}
+
+impl Cors {
+    /// Returns a `Cors` fairing that reads its [`Config`] from the `cors`
+    /// configuration parameter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    ///
+    /// let cors = Cors::new();
+    /// ```
+    pub fn new() -> Self {
+        Cors { _private: () }
+    }
+}
+
+/// A [filter](Route::filter()) for `OPTIONS` requests that answers preflight
+/// requests for any URI matched by at least one route.
+#[derive(Clone)]
+struct Preflight;
+
+#[crate::async_trait]
+impl Handler for Preflight {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> route::Outcome<'r> {
+        let headers = req.headers();
+        let (policy, origin, method) = match (
+            req.rocket().state::<Policy>(),
+            headers.get_one("Origin"),
+            headers.get_one("Access-Control-Request-Method"),
+        ) {
+            (Some(policy), Some(origin), Some(method)) => (policy, origin, method),
+            _ => return Outcome::Forward(data),
+        };
+
+        let mut methods = req.rocket().router.methods(req);
+        if methods.is_empty() {
+            return Outcome::Forward(data);
+        }
+
+        methods.retain(|m| policy.allows_method(*m));
+        methods.sort_by_key(|m| m.as_str());
+
+        let requested_headers: Vec<&str> = headers.get("Access-Control-Request-Headers")
+            .flat_map(|value| value.split(','))
+            .map(|header| header.trim())
+            .filter(|header| !header.is_empty())
+            .collect();
+
+        let method_allowed = matches!(method.parse(), Ok(m) if methods.contains(&m));
+        if !policy.allows_origin(origin)
+            || !method_allowed
+            || !requested_headers.iter().all(|h| policy.allows_header(h))
+        {
+            warn_!("CORS: refusing preflight request from '{}'.", origin);
+            return Outcome::Failure(Status::Forbidden);
+        }
+
+        let methods: Vec<_> = methods.iter().map(|m| m.as_str()).collect();
+        let mut response = Response::build();
+        response.status(Status::NoContent)
+            .header(Header::new("Access-Control-Allow-Methods", methods.join(", ")));
+
+        if !requested_headers.is_empty() {
+            let value = requested_headers.join(", ");
+            response.header(Header::new("Access-Control-Allow-Headers", value));
+        }
+
+        if let Some(max_age) = policy.max_age {
+            response.header(Header::new("Access-Control-Max-Age", max_age.to_string()));
+        }
+
+        Outcome::Success(response.finalize())
+    }
+}
+
+impl Preflight {
+    fn route() -> Route {
+        let mut route = Route::filter(Method::Options, Preflight);
+        route.name = Some("Cors".into());
+        route
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Cors {
+    fn info(&self) -> Info {
+        Info {
+            name: "CORS",
+            kind: Kind::Ignite | Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let config = match crate::config::extract_or_default::<Config>(&rocket, "cors") {
+            Some(config) => config,
+            None => return Err(rocket),
+        };
+
+        match Policy::new(config) {
+            Ok(policy) => Ok(rocket.manage(policy).mount("/", vec![Preflight::route()])),
+            Err(e) => {
+                error!("invalid CORS configuration: {}", e);
+                Err(rocket)
+            }
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let policy = req.rocket().state::<Policy>();
+        let (policy, origin) = match (policy, req.headers().get_one("Origin")) {
+            (Some(policy), Some(origin)) => (policy, origin),
+            _ => return,
+        };
+
+        if policy.varies() {
+            res.adjoin_header(Header::new("Vary", "Origin"));
+        }
+
+        if !policy.allows_origin(origin) {
+            return;
+        }
+
+        res.set_header(Header::new("Access-Control-Allow-Origin", policy.allow_origin(origin)));
+        if policy.allow_credentials {
+            res.set_header(Header::new("Access-Control-Allow-Credentials", "true"));
+        }
+
+        if let Some(exposed) = policy.exposed_headers() {
+            res.set_header(Header::new("Access-Control-Expose-Headers", exposed));
+        }
+    }
+}
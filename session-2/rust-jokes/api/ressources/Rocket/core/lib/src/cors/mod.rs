@@ -0,0 +1,83 @@
+//! Cross-Origin Resource Sharing (CORS).
+//!
+//! The [`Cors`] fairing lets browsers make [cross-origin requests] to a Rocket
+//! application from the origins it allows. It answers [preflight requests] for
+//! every URI matched by a mounted route and adds the `Access-Control-*`
+//! headers to responses to requests from allowed origins.
+//!
+//! [cross-origin requests]: https://developer.mozilla.org/en-US/docs/Web/HTTP/CORS
+//! [preflight requests]: https://developer.mozilla.org/en-US/docs/Glossary/Preflight_request
+//!
+//! # Enabling
+//!
+//! This module is only available when the `cors` feature is enabled. Enable
+//! it in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket]
+//! version = "0.5.0-rc.1"
+//! features = ["cors"]
+//! ```
+//!
+//! # Usage
+//!
+//! Attach an instance of [`Cors`]:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::cors::Cors;
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build().attach(Cors::new())
+//! }
+//! ```
+//!
+//! # Configuration
+//!
+//! The fairing reads a [`Config`] from the `cors` configuration parameter.
+//! Without one, any origin may make cross-origin requests without
+//! credentials. For example, to allow `https://rocket.rs` and its subdomains
+//! to make credentialed `GET` and `POST` requests:
+//!
+//! ```toml
+//! [default.cors]
+//! allowed_origins = ["https://rocket.rs"]
+//! allowed_origin_regexes = ['https://[a-z0-9-]+\.rocket\.rs']
+//! allowed_methods = ["GET", "POST"]
+//! allowed_headers = ["Content-Type", "Authorization"]
+//! exposed_headers = ["X-Total-Count"]
+//! allow_credentials = true
+//! max_age = 3600
+//! ```
+//!
+//! See [`Config`] for all parameters and their defaults. An invalid regular
+//! expression aborts launch, as does allowing credentials from any origin:
+//! `allow_credentials` requires replacing the default `allowed_origins` of
+//! `["*"]` with the origins to trust.
+//!
+//! # Behavior
+//!
+//! A preflight request is an `OPTIONS` request with `Origin` and
+//! `Access-Control-Request-Method` headers. The fairing mounts a catch-all
+//! `OPTIONS` route that answers such requests to URIs matched by at least one
+//! route with `204 No Content`, listing the methods of the matching routes
+//! that are also allowed by the configuration in
+//! `Access-Control-Allow-Methods`. If the origin, the requested method, or any
+//! of the requested headers isn't allowed, the preflight fails with `403
+//! Forbidden` instead. Requests to URIs no route matches, and other `OPTIONS`
+//! requests, are forwarded as usual.
+//!
+//! The response to any request from an allowed origin, including preflight
+//! requests, carries an `Access-Control-Allow-Origin` header. Its value is `*`
+//! when any origin is allowed and the request's origin otherwise, in which
+//! case `Vary: Origin` is also added. Responses to requests
+//! from origins that aren't allowed carry no CORS headers, leaving the browser
+//! to block them.
+
+mod config;
+mod fairing;
+mod policy;
+
+pub use self::config::Config;
+pub use self::fairing::Cors;
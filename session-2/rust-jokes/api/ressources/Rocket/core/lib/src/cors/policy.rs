@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+
+use regex::Regex;
+
+use crate::http::Method;
+use crate::cors::Config;
+
+/// The managed state of the [`Cors`](crate::cors::Cors) fairing: a [`Config`]
+/// prepared for checking requests against.
+#[derive(Debug)]
+pub(crate) struct Policy {
+    any_origin: bool,
+    origins: HashSet<String>,
+    regexes: Vec<Regex>,
+    methods: Option<Vec<Method>>,
+    headers: Option<HashSet<String>>,
+    exposed_headers: Option<String>,
+    pub allow_credentials: bool,
+    pub max_age: Option<u64>,
+}
+
+impl Policy {
+    /// Prepares `config`, failing if any origin regex is invalid or if
+    /// credentials are allowed along with any origin.
+    pub fn new(config: Config) -> Result<Policy, String> {
+        let any_origin = config.allowed_origins.iter().any(|o| o == "*");
+        if any_origin && config.allow_credentials {
+            return Err("credentials can't be allowed from any origin (`*`)".into());
+        }
+
+        let regexes = config.allowed_origin_regexes.iter()
+            .map(|regex| Regex::new(&format!("^(?:{})$", regex)))
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("invalid origin regex: {}", e))?;
+
+        let headers = config.allowed_headers.map(|headers| {
+            headers.iter().map(|h| h.to_ascii_lowercase()).collect()
+        });
+
+        let exposed_headers = match config.exposed_headers.is_empty() {
+            true => None,
+            false => Some(config.exposed_headers.join(", ")),
+        };
+
+        Ok(Policy {
+            any_origin,
+            origins: config.allowed_origins.into_iter().collect(),
+            methods: config.allowed_methods,
+            allow_credentials: config.allow_credentials,
+            max_age: config.max_age,
+            regexes, headers, exposed_headers,
+        })
+    }
+
+    /// Returns `true` if `origin` may make cross-origin requests.
+    pub fn allows_origin(&self, origin: &str) -> bool {
+        self.any_origin
+            || self.origins.contains(origin)
+            || self.regexes.iter().any(|regex| regex.is_match(origin))
+    }
+
+    /// Returns `true` if cross-origin requests may use `method` on a route
+    /// that supports it.
+    pub fn allows_method(&self, method: Method) -> bool {
+        match self.methods {
+            Some(ref methods) => methods.contains(&method),
+            None => true,
+        }
+    }
+
+    /// Returns `true` if cross-origin requests may include `header`.
+    pub fn allows_header(&self, header: &str) -> bool {
+        match self.headers {
+            Some(ref headers) => headers.contains(&header.to_ascii_lowercase()),
+            None => true,
+        }
+    }
+
+    /// The value of `Access-Control-Allow-Origin` for an allowed `origin`:
+    /// `*` when any origin is allowed, `origin` itself otherwise.
+    pub fn allow_origin<'a>(&self, origin: &'a str) -> &'a str {
+        match self.varies() {
+            true => origin,
+            false => "*",
+        }
+    }
+
+    /// Returns `true` if responses depend on the request's `Origin`.
+    pub fn varies(&self) -> bool {
+        !self.any_origin
+    }
+
+    /// The value of `Access-Control-Expose-Headers`, if any.
+    pub fn exposed_headers(&self) -> Option<&str> {
+        self.exposed_headers.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(origins: &[&str], regexes: &[&str]) -> Policy {
+        Policy::new(Config {
+            allowed_origins: origins.iter().map(|s| s.to_string()).collect(),
+            allowed_origin_regexes: regexes.iter().map(|s| s.to_string()).collect(),
+            ..Config::default()
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_origins() {
+        let policy = policy(&["https://rocket.rs"], &[r"https://[a-z]+\.rocket\.rs"]);
+        assert!(policy.allows_origin("https://rocket.rs"));
+        assert!(policy.allows_origin("https://api.rocket.rs"));
+        assert!(!policy.allows_origin("http://rocket.rs"));
+        assert!(!policy.allows_origin("https://rocket.rs.evil.com"));
+        assert!(!policy.allows_origin("https://a.b.rocket.rs"));
+        assert!(!policy.allows_origin("null"));
+        assert!(policy.varies());
+        assert_eq!(policy.allow_origin("https://rocket.rs"), "https://rocket.rs");
+
+        let policy = self::policy(&["*"], &[]);
+        assert!(policy.allows_origin("https://rocket.rs"));
+        assert!(policy.allows_origin("null"));
+        assert!(!policy.varies());
+        assert_eq!(policy.allow_origin("https://rocket.rs"), "*");
+
+        let config = Config { allow_credentials: true, ..Config::default() };
+        assert!(Policy::new(config).is_err());
+
+        let config = Config {
+            allowed_origins: vec!["https://rocket.rs".into(), "*".into()],
+            allow_credentials: true,
+            ..Config::default()
+        };
+
+        assert!(Policy::new(config).is_err());
+
+        let policy = Policy::new(Config {
+            allowed_origins: vec!["https://rocket.rs".into()],
+            allow_credentials: true,
+            ..Config::default()
+        }).unwrap();
+
+        assert!(policy.varies());
+        assert!(!policy.allows_origin("null"));
+        assert_eq!(policy.allow_origin("https://rocket.rs"), "https://rocket.rs");
+    }
+
+    #[test]
+    fn test_methods_and_headers() {
+        let policy = Policy::new(Config {
+            allowed_methods: Some(vec![Method::Get, Method::Post]),
+            allowed_headers: Some(vec!["Content-Type".into(), "x-token".into()]),
+            ..Config::default()
+        }).unwrap();
+
+        assert!(policy.allows_method(Method::Get));
+        assert!(!policy.allows_method(Method::Delete));
+        assert!(policy.allows_header("content-type"));
+        assert!(policy.allows_header("X-Token"));
+        assert!(!policy.allows_header("Authorization"));
+
+        let policy = Policy::new(Config::default()).unwrap();
+        assert!(policy.allows_method(Method::Delete));
+        assert!(policy.allows_header("Authorization"));
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let config = Config { allowed_origin_regexes: vec!["(".into()], ..Config::default() };
+        assert!(Policy::new(config).is_err());
+    }
+}
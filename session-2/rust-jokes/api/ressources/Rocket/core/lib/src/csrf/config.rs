@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// The CSRF configuration: where tokens are kept and where submissions carry
+/// them.
+///
+/// Changing `cookie_name` invalidates every token issued before the change, so
+/// forms rendered by then fail verification on submission.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::csrf::Config;
+///
+/// let figment = rocket::Config::figment()
+///     .merge(("csrf.field_name", "authenticity_token"));
+///
+/// let config: Config = figment.extract_inner("csrf").unwrap();
+/// assert_eq!(config.cookie_name, "rocket_csrf");
+/// assert_eq!(config.field_name, "authenticity_token");
+/// assert_eq!(config.header_name, "X-CSRF-Token");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// The name of the private cookie holding the token. **(default:
+    /// `"rocket_csrf"`)**
+    pub cookie_name: String,
+    /// The name of the form field carrying the token. **(default:
+    /// `"_csrf"`)**
+    pub field_name: String,
+    /// The name of the request header carrying the token. **(default:
+    /// `"X-CSRF-Token"`)**
+    pub header_name: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            cookie_name: "rocket_csrf".into(),
+            field_name: "_csrf".into(),
+            header_name: "X-CSRF-Token".into(),
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{Rocket, Build, Request, Data};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::form::{Error, ValueField, error::ErrorKind};
+use crate::http::{Cookie, SameSite};
+use crate::csrf::Config;
+
+/// Issues and verifies tokens. Managed by the [`Csrf`] fairing.
+pub(crate) struct Manager {
+    pub config: Config,
+}
+
+/// The request's token, cached by the first call to [`Manager::token()`].
+pub(crate) struct Token {
+    /// The token value.
+    pub value: String,
+    /// Whether the token was issued in this request, and so can't have been
+    /// submitted with it.
+    pub issued: bool,
+}
+
+impl Manager {
+    /// Returns the token in `req`'s token cookie, issuing a new token, and
+    /// setting the cookie, if there's none.
+    pub fn token<'r>(&self, req: &'r Request<'_>) -> &'r Token {
+        req.local_cache(|| {
+            if let Some(cookie) = req.cookies().get_private(&self.config.cookie_name) {
+                return Token { value: cookie.value().to_string(), issued: false };
+            }
+
+            let value = crate::ext::random_token(32);
+
+            let cookie = Cookie::build(self.config.cookie_name.clone(), value.clone())
+                .path("/")
+                .same_site(SameSite::Strict)
+                .http_only(true)
+                .secure(req.rocket().config().tls_enabled())
+                .finish();
+
+            req.cookies().add_private(cookie);
+            Token { value, issued: true }
+        })
+    }
+}
+
+/// Verifies the token submitted with a form, as parsed by
+/// [`Form`](crate::form::Form).
+pub(crate) struct Check<'r> {
+    /// The configuration, or `None` if the request needn't be checked.
+    config: Option<&'r Config>,
+    /// The token the client holds, if any.
+    expected: Option<&'r str>,
+    /// The first token submitted in a form field, if any.
+    submitted: Option<&'r str>,
+}
+
+impl<'r> Check<'r> {
+    /// Starts checking `req`. Only requests with methods that support a
+    /// payload are checked, and only if the `Csrf` fairing is attached and
+    /// the request doesn't carry the token in a header.
+    pub fn new(req: &'r Request<'_>) -> Self {
+        let mut check = Check { config: None, expected: None, submitted: None };
+        let manager = match req.rocket().state::<Manager>() {
+            Some(manager) if req.method().supports_payload() => manager,
+            _ => return check,
+        };
+
+        let token = manager.token(req);
+        if !token.issued {
+            check.expected = Some(&token.value);
+        }
+
+        let header = req.headers().get_one(&manager.config.header_name);
+        if !matches!((check.expected, header), (Some(a), Some(b)) if constant_eq(a, b)) {
+            check.config = Some(&manager.config);
+        }
+
+        check
+    }
+
+    /// Returns `true`, recording the token, if `field` carries the token. Such
+    /// fields aren't passed on to the form.
+    pub fn intercept(&mut self, field: &ValueField<'r>) -> bool {
+        match self.field_name() {
+            Some(name) if field.name.source() == name => {
+                self.submit(field.value);
+                true
+            }
+            _ => false
+        }
+    }
+
+    /// Returns the name of the field carrying the token if the request must be
+    /// checked.
+    pub fn field_name(&self) -> Option<&'r str> {
+        self.config.map(|config| config.field_name.as_str())
+    }
+
+    /// Records `token` as the submitted token unless one was already recorded.
+    pub fn submit(&mut self, token: &'r str) {
+        self.submitted = self.submitted.or(Some(token));
+    }
+
+    /// Returns an error for the token field if the request must be rejected.
+    pub fn verify(self) -> Result<(), Error<'r>> {
+        let config = match self.config {
+            Some(config) => config,
+            None => return Ok(()),
+        };
+
+        let error = match (self.expected, self.submitted) {
+            (Some(a), Some(b)) if constant_eq(a, b) => return Ok(()),
+            (_, None) => Error::from(ErrorKind::Missing),
+            (_, Some(_)) => Error::validation("invalid CSRF token"),
+        };
+
+        warn_!("CSRF: rejecting form with a missing or invalid token.");
+        Err(error.with_name(config.field_name.as_str()))
+    }
+}
+
+/// Compares `a` and `b` in time independent of where they differ.
+fn constant_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// A [`Fairing`] that protects forms against cross-site request forgery.
+///
+/// See the [module docs](crate::csrf) for usage, configuration, and behavior
+/// details.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::csrf::Csrf;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(Csrf::new())
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Csrf {
+    _private: (),
+}
+
+impl Csrf {
+    /// Returns a `Csrf` fairing that reads its [`Config`] from the `csrf`
+    /// configuration parameter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::csrf::Csrf;
+    ///
+    /// let csrf = Csrf::new();
+    /// ```
+    pub fn new() -> Self {
+        Csrf { _private: () }
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Csrf {
+    fn info(&self) -> Info {
+        Info {
+            name: "CSRF",
+            kind: Kind::Ignite | Kind::Request | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let config = match crate::config::extract_or_default::<Config>(&rocket, "csrf") {
+            Some(config) => config,
+            None => return Err(rocket),
+        };
+
+        Ok(rocket.manage(Manager { config }))
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data<'_>) {
+        if let Some(manager) = req.rocket().state::<Manager>() {
+            manager.token(req);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::constant_eq;
+
+    #[test]
+    fn test_constant_eq() {
+        assert!(constant_eq("", ""));
+        assert!(constant_eq("abc", "abc"));
+        assert!(!constant_eq("abc", "abd"));
+        assert!(!constant_eq("abc", "ab"));
+        assert!(!constant_eq("", "a"));
+    }
+}
@@ -0,0 +1,121 @@
+//! Cross-site request forgery (CSRF) protection for forms.
+//!
+//! The [`Csrf`] fairing gives each client a random token, kept in a [private
+//! cookie], that a third-party site can't read. Once it's attached,
+//! [`Form`] rejects form submissions that don't carry the same token in a form
+//! field or header. Handlers include the token in the forms they render with
+//! the [`CsrfToken`] request guard.
+//!
+//! [`Form`]: crate::form::Form
+//! [private cookie]: crate::http::CookieJar::add_private()
+//!
+//! # Enabling
+//!
+//! This module is only available when the `csrf` feature is enabled, which in
+//! turn enables the `secrets` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket = { version = "0.5.0-rc.1", features = ["csrf"] }
+//! ```
+//!
+//! As with all private cookies, a [`secret_key`] must be configured in
+//! production.
+//!
+//! [`secret_key`]: crate::Config::secret_key
+//!
+//! # Usage
+//!
+//! Attach the fairing, render forms with the token in a hidden field, and
+//! accept them with `Form` as usual:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::form::Form;
+//! use rocket::csrf::{Csrf, CsrfToken};
+//! use rocket::response::content::Html;
+//!
+//! #[get("/")]
+//! fn index(token: CsrfToken<'_>) -> Html<String> {
+//!     Html(format!(r#"
+//!         <form method="post" action="/">
+//!             <input type="hidden" name="_csrf" value="{}">
+//!             <input type="text" name="message">
+//!             <button>Send</button>
+//!         </form>
+//!     "#, token))
+//! }
+//!
+//! #[post("/", data = "<message>")]
+//! fn submit(message: Form<String>) -> String {
+//!     format!("Got: {}", message.into_inner())
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![index, submit])
+//!         .attach(Csrf::new())
+//! }
+//! ```
+//!
+//! To render a rejected form with its errors, use [`Contextual`]: a failed
+//! check is recorded in the form's context, under the token field's name, and
+//! the form's `value` is `None`:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::form::{Form, Contextual};
+//!
+//! #[post("/", data = "<form>")]
+//! fn submit<'r>(form: Form<Contextual<'r, String>>) -> String {
+//!     match form.value {
+//!         Some(ref value) => value.clone(),
+//!         None => form.context.field_errors("_csrf").map(|e| e.to_string()).collect(),
+//!     }
+//! }
+//! ```
+//!
+//! [`Contextual`]: crate::form::Contextual
+//!
+//! # Configuration
+//!
+//! The names of the token's cookie, form field, and header are read from the
+//! `csrf` configuration parameter into a [`Config`]. The defaults are:
+//!
+//! ```toml
+//! [default.csrf]
+//! cookie_name = "rocket_csrf"
+//! field_name = "_csrf"
+//! header_name = "X-CSRF-Token"
+//! ```
+//!
+//! # Behavior
+//!
+//! The fairing issues a token, setting the token cookie, to every request
+//! without one. The token lasts as long as the client's browser session.
+//!
+//! Requests with a method that supports a payload, `POST`, `PUT`, `PATCH`, or
+//! `DELETE`, are verified when their data is parsed by [`Form`]: the request
+//! must carry the client's token in the configured header or in the first form
+//! field with the configured name. The field is removed from the form before
+//! it's parsed. If verification fails, the `Form` data guard fails with `403
+//! Forbidden` and an error for the field, which is [`Missing`] if no token was
+//! submitted and a validation error otherwise, unless the form is
+//! `Contextual`, which records the error instead. A form submitted with the
+//! client's first request always fails, since it can't carry a token.
+//!
+//! Only `Form` is verified. Handlers that parse form data in other ways, or
+//! that change state in response to `GET` requests, aren't protected.
+//!
+//! [`Missing`]: crate::form::error::ErrorKind::Missing
+
+mod config;
+mod fairing;
+mod token;
+
+pub(crate) use self::fairing::Check;
+
+pub use self::config::Config;
+pub use self::fairing::Csrf;
+pub use self::token::CsrfToken;
@@ -0,0 +1,124 @@
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+
+use crate::{Request, Rocket, Ignite, Sentinel};
+use crate::request::{self, FromRequest};
+use crate::outcome::Outcome;
+use crate::http::Status;
+use crate::csrf::fairing::Manager;
+
+/// A request guard for the requesting client's CSRF token.
+///
+/// Include the token in every form that changes state, in a hidden field named
+/// by [`field_name()`](CsrfToken::field_name()), or send it in the configured
+/// header from scripts. Forms submitted without the token are rejected by
+/// [`Form`](crate::form::Form).
+///
+/// `CsrfToken` serializes as the token string, so it can be passed directly to
+/// templates. It also implements `Display`.
+///
+/// Using a `CsrfToken` requires attaching the [`Csrf`](crate::csrf::Csrf)
+/// fairing; launch is aborted otherwise.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::csrf::CsrfToken;
+/// use rocket::response::content::Html;
+///
+/// #[get("/comment")]
+/// fn comment(token: CsrfToken<'_>) -> Html<String> {
+///     Html(format!(r#"
+///         <form method="post" action="/comment">
+///             <input type="hidden" name="{}" value="{}">
+///             <textarea name="text"></textarea>
+///             <button>Post</button>
+///         </form>
+///     "#, token.field_name(), token))
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsrfToken<'r> {
+    token: &'r str,
+    field_name: &'r str,
+}
+
+impl<'r> CsrfToken<'r> {
+    /// Returns the token.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::csrf::CsrfToken;
+    ///
+    /// #[get("/token")]
+    /// fn token(token: CsrfToken<'_>) -> String {
+    ///     token.as_str().to_string()
+    /// }
+    /// ```
+    pub fn as_str(&self) -> &'r str {
+        self.token
+    }
+
+    /// Returns the name of the form field that must carry the token.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::csrf::CsrfToken;
+    ///
+    /// #[get("/field")]
+    /// fn field(token: CsrfToken<'_>) -> &str {
+    ///     token.field_name()
+    /// }
+    /// ```
+    pub fn field_name(&self) -> &'r str {
+        self.field_name
+    }
+}
+
+impl fmt::Display for CsrfToken<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.token.fmt(f)
+    }
+}
+
+impl Serialize for CsrfToken<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.token)
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for CsrfToken<'r> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        match req.rocket().state::<Manager>() {
+            Some(manager) => Outcome::Success(CsrfToken {
+                token: &manager.token(req).value,
+                field_name: &manager.config.field_name,
+            }),
+            None => {
+                error_!("`CsrfToken` guard used without attaching the `Csrf` fairing.");
+                Outcome::Failure((Status::InternalServerError, ()))
+            }
+        }
+    }
+}
+
+impl Sentinel for CsrfToken<'_> {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        if rocket.state::<Manager>().is_none() {
+            error!("launching with a `CsrfToken` guard without the `Csrf` fairing.");
+            info_!("Using `CsrfToken` requires attaching `Csrf`.");
+            return true;
+        }
+
+        false
+    }
+}
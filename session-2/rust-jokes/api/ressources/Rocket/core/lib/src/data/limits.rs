@@ -63,8 +63,11 @@ use crate::http::uncased::Uncased;
 /// | `bytes`           | 8KiB    | [`Vec<u8>`]  | data guard                            |
 /// | `json`            | 1MiB    | [`Json`]     | JSON data and form payloads           |
 /// | `msgpack`         | 1MiB    | [`MsgPack`]  | MessagePack data and form payloads    |
+/// | `multipart/$name` | _N/A_   | [`Multipart`]| streamed form part named `$name`      |
+/// | `upload`          | 1GiB    | `Uploads`    | entire resumable upload               |
 ///
 /// [`TempFile`]: crate::fs::TempFile
+/// [`Multipart`]: crate::form::Multipart
 /// [`Json`]: crate::serde::json::Json
 /// [`MsgPack`]: crate::serde::msgpack::MsgPack
 ///
@@ -136,6 +139,7 @@ impl Default for Limits {
             .limit("bytes", Limits::BYTES)
             .limit("json", Limits::JSON)
             .limit("msgpack", Limits::MESSAGE_PACK)
+            .limit("upload", Limits::UPLOAD)
     }
 }
 
@@ -161,6 +165,9 @@ impl Limits {
     /// Default limit for MessagePack payloads.
     pub const MESSAGE_PACK: ByteUnit = ByteUnit::Mebibyte(1);
 
+    /// Default limit for resumable uploads.
+    pub const UPLOAD: ByteUnit = ByteUnit::Gibibyte(1);
+
     /// Construct a new `Limits` structure with no limits set.
     ///
     /// # Example
@@ -239,6 +239,18 @@ impl<F: Future, I: AsyncWrite> CancellableIo<F, I> {
     }
 }
 
+/// Returns a random alphanumeric string of length `len`, suitable for ids,
+/// tokens, and nonces.
+pub(crate) fn random_token(len: usize) -> String {
+    use rand::{Rng, distributions::Alphanumeric};
+
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
 fn time_out() -> io::Error {
     io::Error::new(io::ErrorKind::TimedOut, "Shutdown grace timed out")
 }
@@ -293,12 +305,16 @@ impl<F: Future, I: AsyncWrite> AsyncWrite for CancellableIo<F, I> {
     }
 }
 
-use crate::http::private::{Listener, Connection};
+use crate::http::private::{Listener, Connection, RawCertificate};
 
 impl<F: Future, C: Connection> Connection for CancellableIo<F, C> {
     fn remote_addr(&self) -> Option<std::net::SocketAddr> {
         self.io.remote_addr()
     }
+
+    fn peer_certificates(&self) -> Option<Vec<RawCertificate>> {
+        self.io.peer_certificates()
+    }
 }
 
 pin_project! {
@@ -8,6 +8,8 @@ use crate::http::Status;
 ///
 /// This form guard _never fails_. It should be use _only_ when the form
 /// [`Context`] is required. In all other cases, prefer to use `T` directly.
+/// The enclosing [`Form`] may still fail, however, if the form fails CSRF
+/// verification.
 ///
 /// # Usage
 ///
@@ -86,6 +88,8 @@ pub struct Context<'v> {
     form_errors: Errors<'v>,
     #[serde(skip)]
     status: Status,
+    #[serde(skip)]
+    rejected: bool,
 }
 
 impl<'v> Context<'v> {
@@ -383,8 +387,15 @@ impl<'v, T: FromForm<'v>> FromForm<'v> for Contextual<'v, T> {
         ctxt.push_error(e);
     }
 
+    fn push_rejection((_, ctxt): &mut Self::Context, e: Error<'v>) -> Result<'v, ()> {
+        ctxt.push_error(e);
+        ctxt.rejected = true;
+        Ok(())
+    }
+
     fn finalize((val_ctxt, mut context): Self::Context) -> Result<'v, Self> {
         let value = match T::finalize(val_ctxt) {
+            Ok(_) if context.rejected => None,
             Ok(value) => Some(value),
             Err(errors) => {
                 context.push_errors(errors);
@@ -24,6 +24,15 @@ use crate::form::prelude::*;
 /// "a" and "c", the form _will_ parse as `Form<T>`. To parse strictly, use the
 /// [`Strict`](crate::form::Strict) form guard.
 ///
+/// # CSRF Protection
+///
+/// When the `csrf` feature is enabled and the [`Csrf`](crate::csrf::Csrf)
+/// fairing is attached, `Form` verifies the CSRF token submitted with `POST`,
+/// `PUT`, `PATCH`, and `DELETE` requests before finalizing `T`, removing the
+/// token field from the form. If verification fails, the guard fails with `403
+/// Forbidden` even if `T` is [`Contextual`](crate::form::Contextual). See the
+/// [`csrf`](crate::csrf) module docs for details.
+///
 /// # Usage
 ///
 /// This type can be used with any type that implements the `FromForm` trait.
@@ -304,14 +313,25 @@ impl<'r, T: FromForm<'r>> FromData<'r> for Form<T> {
 
         let mut parser = try_outcome!(Parser::new(req, data).await);
         let mut context = T::init(Options::Lenient);
+        #[cfg(feature = "csrf")]
+        let mut csrf = crate::csrf::Check::new(req);
         while let Some(field) = parser.next().await {
             match field {
+                #[cfg(feature = "csrf")]
+                Ok(Either::Left(value)) if csrf.intercept(&value) => continue,
                 Ok(Either::Left(value)) => T::push_value(&mut context, value),
                 Ok(Either::Right(data)) => T::push_data(&mut context, data).await,
                 Err(e) => T::push_error(&mut context, e),
             }
         }
 
+        #[cfg(feature = "csrf")]
+        if let Err(e) = csrf.verify() {
+            if let Err(e) = T::push_rejection(&mut context, e) {
+                return Outcome::Failure((crate::http::Status::Forbidden, e));
+            }
+        }
+
         match T::finalize(context) {
             Ok(value) => Outcome::Success(Form(value)),
             Err(e) => Outcome::Failure((e.status(), e)),
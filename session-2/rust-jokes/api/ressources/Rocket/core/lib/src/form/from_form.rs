@@ -537,6 +537,19 @@ pub trait FromForm<'r>: Send + Sized {
     /// The default implementation does nothing, which is always correct.
     fn push_error(_ctxt: &mut Self::Context, _error: Error<'r>) { }
 
+    /// Processes `error`, which rejects the entire form, such as a failed CSRF
+    /// check. Returns `Ok` if the error was recorded so that the form can
+    /// still be finalized and the error inspected, as [`Contextual`] does, or
+    /// gives `error` back if the form must fail with it.
+    ///
+    /// The default implementation returns `Err(error)`.
+    ///
+    /// [`Contextual`]: crate::form::Contextual
+    #[doc(hidden)]
+    fn push_rejection(_ctxt: &mut Self::Context, error: Error<'r>) -> Result<'r, ()> {
+        Err(error.into())
+    }
+
     /// Finalizes parsing. Returns the parsed value when successful or
     /// collection of [`Errors`] otherwise.
     fn finalize(ctxt: Self::Context) -> Result<'r, Self>;
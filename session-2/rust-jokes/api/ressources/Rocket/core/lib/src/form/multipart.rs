@@ -0,0 +1,275 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures::stream::{Stream, StreamExt, TryStreamExt};
+use tokio::io::AsyncRead;
+
+use crate::Request;
+#[cfg(feature = "csrf")]
+use crate::request::local_cache;
+use crate::data::{Data, FromData, Limits, Outcome, ByteUnit};
+use crate::form::{Error, Errors};
+use crate::fs::FileName;
+use crate::http::ContentType;
+
+/// A data guard that streams the parts of a `multipart/form-data` body.
+///
+/// Unlike [`Form`](crate::form::Form), which parses every part before the
+/// handler runs, spooling files into [`TempFile`](crate::fs::TempFile)s,
+/// `Multipart` yields each [`Part`] as it's received, in order, with its data
+/// as a stream. Handlers can thus pipe uploads elsewhere without buffering
+/// them in memory or on disk. A part must be read, or dropped, before the next
+/// one is requested; unread data is skipped.
+///
+/// The guard forwards requests that aren't `multipart/form-data`.
+///
+/// # Limits
+///
+/// The body as a whole is limited by the `data-form` limit. Each part is
+/// further limited, by name, by the `multipart/<name>` limit, if one is set,
+/// and otherwise like the corresponding `Form` field: by the `file/<ext>` or
+/// `file` limit for parts with a `Content-Type`, and by the `string` limit for
+/// those without. A part's stream fails with an error of kind
+/// [`io::ErrorKind::InvalidData`] once the part exceeds its limit.
+///
+/// # CSRF
+///
+/// If the `csrf` feature is enabled and the [`Csrf`] fairing is attached, a
+/// request that doesn't carry the CSRF token in a header must send it in a
+/// part preceding all others. The part is consumed by the guard, which fails
+/// with a status of `403 Forbidden` if the token is missing or invalid.
+///
+/// [`Csrf`]: crate::csrf::Csrf
+///
+/// # Example
+///
+/// Write every file in an upload to standard output, as it arrives:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::form::Multipart;
+/// use rocket::http::Status;
+/// use rocket::tokio::io;
+///
+/// #[post("/upload", data = "<form>")]
+/// async fn upload(mut form: Multipart<'_>) -> Result<&'static str, Status> {
+///     while let Some(part) = form.next().await.map_err(|e| e.status())? {
+///         if part.is_file() {
+///             io::copy(&mut part.into_reader(), &mut io::stdout()).await
+///                 .map_err(|_| Status::PayloadTooLarge)?;
+///         }
+///     }
+///
+///     Ok("done")
+/// }
+/// ```
+pub struct Multipart<'r> {
+    source: multer::Multipart<'r>,
+    limits: &'r Limits,
+    pending: Option<Part<'r>>,
+}
+
+/// A part of a `multipart/form-data` body, yielded by [`Multipart`].
+///
+/// A `Part` is a [`Stream`] of the chunks of its data; it can also be read as
+/// an [`AsyncRead`] via [`Part::into_reader()`], or collected into a `String`
+/// via [`Part::text()`]. All of these enforce the part's
+/// [limit](Multipart#limits).
+pub struct Part<'r> {
+    field: multer::Field<'r>,
+    name: Option<String>,
+    file_name: Option<String>,
+    content_type: Option<ContentType>,
+    limit: ByteUnit,
+    read: u64,
+}
+
+impl<'r> Multipart<'r> {
+    /// Returns the next part of the body, or `None` if there are no more.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::form::Multipart;
+    /// use rocket::http::Status;
+    ///
+    /// #[post("/names", data = "<form>")]
+    /// async fn names(mut form: Multipart<'_>) -> Result<String, Status> {
+    ///     let mut names = vec![];
+    ///     while let Some(part) = form.next().await.map_err(|e| e.status())? {
+    ///         names.push(part.name().unwrap_or_default().to_string());
+    ///     }
+    ///
+    ///     Ok(names.join(", "))
+    /// }
+    /// ```
+    pub async fn next(&mut self) -> Result<Option<Part<'r>>, Errors<'r>> {
+        match self.pending.take() {
+            Some(part) => Ok(Some(part)),
+            None => self.next_part().await,
+        }
+    }
+
+    async fn next_part(&mut self) -> Result<Option<Part<'r>>, Errors<'r>> {
+        let field = match self.source.next_field().await.map_err(Error::from)? {
+            Some(field) => field,
+            None => return Ok(None),
+        };
+
+        trace_!("multipart part: {:?}", field);
+        let name = field.name().map(|name| name.to_string());
+        let file_name = field.file_name().map(|name| name.to_string());
+        let content_type = field.content_type()
+            .and_then(|m| m.as_ref().parse::<ContentType>().ok());
+
+        let limit = self.limits.find(["multipart", name.as_deref().unwrap_or("")])
+            .or_else(|| match content_type {
+                Some(ref ct) => ct.extension()
+                    .and_then(|ext| self.limits.find(["file", ext.as_str()]))
+                    .or_else(|| self.limits.get("file")),
+                None => self.limits.get("string"),
+            })
+            .unwrap_or(match content_type {
+                Some(_) => Limits::FILE,
+                None => Limits::STRING,
+            });
+
+        Ok(Some(Part { field, name, file_name, content_type, limit, read: 0 }))
+    }
+}
+
+impl<'r> Part<'r> {
+    /// Returns the name of the part, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the file name of the part, if it has one.
+    pub fn file_name(&self) -> Option<&FileName> {
+        self.file_name.as_deref().map(FileName::new)
+    }
+
+    /// Returns the `Content-Type` of the part, if it has one.
+    pub fn content_type(&self) -> Option<&ContentType> {
+        self.content_type.as_ref()
+    }
+
+    /// Returns `true` if the part has a `Content-Type`, as files do. A `Form`
+    /// parses such parts as data fields and others as value fields.
+    pub fn is_file(&self) -> bool {
+        self.content_type.is_some()
+    }
+
+    /// Returns the maximum number of bytes the part may contain.
+    pub fn limit(&self) -> ByteUnit {
+        self.limit
+    }
+
+    /// Reads the rest of the part into a `String`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::form::Multipart;
+    ///
+    /// #[post("/first", data = "<form>")]
+    /// async fn first(mut form: Multipart<'_>) -> Option<String> {
+    ///     form.next().await.ok()??.text().await.ok()
+    /// }
+    /// ```
+    pub async fn text(self) -> io::Result<String> {
+        let bytes: Vec<u8> = self.map_ok(|chunk| chunk.to_vec()).try_concat().await?;
+        String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns an [`AsyncRead`] of the rest of the part.
+    pub fn into_reader(self) -> impl AsyncRead + Send + Unpin + 'r {
+        tokio_util::io::StreamReader::new(self)
+    }
+}
+
+impl Stream for Part<'_> {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.read > self.limit.as_u64() {
+            return Poll::Ready(None);
+        }
+
+        let chunk = match futures::ready!(self.field.poll_next_unpin(cx)) {
+            Some(Ok(chunk)) => chunk,
+            Some(Err(e)) => {
+                return Poll::Ready(Some(Err(io::Error::new(io::ErrorKind::Other, e))));
+            }
+            None => return Poll::Ready(None),
+        };
+
+        self.read += chunk.len() as u64;
+        if self.read > self.limit.as_u64() {
+            let msg = format!("multipart part exceeds its limit of {}", self.limit);
+            return Poll::Ready(Some(Err(io::Error::new(io::ErrorKind::InvalidData, msg))));
+        }
+
+        Poll::Ready(Some(Ok(chunk)))
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromData<'r> for Multipart<'r> {
+    type Error = Errors<'r>;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        let boundary = match req.content_type() {
+            Some(ct) if ct.is_form_data() => match ct.param("boundary") {
+                Some(boundary) => boundary,
+                None => {
+                    let error = Error::from(multer::Error::NoBoundary);
+                    return Outcome::Failure((error.status(), error.into()));
+                }
+            },
+            _ => return Outcome::Forward(data),
+        };
+
+        let limit = req.limits().get("data-form").unwrap_or(Limits::DATA_FORM);
+        #[cfg_attr(not(feature = "csrf"), allow(unused_mut))]
+        let mut multipart = Multipart {
+            source: multer::Multipart::with_reader(data.open(limit), boundary),
+            limits: req.limits(),
+            pending: None,
+        };
+
+        // The token, if it isn't in a header, must be in the first part.
+        #[cfg(feature = "csrf")]
+        {
+            let mut csrf = crate::csrf::Check::new(req);
+            if let Some(name) = csrf.field_name() {
+                let mut part = match multipart.next_part().await {
+                    Ok(part) => part,
+                    Err(e) => return Outcome::Failure((e.status(), e)),
+                };
+
+                if matches!(&part, Some(p) if !p.is_file() && p.name() == Some(name)) {
+                    match part.take().expect("token part").text().await {
+                        Ok(token) => csrf.submit(local_cache!(req, token)),
+                        Err(e) => {
+                            let error = Error::from(e).with_name(name);
+                            return Outcome::Failure((error.status(), error.into()));
+                        }
+                    }
+                }
+
+                if let Err(e) = csrf.verify() {
+                    return Outcome::Failure((crate::http::Status::Forbidden, e.into()));
+                }
+
+                multipart.pending = part;
+            }
+        }
+
+        Outcome::Success(multipart)
+    }
+}
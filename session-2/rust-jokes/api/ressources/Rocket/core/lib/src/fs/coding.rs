@@ -0,0 +1,116 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use crate::request::Request;
+use crate::accept_encoding::AcceptEncoding;
+
+/// The content coding of a file served by [`FileServer`] with
+/// [`Options::Precompressed`].
+///
+/// [`FileServer`]: crate::fs::FileServer
+/// [`Options::Precompressed`]: crate::fs::Options::Precompressed
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Coding {
+    /// The raw file, served because no precompressed sibling was acceptable.
+    Identity,
+    /// A `.br` sibling.
+    Brotli,
+    /// A `.gz` sibling.
+    Gzip,
+}
+
+impl Coding {
+    /// Precompressed codings, in order of preference.
+    const PRECOMPRESSED: [Coding; 2] = [Coding::Brotli, Coding::Gzip];
+
+    /// The value of the `Content-Encoding` header for this coding, if any.
+    pub fn content_encoding(self) -> Option<&'static str> {
+        match self {
+            Coding::Identity => None,
+            Coding::Brotli => Some("br"),
+            Coding::Gzip => Some("gzip"),
+        }
+    }
+
+    /// The names of this coding in `Accept-Encoding` headers.
+    fn names(self) -> &'static [&'static str] {
+        match self {
+            Coding::Identity => &["identity"],
+            Coding::Brotli => &["br"],
+            Coding::Gzip => &["gzip", "x-gzip"],
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Coding::Identity => "",
+            Coding::Brotli => ".br",
+            Coding::Gzip => ".gz",
+        }
+    }
+
+    /// The path of the sibling of `path` with this coding.
+    pub fn sibling(self, path: &Path) -> PathBuf {
+        let mut path = OsString::from(path);
+        path.push(self.extension());
+        path.into()
+    }
+
+    /// Returns the precompressed codings acceptable to the client according to
+    /// the `Accept-Encoding` header in `req`, most preferred first. A coding
+    /// is acceptable if it, or `*`, is listed with a nonzero q-value. Ties are
+    /// broken in favor of `br`.
+    pub fn acceptable(req: &Request<'_>) -> Vec<Coding> {
+        let accept = AcceptEncoding::parse(req.headers().get("Accept-Encoding"));
+        let mut codings: Vec<(Coding, f32)> = Self::PRECOMPRESSED.iter()
+            .map(|coding| (*coding, accept.quality(coding.names())))
+            .filter(|(_, q)| *q > 0.0)
+            .collect();
+
+        // The sort is stable, so ties retain the order of preference.
+        codings.sort_by(|(_, a), (_, b)| b.partial_cmp(a).expect("q-values are finite"));
+        codings.into_iter().map(|(coding, _)| coding).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::Coding::{self, *};
+    use crate::local::blocking::Client;
+    use crate::http::Header;
+
+    fn acceptable(accept: Option<&str>) -> Vec<Coding> {
+        let client = Client::debug_with(vec![]).unwrap();
+        let mut request = client.get("/");
+        if let Some(accept) = accept {
+            request.add_header(Header::new("Accept-Encoding", accept.to_string()));
+        }
+
+        Coding::acceptable(request.inner())
+    }
+
+    #[test]
+    fn test_acceptable() {
+        assert_eq!(acceptable(None), vec![]);
+        assert_eq!(acceptable(Some("identity")), vec![]);
+        assert_eq!(acceptable(Some("deflate")), vec![]);
+        assert_eq!(acceptable(Some("gzip")), vec![Gzip]);
+        assert_eq!(acceptable(Some("x-gzip")), vec![Gzip]);
+        assert_eq!(acceptable(Some("gzip, deflate, br")), vec![Brotli, Gzip]);
+        assert_eq!(acceptable(Some("*")), vec![Brotli, Gzip]);
+        assert_eq!(acceptable(Some("br;q=0.5, gzip")), vec![Gzip, Brotli]);
+        assert_eq!(acceptable(Some("*;q=0.2, gzip;q=0.1")), vec![Brotli, Gzip]);
+        assert_eq!(acceptable(Some("*, br;q=0")), vec![Gzip]);
+        assert_eq!(acceptable(Some("br;q=2, gzip;q=x")), vec![]);
+    }
+
+    #[test]
+    fn test_sibling() {
+        let path = Path::new("static/app.min.js");
+        assert_eq!(Brotli.sibling(path), Path::new("static/app.min.js.br"));
+        assert_eq!(Gzip.sibling(path), Path::new("static/app.min.js.gz"));
+        assert_eq!(Identity.sibling(path), path);
+    }
+}
@@ -0,0 +1,84 @@
+use std::fs::Metadata;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::request::Request;
+use crate::http::Header;
+
+/// Validators of a file's current representation, derived from its metadata:
+/// an entity tag and a last-modified date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Validators {
+    etag: String,
+    last_modified: SystemTime,
+}
+
+impl Validators {
+    /// Returns the validators for a file with metadata `metadata`, or `None`
+    /// if the file's modification time is unavailable.
+    pub fn new(metadata: &Metadata) -> Option<Self> {
+        let last_modified = metadata.modified().ok()?;
+        let nanos = last_modified.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+        let etag = format!("\"{:x}-{:x}\"", nanos, metadata.len());
+        Some(Validators { etag, last_modified })
+    }
+
+    /// The `ETag` and `Last-Modified` headers for these validators.
+    pub fn headers(&self) -> [Header<'static>; 2] {
+        [
+            Header::new("ETag", self.etag.clone()),
+            Header::new("Last-Modified", httpdate::fmt_http_date(self.last_modified)),
+        ]
+    }
+
+    /// Returns `true` if the client's cached representation, as identified by
+    /// the `If-None-Match` or, in its absence, `If-Modified-Since` header of
+    /// `req`, is current. `If-None-Match` uses weak comparison.
+    pub fn is_fresh(&self, req: &Request<'_>) -> bool {
+        let mut if_none_match = req.headers().get("If-None-Match").peekable();
+        if if_none_match.peek().is_some() {
+            return if_none_match
+                .flat_map(|value| value.split(','))
+                .map(|tag| tag.trim())
+                .any(|tag| tag == "*" || weak_eq(tag, &self.etag));
+        }
+
+        match req.headers().get_one("If-Modified-Since") {
+            Some(date) => match httpdate::parse_http_date(date) {
+                Ok(since) => unix_secs(self.last_modified) <= unix_secs(since),
+                Err(_) => false,
+            },
+            None => false,
+        }
+    }
+
+    /// Returns `true` if a `Range` in `req` should be honored: there is no
+    /// `If-Range` header, or it matches these validators. An entity tag must
+    /// match strongly while a date must match exactly.
+    pub fn if_range_matches(&self, req: &Request<'_>) -> bool {
+        let value = match req.headers().get_one("If-Range") {
+            Some(value) => value.trim(),
+            None => return true,
+        };
+
+        if value.starts_with('"') {
+            return value == self.etag;
+        } else if value.starts_with("W/") {
+            return false;
+        }
+
+        match httpdate::parse_http_date(value) {
+            Ok(date) => unix_secs(date) == unix_secs(self.last_modified),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Compares two entity tags using the weak comparison function.
+fn weak_eq(a: &str, b: &str) -> bool {
+    a.trim_start_matches("W/") == b.trim_start_matches("W/")
+}
+
+/// HTTP dates have second precision, so comparisons are made in seconds.
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
@@ -0,0 +1,192 @@
+use std::io;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::request::Request;
+use crate::response::{self, Responder};
+use crate::http::{ContentType, RawStr};
+use crate::log::write_json_str;
+
+/// A file or directory in a [`Listing`].
+#[derive(Debug)]
+struct Entry {
+    name: String,
+    is_dir: bool,
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+/// A listing of the contents of a directory, rendered as HTML or, when the
+/// client prefers it, as JSON.
+#[derive(Debug)]
+pub(crate) struct Listing {
+    /// The decoded request path, for display.
+    path: String,
+    /// The encoded request path with a trailing slash, for links.
+    base: String,
+    /// Whether the directory is the root of the `FileServer`.
+    is_root: bool,
+    entries: Vec<Entry>,
+}
+
+impl Listing {
+    /// Reads the directory at `dir`, which `req` resolved to, skipping
+    /// entries that the `FileServer` wouldn't serve: dotfiles, unless
+    /// `allow_dotfiles`, and names that can't be requested as a path segment.
+    pub async fn read(
+        dir: &Path,
+        req: &Request<'_>,
+        is_root: bool,
+        allow_dotfiles: bool
+    ) -> io::Result<Listing> {
+        let mut entries = vec![];
+        let mut dir_entries = tokio::fs::read_dir(dir).await?;
+        while let Some(dir_entry) = dir_entries.next_entry().await? {
+            let name = match dir_entry.file_name().into_string() {
+                Ok(name) if is_servable(&name, allow_dotfiles) => name,
+                _ => continue,
+            };
+
+            // Follow symlinks, skipping dangling ones.
+            let metadata = match tokio::fs::metadata(dir_entry.path()).await {
+                Ok(metadata) => metadata,
+                Err(_) => continue,
+            };
+
+            entries.push(Entry {
+                name,
+                is_dir: metadata.is_dir(),
+                size: if metadata.is_dir() { 0 } else { metadata.len() },
+                modified: metadata.modified().ok(),
+            });
+        }
+
+        entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+
+        let path = req.uri().path();
+        let base = format!("{}/", path.as_str().trim_end_matches('/'));
+        let path = path.percent_decode_lossy().into_owned();
+        Ok(Listing { path, base, is_root, entries })
+    }
+
+    fn href(&self, entry: &Entry) -> String {
+        let name = RawStr::new(&entry.name).percent_encode();
+        let slash = if entry.is_dir { "/" } else { "" };
+        format!("{}{}{}", self.base, name, slash)
+    }
+
+    fn html(&self) -> String {
+        let title = RawStr::new(&self.path).html_escape();
+        let mut html = String::new();
+        let _ = write!(html, "<!DOCTYPE html>\n<html>\n<head>\n\
+            <meta charset=\"utf-8\">\n<title>Index of {0}</title>\n</head>\n<body>\n\
+            <h1>Index of {0}</h1>\n<table>\n\
+            <thead><tr><th>Name</th><th>Size</th><th>Modified</th></tr></thead>\n\
+            <tbody>\n", title);
+
+        if !self.is_root {
+            html.push_str("<tr><td><a href=\"../\">../</a></td><td></td><td></td></tr>\n");
+        }
+
+        for entry in &self.entries {
+            let href = RawStr::new(&self.href(entry)).html_escape().into_owned();
+            let name = RawStr::new(&entry.name).html_escape().into_owned();
+            let slash = if entry.is_dir { "/" } else { "" };
+            let size = if entry.is_dir { "-".into() } else { entry.size.to_string() };
+            let modified = entry.modified.map(httpdate::fmt_http_date).unwrap_or_default();
+            let _ = writeln!(html,
+                "<tr><td><a href=\"{}\">{}{}</a></td><td>{}</td><td>{}</td></tr>",
+                href, name, slash, size, modified);
+        }
+
+        html.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+        html
+    }
+
+    fn json(&self) -> String {
+        let mut json = String::from("{\"path\":");
+        write_json_str(&mut json, &self.path);
+        json.push_str(",\"entries\":[");
+        for (i, entry) in self.entries.iter().enumerate() {
+            let modified = entry.modified
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_secs().to_string())
+                .unwrap_or_else(|| "null".into());
+
+            json.push_str(if i > 0 { ",{\"name\":" } else { "{\"name\":" });
+            write_json_str(&mut json, &entry.name);
+            let _ = write!(json, ",\"type\":\"{}\",\"size\":{},\"modified\":{}}}",
+                if entry.is_dir { "directory" } else { "file" },
+                entry.size,
+                modified);
+        }
+
+        json.push_str("]}");
+        json
+    }
+}
+
+/// Returns `true` if an entry named `name` can be requested from a
+/// `FileServer`, mirroring the checks in `Segments::to_path_buf()`.
+fn is_servable(name: &str, allow_dotfiles: bool) -> bool {
+    !(name.starts_with('*')
+        || (name.starts_with('.') && !allow_dotfiles)
+        || name.ends_with(':')
+        || name.ends_with('>')
+        || name.ends_with('<')
+        || (cfg!(windows) && name.contains('\\')))
+}
+
+/// Renders the listing as JSON if the client's preferred media type is JSON
+/// and as HTML otherwise.
+impl<'r> Responder<'r, 'static> for Listing {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let json = matches!(req.accept(), Some(accept) if accept.preferred().is_json());
+        let mut response = match json {
+            true => (ContentType::JSON, self.json()).respond_to(req)?,
+            false => (ContentType::HTML, self.html()).respond_to(req)?,
+        };
+
+        response.adjoin_raw_header("Vary", "Accept");
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_servable() {
+        assert!(is_servable("index.html", false));
+        assert!(is_servable("a b", false));
+        assert!(is_servable(".hidden", true));
+        assert!(!is_servable(".hidden", false));
+        assert!(!is_servable("*star", true));
+        assert!(!is_servable("colon:", true));
+        assert!(!is_servable("angle<", true));
+    }
+
+    #[test]
+    fn test_json() {
+        let entry = |name: &str, is_dir| Entry {
+            name: name.into(),
+            is_dir,
+            size: 3,
+            modified: Some(UNIX_EPOCH + std::time::Duration::from_secs(60)),
+        };
+
+        let listing = Listing {
+            path: "/a \"b\"/".into(),
+            base: "/a%20%22b%22/".into(),
+            is_root: false,
+            entries: vec![entry("dir", true), entry("ünï\n", false)],
+        };
+
+        assert_eq!(listing.json(), concat!(
+            r#"{"path":"/a \"b\"/","entries":["#,
+            r#"{"name":"dir","type":"directory","size":3,"modified":60},"#,
+            r#"{"name":"ünï\n","type":"file","size":3,"modified":60}]}"#));
+    }
+}
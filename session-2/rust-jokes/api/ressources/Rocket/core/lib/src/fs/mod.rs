@@ -4,6 +4,10 @@ mod server;
 mod named_file;
 mod temp_file;
 mod file_name;
+mod range;
+mod conditional;
+mod coding;
+mod listing;
 
 pub use server::*;
 pub use named_file::*;
@@ -1,12 +1,17 @@
 use std::io;
+use std::fs::Metadata;
 use std::path::{Path, PathBuf};
 use std::ops::{Deref, DerefMut};
 
 use tokio::fs::File;
 
 use crate::request::Request;
-use crate::response::{self, Responder};
-use crate::http::ContentType;
+use crate::response::{self, Responder, Response};
+use crate::http::{ContentType, Method, Status};
+use crate::fs::Options;
+use crate::fs::coding::Coding;
+use crate::fs::conditional::Validators;
+use crate::fs::range::{parse_ranges, ByteRange, RangeReader};
 
 /// A [`Responder`] that sends a file with a Content-Type based on its name.
 ///
@@ -34,9 +39,23 @@ use crate::http::ContentType;
 /// Always prefer to use [`FileServer`] which has more functionality and a
 /// pithier API.
 ///
+/// # Ranges and Conditional Requests
+///
+/// By default, a `NamedFile` always responds with the entire file. Range
+/// requests and conditional requests can be enabled with
+/// [`NamedFile::with_options()`] by passing [`Options::Ranges`] and
+/// [`Options::Conditional`], respectively. See the documentation for those
+/// options for details.
+///
 /// [`FileServer`]: crate::fs::FileServer
 #[derive(Debug)]
-pub struct NamedFile(PathBuf, File);
+pub struct NamedFile {
+    path: PathBuf,
+    file: File,
+    metadata: Metadata,
+    options: Options,
+    coding: Option<Coding>,
+}
 
 impl NamedFile {
     /// Attempts to open a file in read-only mode.
@@ -64,7 +83,49 @@ impl NamedFile {
         // all of those `seek`s to determine the file size. But, what happens if
         // the file gets changed between now and then?
         let file = File::open(path.as_ref()).await?;
-        Ok(NamedFile(path.as_ref().to_path_buf(), file))
+        let metadata = file.metadata().await?;
+        let path = path.as_ref().to_path_buf();
+        Ok(NamedFile { path, file, metadata, options: Options::None, coding: None })
+    }
+
+    /// Sets the options that apply to `self` to `options`. Only
+    /// [`Options::Ranges`] and [`Options::Conditional`] affect a `NamedFile`;
+    /// all other options are ignored. By default, no options are set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::get;
+    /// use rocket::fs::{NamedFile, Options};
+    ///
+    /// #[get("/video")]
+    /// async fn video() -> Option<NamedFile> {
+    ///     let file = NamedFile::open("video.mp4").await.ok()?;
+    ///     Some(file.with_options(Options::Ranges | Options::Conditional))
+    /// }
+    /// ```
+    pub fn with_options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Opens the variant of the file at `path` with the most preferred
+    /// precompressed coding acceptable to the client that made `req`, falling
+    /// back to `path` itself. Variants that aren't regular files are skipped.
+    pub(crate) async fn open_precompressed(path: &Path, req: &Request<'_>) -> io::Result<Self> {
+        for coding in Coding::acceptable(req) {
+            if let Ok(mut file) = NamedFile::open(coding.sibling(path)).await {
+                if file.metadata.is_file() {
+                    file.path = path.to_path_buf();
+                    file.coding = Some(coding);
+                    return Ok(file);
+                }
+            }
+        }
+
+        let mut file = NamedFile::open(path).await?;
+        file.coding = Some(Coding::Identity);
+        Ok(file)
     }
 
     /// Retrieve the underlying `File`.
@@ -82,7 +143,7 @@ impl NamedFile {
     /// ```
     #[inline(always)]
     pub fn file(&self) -> &File {
-        &self.1
+        &self.file
     }
 
     /// Retrieve a mutable borrow to the underlying `File`.
@@ -100,7 +161,7 @@ impl NamedFile {
     /// ```
     #[inline(always)]
     pub fn file_mut(&mut self) -> &mut File {
-        &mut self.1
+        &mut self.file
     }
 
     /// Take the underlying `File`.
@@ -118,7 +179,7 @@ impl NamedFile {
     /// ```
     #[inline(always)]
     pub fn take_file(self) -> File {
-        self.1
+        self.file
     }
 
     /// Retrieve the path of this file.
@@ -136,7 +197,7 @@ impl NamedFile {
     /// ```
     #[inline(always)]
     pub fn path(&self) -> &Path {
-        self.0.as_path()
+        self.path.as_path()
     }
 }
 
@@ -145,12 +206,57 @@ impl NamedFile {
 /// recognized. See [`ContentType::from_extension()`] for more information. If
 /// you would like to stream a file with a different Content-Type than that
 /// implied by its extension, use a [`File`] directly.
+///
+/// If [`Options::Conditional`] or [`Options::Ranges`] are set, conditional
+/// and range requests, respectively, are honored for `GET` and `HEAD`
+/// requests as described in the documentation for those options.
 impl<'r> Responder<'r, 'static> for NamedFile {
     fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
-        let mut response = self.1.respond_to(req)?;
-        if let Some(ext) = self.0.extension() {
-            if let Some(ct) = ContentType::from_extension(&ext.to_string_lossy()) {
-                response.set_header(ct);
+        let content_type = self.path.extension()
+            .and_then(|ext| ContentType::from_extension(&ext.to_string_lossy()));
+
+        let conditional = self.options.contains(Options::Conditional);
+        let ranges = self.options.contains(Options::Ranges);
+        let validators = match conditional || ranges {
+            true => Validators::new(&self.metadata),
+            false => None,
+        };
+
+        let cacheable = matches!(req.method(), Method::Get | Method::Head);
+        let mut response = match validators {
+            Some(ref v) if conditional && cacheable && v.is_fresh(req) => {
+                Response::build().status(Status::NotModified).finalize()
+            }
+            _ if ranges && cacheable => {
+                let honor_range = match validators {
+                    Some(ref v) => v.if_range_matches(req),
+                    None => !req.headers().contains("If-Range"),
+                };
+
+                let size = self.metadata.len();
+                let range_header = req.headers().get_one("Range").filter(|_| honor_range);
+                match range_header.and_then(|header| parse_ranges(header, size)) {
+                    Some(ranges) => respond_ranged(self.file, size, content_type, &ranges),
+                    None => full(self.file, content_type, req)?,
+                }
+            }
+            _ => full(self.file, content_type, req)?,
+        };
+
+        if ranges {
+            response.set_raw_header("Accept-Ranges", "bytes");
+        }
+
+        if let Some(coding) = self.coding {
+            response.adjoin_raw_header("Vary", "Accept-Encoding");
+            if let Some(encoding) = coding.content_encoding() {
+                response.set_raw_header("Content-Encoding", encoding);
+            }
+        }
+
+        if let Some(validators) = validators.filter(|_| conditional) {
+            for header in validators.headers().iter().cloned() {
+                response.set_header(header);
             }
         }
 
@@ -158,16 +264,71 @@ impl<'r> Responder<'r, 'static> for NamedFile {
     }
 }
 
+/// Responds with the entirety of `file`.
+fn full(
+    file: File,
+    content_type: Option<ContentType>,
+    req: &Request<'_>
+) -> response::Result<'static> {
+    let mut response = file.respond_to(req)?;
+    if let Some(ct) = content_type {
+        response.set_header(ct);
+    }
+
+    Ok(response)
+}
+
+/// Responds with the satisfiable byte `ranges` of `file`, which is `size`
+/// bytes, or a `416 Range Not Satisfiable` if there are none.
+fn respond_ranged(
+    file: File,
+    size: u64,
+    content_type: Option<ContentType>,
+    ranges: &[ByteRange],
+) -> Response<'static> {
+    let mut response = Response::build();
+    match ranges {
+        [] => {
+            response.status(Status::RangeNotSatisfiable)
+                .raw_header("Content-Range", format!("bytes */{}", size));
+        }
+        [range] => {
+            let body = RangeReader::single(file, *range);
+            response.status(Status::PartialContent)
+                .raw_header("Content-Range", range.content_range(size))
+                .sized_body(body.len() as usize, body);
+
+            if let Some(ct) = content_type {
+                response.header(ct);
+            }
+        }
+        ranges => {
+            let boundary = crate::ext::random_token(24);
+
+            let part_type = content_type.unwrap_or(ContentType::Binary);
+            let body = RangeReader::multipart(file, ranges, size, &part_type, &boundary);
+            let content_type = ContentType::with_params("multipart", "byteranges",
+                ("boundary", boundary));
+
+            response.status(Status::PartialContent)
+                .header(content_type)
+                .sized_body(body.len() as usize, body);
+        }
+    }
+
+    response.finalize()
+}
+
 impl Deref for NamedFile {
     type Target = File;
 
     fn deref(&self) -> &File {
-        &self.1
+        &self.file
     }
 }
 
 impl DerefMut for NamedFile {
     fn deref_mut(&mut self) -> &mut File {
-        &mut self.1
+        &mut self.file
     }
 }
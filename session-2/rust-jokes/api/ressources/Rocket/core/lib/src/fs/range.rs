@@ -0,0 +1,326 @@
+use std::io::{self, SeekFrom};
+use std::convert::TryFrom;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+
+use crate::http::ContentType;
+
+/// The maximum number of ranges honored in a single `Range` header. Requests
+/// with more ranges are served in full.
+const MAX_RANGES: usize = 16;
+
+/// A satisfiable byte range of a representation: `start..(start + len)`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct ByteRange {
+    pub start: u64,
+    pub len: u64,
+}
+
+impl ByteRange {
+    /// The value of a `Content-Range` header for `self` in a representation
+    /// of `size` bytes.
+    pub fn content_range(&self, size: u64) -> String {
+        format!("bytes {}-{}/{}", self.start, self.start + self.len - 1, size)
+    }
+}
+
+/// Parses the value of a `Range` header for a representation of `size` bytes.
+///
+/// Returns `None` if the header should be ignored: it's syntactically invalid,
+/// uses a unit other than `bytes`, or requests too many ranges. Otherwise,
+/// returns the satisfiable ranges, in request order, which may be empty.
+pub(crate) fn parse_ranges(header: &str, size: u64) -> Option<Vec<ByteRange>> {
+    let (unit, specs) = header.split_once('=')?;
+    if !unit.trim().eq_ignore_ascii_case("bytes") {
+        return None;
+    }
+
+    let mut ranges = vec![];
+    for (i, spec) in specs.split(',').map(|s| s.trim()).enumerate() {
+        if i >= MAX_RANGES {
+            return None;
+        }
+
+        let (first, last) = spec.split_once('-')?;
+        let (first, last) = (first.trim(), last.trim());
+        let range = if first.is_empty() {
+            // A suffix range: the last `n` bytes.
+            let n: u64 = last.parse().ok()?;
+            match n.min(size) {
+                0 => None,
+                n => Some(ByteRange { start: size - n, len: n }),
+            }
+        } else {
+            let first: u64 = first.parse().ok()?;
+            let last: Option<u64> = match last {
+                "" => None,
+                last => Some(last.parse().ok()?),
+            };
+
+            match last {
+                Some(last) if last < first => return None,
+                _ if first >= size => None,
+                Some(last) => Some(ByteRange { start: first, len: last.min(size - 1) - first + 1 }),
+                None => Some(ByteRange { start: first, len: size - first }),
+            }
+        };
+
+        ranges.extend(range);
+    }
+
+    Some(ranges)
+}
+
+/// A piece of a [`RangeReader`]'s output.
+#[derive(Debug)]
+enum Part {
+    /// Literal bytes.
+    Bytes(Vec<u8>),
+    /// A range of the inner reader.
+    Range(ByteRange),
+}
+
+impl Part {
+    fn len(&self) -> u64 {
+        match self {
+            Part::Bytes(bytes) => bytes.len() as u64,
+            Part::Range(range) => range.len,
+        }
+    }
+}
+
+/// A reader over select ranges of a seekable reader `R`, optionally
+/// interspersed with literal bytes, such as the part headers of a
+/// `multipart/byteranges` body.
+///
+/// The inner reader is seeked lazily, just before reading from it, so a
+/// `RangeReader` can be constructed synchronously.
+#[derive(Debug)]
+pub(crate) struct RangeReader<R> {
+    inner: R,
+    parts: Vec<Part>,
+    /// The total length of all parts.
+    len: u64,
+    /// The current position in the output.
+    pos: u64,
+    /// The known position of the inner reader, if any.
+    inner_pos: Option<u64>,
+    /// The target of an in-progress seek of the inner reader.
+    seeking: Option<u64>,
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> RangeReader<R> {
+    /// A reader over the single `range` of `inner`.
+    pub fn single(inner: R, range: ByteRange) -> Self {
+        Self::new(inner, vec![Part::Range(range)])
+    }
+
+    /// A reader producing a `multipart/byteranges` body with each of `ranges`
+    /// of `inner`, a representation of `size` bytes and type `content_type`,
+    /// separated by `boundary`.
+    pub fn multipart(
+        inner: R,
+        ranges: &[ByteRange],
+        size: u64,
+        content_type: &ContentType,
+        boundary: &str,
+    ) -> Self {
+        let mut parts = Vec::with_capacity(ranges.len() * 2 + 1);
+        for range in ranges {
+            let head = format!("\r\n--{}\r\nContent-Type: {}\r\nContent-Range: {}\r\n\r\n",
+                boundary, content_type, range.content_range(size));
+
+            parts.push(Part::Bytes(head.into_bytes()));
+            parts.push(Part::Range(*range));
+        }
+
+        parts.push(Part::Bytes(format!("\r\n--{}--\r\n", boundary).into_bytes()));
+        Self::new(inner, parts)
+    }
+
+    fn new(inner: R, parts: Vec<Part>) -> Self {
+        let len = parts.iter().map(|p| p.len()).sum();
+        RangeReader { inner, parts, len, pos: 0, inner_pos: None, seeking: None }
+    }
+
+    /// The total number of bytes this reader produces.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns the part containing `pos` and the offset of `pos` within it.
+    fn part_at(&self, pos: u64) -> Option<(&Part, u64)> {
+        let mut start = 0;
+        for part in &self.parts {
+            if pos < start + part.len() {
+                return Some((part, pos - start));
+            }
+
+            start += part.len();
+        }
+
+        None
+    }
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> AsyncRead for RangeReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        loop {
+            let (part, offset) = match this.part_at(this.pos) {
+                Some(found) => found,
+                None => return Poll::Ready(Ok(())),
+            };
+
+            let range = match part {
+                Part::Bytes(bytes) => {
+                    let bytes = &bytes[offset as usize..];
+                    let n = bytes.len().min(buf.remaining());
+                    buf.put_slice(&bytes[..n]);
+                    this.pos += n as u64;
+                    return Poll::Ready(Ok(()));
+                }
+                Part::Range(range) => *range,
+            };
+
+            let target = range.start + offset;
+            if this.inner_pos != Some(target) {
+                if this.seeking != Some(target) {
+                    Pin::new(&mut this.inner).start_seek(SeekFrom::Start(target))?;
+                    this.seeking = Some(target);
+                }
+
+                let pos = futures::ready!(Pin::new(&mut this.inner).poll_complete(cx))?;
+                this.seeking = None;
+                this.inner_pos = Some(pos);
+                continue;
+            }
+
+            let left = usize::try_from(range.len - offset).unwrap_or(usize::MAX);
+            let max = buf.remaining().min(left);
+            let mut limited = ReadBuf::new(buf.initialize_unfilled_to(max));
+            futures::ready!(Pin::new(&mut this.inner).poll_read(cx, &mut limited))?;
+            let n = limited.filled().len();
+            if n == 0 && max > 0 {
+                let msg = "file ended before the end of the requested range";
+                return Poll::Ready(Err(io::Error::new(io::ErrorKind::UnexpectedEof, msg)));
+            }
+
+            buf.advance(n);
+            this.pos += n as u64;
+            this.inner_pos = Some(target + n as u64);
+            return Poll::Ready(Ok(()));
+        }
+    }
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> AsyncSeek for RangeReader<R> {
+    fn start_seek(mut self: Pin<&mut Self>, position: SeekFrom) -> io::Result<()> {
+        let pos = match position {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::End(n) => offset(self.len, n),
+            SeekFrom::Current(n) => offset(self.pos, n),
+        };
+
+        let msg = "invalid seek to a negative or overflowing position";
+        self.pos = pos.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, msg))?;
+        Ok(())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Poll::Ready(Ok(self.pos))
+    }
+}
+
+fn offset(base: u64, n: i64) -> Option<u64> {
+    match n >= 0 {
+        true => base.checked_add(n as u64),
+        false => base.checked_sub(n.unsigned_abs()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+    use super::*;
+
+    fn range(start: u64, len: u64) -> ByteRange {
+        ByteRange { start, len }
+    }
+
+    #[test]
+    fn test_parse_ranges() {
+        assert_eq!(parse_ranges("bytes=0-499", 1000), Some(vec![range(0, 500)]));
+        assert_eq!(parse_ranges("bytes=500-", 1000), Some(vec![range(500, 500)]));
+        assert_eq!(parse_ranges("bytes=-200", 1000), Some(vec![range(800, 200)]));
+        assert_eq!(parse_ranges("bytes=-2000", 1000), Some(vec![range(0, 1000)]));
+        assert_eq!(parse_ranges("bytes=900-2000", 1000), Some(vec![range(900, 100)]));
+        assert_eq!(parse_ranges("Bytes = 0-0 , 2-3", 10), Some(vec![range(0, 1), range(2, 2)]));
+        assert_eq!(parse_ranges("bytes=5-5, -1", 10), Some(vec![range(5, 1), range(9, 1)]));
+
+        // Unsatisfiable.
+        assert_eq!(parse_ranges("bytes=1000-", 1000), Some(vec![]));
+        assert_eq!(parse_ranges("bytes=-0", 1000), Some(vec![]));
+        assert_eq!(parse_ranges("bytes=0-", 0), Some(vec![]));
+        assert_eq!(parse_ranges("bytes=10-20, 1000-1001", 1000), Some(vec![range(10, 11)]));
+
+        // Invalid: ignored.
+        assert_eq!(parse_ranges("bytes=", 1000), None);
+        assert_eq!(parse_ranges("bytes=abc", 1000), None);
+        assert_eq!(parse_ranges("bytes=5-1", 1000), None);
+        assert_eq!(parse_ranges("bytes=1-2,", 1000), None);
+        assert_eq!(parse_ranges("items=0-1", 1000), None);
+        assert_eq!(parse_ranges("0-1", 1000), None);
+
+        let many = format!("bytes={}", vec!["0-1"; MAX_RANGES + 1].join(","));
+        assert_eq!(parse_ranges(&many, 1000), None);
+    }
+
+    #[tokio::test]
+    async fn test_range_reader() {
+        let data: Vec<u8> = (0..100).collect();
+
+        let mut reader = RangeReader::single(Cursor::new(&data), range(10, 5));
+        let mut out = vec![];
+        reader.read_to_end(&mut out).await.unwrap();
+        assert_eq!(out, &data[10..15]);
+
+        let ranges = [range(0, 2), range(98, 2)];
+        let ct = ContentType::Plain;
+        let mut reader = RangeReader::multipart(Cursor::new(&data), &ranges, 100, &ct, "XYZ");
+        let len = reader.len();
+        let mut out = vec![];
+        reader.read_to_end(&mut out).await.unwrap();
+        assert_eq!(out.len() as u64, len);
+
+        let mut expected = b"\r\n--XYZ\r\nContent-Type: text/plain; charset=utf-8\r\n\
+            Content-Range: bytes 0-1/100\r\n\r\n".to_vec();
+        expected.extend_from_slice(&data[0..2]);
+        expected.extend_from_slice(b"\r\n--XYZ\r\nContent-Type: text/plain; charset=utf-8\r\n\
+            Content-Range: bytes 98-99/100\r\n\r\n");
+        expected.extend_from_slice(&data[98..100]);
+        expected.extend_from_slice(b"\r\n--XYZ--\r\n");
+        assert_eq!(out, expected);
+
+        let end = reader.seek(SeekFrom::End(0)).await.unwrap();
+        assert_eq!(end, len);
+        let start = reader.seek(SeekFrom::Current(-(len as i64))).await.unwrap();
+        assert_eq!(start, 0);
+        assert!(reader.seek(SeekFrom::Current(-1)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_range_reader_truncated() {
+        let data: Vec<u8> = (0..10).collect();
+        let mut reader = RangeReader::single(Cursor::new(&data), range(5, 10));
+        assert!(reader.read_to_end(&mut vec![]).await.is_err());
+    }
+}
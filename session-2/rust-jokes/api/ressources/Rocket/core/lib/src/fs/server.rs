@@ -5,6 +5,7 @@ use crate::http::{Method, uri::Segments, ext::IntoOwned};
 use crate::route::{Route, Handler, Outcome};
 use crate::response::Redirect;
 use crate::fs::NamedFile;
+use crate::fs::listing::Listing;
 
 /// Custom handler for serving static files.
 ///
@@ -190,11 +191,11 @@ impl Handler for FileServer {
         // Get the segments as a `PathBuf`, allowing dotfiles requested.
         let options = self.options;
         let allow_dotfiles = options.contains(Options::DotFiles);
-        let path = req.segments::<Segments<'_, Path>>(0..).ok()
-            .and_then(|segments| segments.to_path_buf(allow_dotfiles).ok())
-            .map(|path| self.root.join(path));
+        let relative = req.segments::<Segments<'_, Path>>(0..).ok()
+            .and_then(|segments| segments.to_path_buf(allow_dotfiles).ok());
 
-        match path {
+        let is_root = matches!(relative, Some(ref path) if path.as_os_str().is_empty());
+        match relative.map(|path| self.root.join(path)) {
             Some(p) if p.is_dir() => {
                 // Normalize '/a/b/foo' to '/a/b/foo/'.
                 if options.contains(Options::NormalizeDirs) && !req.uri().path().ends_with('/') {
@@ -205,19 +206,35 @@ impl Handler for FileServer {
                     return Outcome::from_or_forward(req, data, Redirect::permanent(normal));
                 }
 
-                if !options.contains(Options::Index) {
+                if options.contains(Options::Index) {
+                    if let Some(index) = open(&p.join("index.html"), options, req).await {
+                        return Outcome::from_or_forward(req, data, index);
+                    }
+                }
+
+                if !options.contains(Options::Listing) {
                     return Outcome::forward(data);
                 }
 
-                let index = NamedFile::open(p.join("index.html")).await.ok();
-                Outcome::from_or_forward(req, data, index)
+                let listing = Listing::read(&p, req, is_root, allow_dotfiles).await.ok();
+                Outcome::from_or_forward(req, data, listing)
             },
-            Some(p) => Outcome::from_or_forward(req, data, NamedFile::open(p).await.ok()),
+            Some(p) => Outcome::from_or_forward(req, data, open(&p, options, req).await),
             None => Outcome::forward(data),
         }
     }
 }
 
+/// Opens the file at `path` to respond to `req` with `options`.
+async fn open(path: &Path, options: Options, req: &Request<'_>) -> Option<NamedFile> {
+    let file = match options.contains(Options::Precompressed) {
+        true => NamedFile::open_precompressed(path, req).await,
+        false => NamedFile::open(path).await,
+    };
+
+    file.ok().map(|file| file.with_options(options))
+}
+
 /// A bitset representing configurable options for [`FileServer`].
 ///
 /// The valid options are:
@@ -227,6 +244,13 @@ impl Handler for FileServer {
 ///   * [`Options::Index`] - Render `index.html` pages for directory requests.
 ///   * [`Options::NormalizeDirs`] - Redirect directories without a trailing
 ///     slash to ones with a trailing slash.
+///   * [`Options::Ranges`] - Respond to `Range` requests with partial content.
+///   * [`Options::Conditional`] - Respond to conditional requests with `304
+///     Not Modified` when the client's copy is current.
+///   * [`Options::Precompressed`] - Serve precompressed `.br` and `.gz`
+///     siblings of files to clients that accept them.
+///   * [`Options::Listing`] - Render a listing of the directory's contents
+///     for directory requests without an index page.
 ///
 /// `Options` structures can be `or`d together to select two or more options.
 /// For instance, to request that both dot files and index pages be returned,
@@ -293,6 +317,77 @@ impl Options {
     /// that would be served is a directory.
     pub const NormalizeDirs: Options = Options(0b0100);
 
+    /// Respond to range requests with only the requested bytes.
+    ///
+    /// When enabled, responses include an `Accept-Ranges: bytes` header, and
+    /// `GET` requests with a valid `Range` header are answered with `206
+    /// Partial Content` and only the requested bytes. A request for a single
+    /// range is answered with that range and a `Content-Range` header, while a
+    /// request for multiple ranges is answered with a `multipart/byteranges`
+    /// body. If none of the requested ranges overlap the file, the response is
+    /// `416 Range Not Satisfiable`. Malformed `Range` headers are ignored, as
+    /// are `Range` headers accompanied by an `If-Range` header that doesn't
+    /// match the file's current validators; the full file is sent instead.
+    ///
+    /// **Disabled by default.**
+    pub const Ranges: Options = Options(0b1000);
+
+    /// Respond to conditional requests based on file metadata.
+    ///
+    /// When enabled, responses include `ETag` and `Last-Modified` headers
+    /// derived from the file's size and modification time. `GET` requests
+    /// whose `If-None-Match` header matches the `ETag` or, in the absence of
+    /// `If-None-Match`, whose `If-Modified-Since` date is no earlier than the
+    /// file's modification time, are answered with `304 Not Modified` and no
+    /// body.
+    ///
+    /// **Disabled by default.**
+    pub const Conditional: Options = Options(0b10000);
+
+    /// Serve precompressed variants of files when the client accepts them.
+    ///
+    /// When enabled, a request for `path` is served from the sibling
+    /// `path.br` or `path.gz`, if it exists, and the request's
+    /// `Accept-Encoding` header lists `br` or `gzip`, respectively. When both
+    /// are acceptable, the one with the higher q-value is preferred, with
+    /// ties going to `br`. A precompressed variant is served with a matching
+    /// `Content-Encoding` header and the `Content-Type` of the original
+    /// `path`. If no variant is acceptable or exists, the original file is
+    /// served. Either way, the response includes `Vary: Accept-Encoding`.
+    ///
+    /// Variants are served as-is: they must be kept in sync with the original
+    /// files by whatever produces them.
+    ///
+    /// **Disabled by default.**
+    pub const Precompressed: Options = Options(0b100000);
+
+    /// Respond to requests for a directory with a listing of its contents.
+    ///
+    /// When enabled, a request for a directory that isn't answered with an
+    /// `index.html` page, either because [`Options::Index`] is disabled or
+    /// because the directory has no such file, is answered with a listing of
+    /// the directory's entries along with their sizes and modification
+    /// times. The listing is rendered as an HTML page unless the client's
+    /// preferred media type, per its `Accept` header, is JSON, in which case
+    /// it is rendered as a JSON object of the form:
+    ///
+    /// ```json
+    /// {
+    ///   "path": "/files/reports/",
+    ///   "entries": [
+    ///     { "name": "2021", "type": "directory", "size": 0, "modified": 1625097600 },
+    ///     { "name": "summary.pdf", "type": "file", "size": 48213, "modified": 1625184000 }
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Modification times are in seconds since the Unix epoch, or `null` if
+    /// unavailable. Entries that couldn't be served by the `FileServer`, such
+    /// as dotfiles when [`Options::DotFiles`] is disabled, are omitted.
+    ///
+    /// **Disabled by default.**
+    pub const Listing: Options = Options(0b1000000);
+
     /// Returns `true` if `self` is a superset of `other`. In other words,
     /// returns `true` if all of the options in `other` are also in `self`.
     ///
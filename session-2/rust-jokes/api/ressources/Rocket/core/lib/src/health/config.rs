@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use crate::http::uri::Origin;
+
+/// The health configuration: where health endpoints are served and how long
+/// checks may take.
+///
+/// A check still running after `timeout` seconds fails, so the readiness
+/// endpoint answers within `timeout` seconds even if a dependency hangs. The
+/// [`Health`](crate::health::Health) fairing fails to ignite if `path` isn't a
+/// static route path.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::health::Config;
+///
+/// let figment = rocket::Config::figment()
+///     .merge(("health.path", "/internal/health"))
+///     .merge(("health.timeout", 2));
+///
+/// let config: Config = figment.extract_inner("health").unwrap();
+/// assert_eq!(config.path, "/internal/health");
+/// assert_eq!(config.timeout, 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// The path the `live` and `ready` endpoints are served under.
+    /// **(default: `/health`)**
+    pub path: String,
+    /// The number of seconds after which a running check fails.
+    /// **(default: `5`)**
+    pub timeout: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { path: "/health".into(), timeout: 5 }
+    }
+}
+
+impl Config {
+    /// Returns an error message if the path is invalid.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        match Origin::parse_route(&self.path) {
+            Ok(uri) if uri.is_normalized() && !uri.path().contains('<') => Ok(()),
+            _ => Err(format!("invalid path: '{}'", self.path)),
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::{self, BoxFuture};
+use parking_lot::Mutex;
+
+use crate::{Rocket, Build, Orbit, Request, Data, Route};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::route::{self, Handler, Outcome};
+use crate::http::{ContentType, Method, Status};
+use crate::health::Config;
+use crate::log::write_json_str;
+
+/// The type of a health check: an async function of the running application
+/// returning an error message if the check fails.
+type CheckFn = Box<dyn for<'a> Fn(&'a Rocket<Orbit>) -> BoxFuture<'a, Result<(), String>>
+    + Send + Sync + 'static>;
+
+/// A registered, named health check.
+struct Check {
+    name: String,
+    check: CheckFn,
+}
+
+/// The health checks registered with [`Health::register()`] or
+/// [`Health::check()`], managed by the first registration.
+struct Checks(Mutex<Vec<Arc<Check>>>);
+
+/// A [`Fairing`] that serves liveness and readiness endpoints backed by
+/// registered health checks.
+///
+/// See the [module docs](crate::health) for the endpoints, their responses, and
+/// configuration details.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::health::Health;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let health = Health::new().check("disk", |_| Box::pin(async move {
+///         match std::env::temp_dir().exists() {
+///             true => Ok(()),
+///             false => Err("temporary directory is missing".into()),
+///         }
+///     }));
+///
+///     rocket::build().attach(health)
+/// }
+/// ```
+pub struct Health {
+    checks: Mutex<Vec<Check>>,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Health::new()
+    }
+}
+
+impl Health {
+    /// Returns a `Health` fairing without any checks of its own.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::health::Health;
+    ///
+    /// let health = Health::new();
+    /// ```
+    pub fn new() -> Self {
+        Health { checks: Mutex::new(vec![]) }
+    }
+
+    /// Adds a check named `name`. The application is ready only while `f`
+    /// returns `Ok`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::health::Health;
+    ///
+    /// let health = Health::new().check("cache", |rocket| Box::pin(async move {
+    ///     match rocket.state::<String>() {
+    ///         Some(_) => Ok(()),
+    ///         None => Err("cache is unavailable".into()),
+    ///     }
+    /// }));
+    /// ```
+    pub fn check<N, F>(self, name: N, f: F) -> Self
+        where N: Into<String>,
+              F: for<'a> Fn(&'a Rocket<Orbit>) -> BoxFuture<'a, Result<(), String>>,
+              F: Send + Sync + 'static
+    {
+        self.checks.lock().push(Check { name: name.into(), check: Box::new(f) });
+        self
+    }
+
+    /// Registers a check named `name` with `rocket`, to be run by the `Health`
+    /// fairing if it's attached. Intended for fairings and libraries that
+    /// manage state whose health the application depends on, such as a
+    /// database pool, and which don't know whether the fairing is attached.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    /// use rocket::health::Health;
+    ///
+    /// struct Pool;
+    ///
+    /// impl Pool {
+    ///     async fn ping(&self) -> bool {
+    ///         /* ... */
+    ///         # true
+    ///     }
+    /// }
+    ///
+    /// let fairing = AdHoc::on_ignite("Pool", |rocket| async {
+    ///     let rocket = rocket.manage(Pool);
+    ///     Health::register(rocket, "pool", |rocket| Box::pin(async move {
+    ///         match rocket.state::<Pool>() {
+    ///             Some(pool) if pool.ping().await => Ok(()),
+    ///             _ => Err("pool is unreachable".into()),
+    ///         }
+    ///     }))
+    /// });
+    /// ```
+    pub fn register<N, F>(rocket: Rocket<Build>, name: N, f: F) -> Rocket<Build>
+        where N: Into<String>,
+              F: for<'a> Fn(&'a Rocket<Orbit>) -> BoxFuture<'a, Result<(), String>>,
+              F: Send + Sync + 'static
+    {
+        let check = Check { name: name.into(), check: Box::new(f) };
+        match rocket.state::<Checks>() {
+            Some(checks) => {
+                checks.0.lock().push(check.into());
+                rocket
+            }
+            None => rocket.manage(Checks(Mutex::new(vec![check.into()]))),
+        }
+    }
+}
+
+/// The route handler of the `live` and `ready` endpoints.
+#[derive(Clone)]
+struct Endpoint {
+    ready: bool,
+    timeout: Duration,
+}
+
+#[crate::async_trait]
+impl Handler for Endpoint {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> route::Outcome<'r> {
+        let rocket = req.rocket();
+        let shutdown = rocket.shutdown.0.tripped();
+        if !self.ready {
+            let body = r#"{"status":"up"}"#.to_string();
+            return Outcome::from(req, (Status::Ok, (ContentType::JSON, body)));
+        }
+
+        // Run every check concurrently, unless we're shutting down.
+        let checks = match (shutdown, rocket.state::<Checks>()) {
+            (false, Some(checks)) => checks.0.lock().clone(),
+            _ => vec![],
+        };
+
+        let results = future::join_all(checks.iter().map(|check| async move {
+            match tokio::time::timeout(self.timeout, (check.check)(rocket)).await {
+                Ok(result) => result,
+                Err(_) => Err(format!("timed out after {}s", self.timeout.as_secs())),
+            }
+        })).await;
+
+        let up = !shutdown && results.iter().all(|result| result.is_ok());
+        let mut body = String::with_capacity(64 * (checks.len() + 1));
+        body.push_str(if up { r#"{"status":"up""# } else { r#"{"status":"down""# });
+        if shutdown {
+            body.push_str(r#","shutdown":true"#);
+        }
+
+        body.push_str(r#","checks":{"#);
+        for (i, (check, result)) in checks.iter().zip(results).enumerate() {
+            if i > 0 {
+                body.push(',');
+            }
+
+            write_json_str(&mut body, &check.name);
+            match result {
+                Ok(()) => body.push_str(r#":{"status":"up"}"#),
+                Err(e) => {
+                    body.push_str(r#":{"status":"down","error":"#);
+                    write_json_str(&mut body, &e);
+                    body.push('}');
+                }
+            }
+        }
+
+        body.push_str("}}");
+        let status = if up { Status::Ok } else { Status::ServiceUnavailable };
+        Outcome::from(req, (status, (ContentType::JSON, body)))
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Health {
+    fn info(&self) -> Info {
+        Info { name: "Health", kind: Kind::Ignite | Kind::Singleton }
+    }
+
+    async fn on_ignite(&self, mut rocket: Rocket<Build>) -> fairing::Result {
+        let config = match crate::config::extract_or_default::<Config>(&rocket, "health") {
+            Some(config) => config,
+            None => return Err(rocket),
+        };
+
+        if let Err(e) = config.validate() {
+            error!("invalid health configuration: {}", e);
+            return Err(rocket);
+        }
+
+        for check in self.checks.lock().drain(..) {
+            rocket = Health::register(rocket, check.name, check.check);
+        }
+
+        let base = config.path.trim_end_matches('/');
+        let timeout = Duration::from_secs(config.timeout);
+        let routes = vec![
+            (format!("{}/live", base), Endpoint { ready: false, timeout }),
+            (format!("{}/ready", base), Endpoint { ready: true, timeout }),
+        ];
+
+        let routes: Vec<_> = routes.into_iter()
+            .map(|(path, endpoint)| {
+                let mut route = Route::new(Method::Get, &path, endpoint);
+                route.name = Some("Health".into());
+                route
+            })
+            .collect();
+
+        Ok(rocket.mount("/", routes))
+    }
+}
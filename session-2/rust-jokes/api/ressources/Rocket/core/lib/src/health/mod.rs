@@ -0,0 +1,97 @@
+//! Liveness and readiness endpoints backed by registered health checks.
+//!
+//! [Sentinels](crate::Sentinel) validate an application before it launches;
+//! the [`Health`] fairing reports on it while it runs. It serves two endpoints,
+//! meant to be polled by orchestrators and load balancers:
+//!
+//!   * `GET /health/live`: always `200 OK` while the server is responding.
+//!   * `GET /health/ready`: `200 OK` if every registered check passes, `503
+//!     Service Unavailable` otherwise, and from the moment [`Shutdown`] is
+//!     triggered so that load balancers stop routing requests to a server
+//!     that's about to stop.
+//!
+//! [`Shutdown`]: crate::Shutdown
+//!
+//! # Enabling
+//!
+//! This module is only available when the `health` feature is enabled:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket = { version = "0.5.0-rc.1", features = ["health"] }
+//! ```
+//!
+//! # Usage
+//!
+//! Attach the fairing, along with any checks of the application's own:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use std::sync::atomic::{AtomicBool, Ordering};
+//!
+//! use rocket::health::Health;
+//!
+//! struct Queue {
+//!     connected: AtomicBool,
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     let health = Health::new().check("queue", |rocket| Box::pin(async move {
+//!         match rocket.state::<Queue>() {
+//!             Some(queue) if queue.connected.load(Ordering::Acquire) => Ok(()),
+//!             _ => Err("the queue is disconnected".into()),
+//!         }
+//!     }));
+//!
+//!     rocket::build()
+//!         .manage(Queue { connected: AtomicBool::new(true) })
+//!         .attach(health)
+//! }
+//! ```
+//!
+//! Fairings and libraries register checks for the state they manage with
+//! [`Health::register()`]: the database pools of `rocket_sync_db_pools` and the
+//! template contexts of `rocket_dyn_templates` do so when their own `health`
+//! features are enabled. Checks registered without the fairing attached are
+//! never run.
+//!
+//! # Responses
+//!
+//! Both endpoints respond with JSON. Readiness details the result of each
+//! check, run concurrently for every request:
+//!
+//! ```json
+//! {
+//!   "status": "down",
+//!   "checks": {
+//!     "queue": { "status": "up" },
+//!     "database": { "status": "down", "error": "timed out after 5s" }
+//!   }
+//! }
+//! ```
+//!
+//! Once shutdown is triggered, checks are no longer run and the response is
+//! `{"status":"down","shutdown":true,"checks":{}}`.
+//!
+//! # Configuration
+//!
+//! The path the endpoints are served under and the number of seconds after
+//! which a running check fails are read from the `health` configuration
+//! parameter into a [`Config`]. The defaults are:
+//!
+//! ```toml
+//! [default.health]
+//! path = "/health"
+//! timeout = 5
+//! ```
+//!
+//! The endpoints aren't protected in any way, and check errors may reveal
+//! details about the application: consider serving them on a path that isn't
+//! reachable from the public internet.
+
+mod config;
+mod fairing;
+
+pub use self::config::Config;
+pub use self::fairing::Health;
@@ -59,13 +59,25 @@
 //! To avoid compiling unused dependencies, Rocket gates certain features, all
 //! of which are disabled by default:
 //!
-//! | Feature   | Description                                             |
-//! |-----------|---------------------------------------------------------|
-//! | `secrets` | Support for authenticated, encrypted [private cookies]. |
-//! | `tls`     | Support for [TLS] encrypted connections.                |
-//! | `json`    | Support for [JSON (de)serialization].                   |
-//! | `msgpack` | Support for [MessagePack (de)serialization].            |
-//! | `uuid`    | Support for [UUID value parsing and (de)serialization]. |
+//! | Feature       | Description                                             |
+//! |---------------|---------------------------------------------------------|
+//! | `secrets`     | Support for authenticated, encrypted [private cookies]. |
+//! | `tls`         | Support for [TLS] encrypted connections.                |
+//! | `mtls`        | Support for verified clients via [mutual TLS].          |
+//! | `json`        | Support for [JSON (de)serialization].                   |
+//! | `msgpack`     | Support for [MessagePack (de)serialization].            |
+//! | `uuid`        | Support for [UUID value parsing and (de)serialization]. |
+//! | `compression` | Support for [response compression].                     |
+//! | `ratelimit`   | Support for [rate limiting].                            |
+//! | `cors`        | Support for [Cross-Origin Resource Sharing].            |
+//! | `sessions`    | Support for [server-side sessions].                     |
+//! | `csrf`        | Support for [cross-site request forgery protection].    |
+//! | `openapi`     | Support for [OpenAPI document generation].              |
+//! | `websocket`   | Support for [WebSocket connections].                    |
+//! | `upload`      | Support for [resumable uploads].                        |
+//! | `metrics`     | Support for [Prometheus request metrics].               |
+//! | `access-log`  | Support for [access logging].                           |
+//! | `health`      | Support for [liveness and readiness endpoints].         |
 //!
 //! Features can be selectively enabled in `Cargo.toml`:
 //!
@@ -79,6 +91,18 @@
 //! [UUID value parsing and (de)serialization]: crate::serde::uuid
 //! [private cookies]: https://rocket.rs/v0.5-rc/guide/requests/#private-cookies
 //! [TLS]: https://rocket.rs/v0.5-rc/guide/configuration/#tls
+//! [mutual TLS]: crate::mtls
+//! [response compression]: crate::compression
+//! [rate limiting]: crate::ratelimit
+//! [Cross-Origin Resource Sharing]: crate::cors
+//! [server-side sessions]: crate::session
+//! [cross-site request forgery protection]: crate::csrf
+//! [OpenAPI document generation]: crate::openapi
+//! [WebSocket connections]: crate::websocket
+//! [resumable uploads]: crate::upload
+//! [Prometheus request metrics]: crate::metrics
+//! [access logging]: crate::access_log
+//! [liveness and readiness endpoints]: crate::health
 //!
 //! ## Configuration
 //!
@@ -123,6 +147,54 @@ pub mod serde;
 pub mod shield;
 pub mod fs;
 
+#[cfg(feature = "mtls")]
+#[cfg_attr(nightly, doc(cfg(feature = "mtls")))]
+pub mod mtls;
+
+#[cfg(feature = "compression")]
+#[cfg_attr(nightly, doc(cfg(feature = "compression")))]
+pub mod compression;
+
+#[cfg(feature = "ratelimit")]
+#[cfg_attr(nightly, doc(cfg(feature = "ratelimit")))]
+pub mod ratelimit;
+
+#[cfg(feature = "cors")]
+#[cfg_attr(nightly, doc(cfg(feature = "cors")))]
+pub mod cors;
+
+#[cfg(feature = "sessions")]
+#[cfg_attr(nightly, doc(cfg(feature = "sessions")))]
+pub mod session;
+
+#[cfg(feature = "csrf")]
+#[cfg_attr(nightly, doc(cfg(feature = "csrf")))]
+pub mod csrf;
+
+#[cfg(feature = "openapi")]
+#[cfg_attr(nightly, doc(cfg(feature = "openapi")))]
+pub mod openapi;
+
+#[cfg(feature = "websocket")]
+#[cfg_attr(nightly, doc(cfg(feature = "websocket")))]
+pub mod websocket;
+
+#[cfg(feature = "upload")]
+#[cfg_attr(nightly, doc(cfg(feature = "upload")))]
+pub mod upload;
+
+#[cfg(feature = "metrics")]
+#[cfg_attr(nightly, doc(cfg(feature = "metrics")))]
+pub mod metrics;
+
+#[cfg(feature = "access-log")]
+#[cfg_attr(nightly, doc(cfg(feature = "access-log")))]
+pub mod access_log;
+
+#[cfg(feature = "health")]
+#[cfg_attr(nightly, doc(cfg(feature = "health")))]
+pub mod health;
+
 // Reexport of HTTP everything.
 pub mod http {
     //! Types that map to concepts in HTTP.
@@ -144,6 +216,7 @@ mod server;
 mod ext;
 mod state;
 mod cookies;
+mod accept_encoding;
 mod rocket;
 mod router;
 mod phase;
@@ -4,6 +4,7 @@ use std::convert::TryInto;
 use crate::{Request, Data};
 use crate::http::{Status, Method};
 use crate::http::uri::Origin;
+use crate::log::Span;
 
 use super::{Client, LocalResponse};
 
@@ -76,7 +77,7 @@ impl<'c> LocalRequest<'c> {
     }
 
     // Performs the actual dispatch.
-    async fn _dispatch(mut self) -> LocalResponse<'c> {
+    async fn _dispatch(self) -> LocalResponse<'c> {
         // First, revalidate the URI, returning an error response (generated
         // from an error catcher) immediately if it's invalid. If it's valid,
         // then `request` already contains a correct URI.
@@ -92,11 +93,12 @@ impl<'c> LocalRequest<'c> {
             }
         }
 
-        // Actually dispatch the request.
-        let mut data = Data::local(self.data);
-        let token = rocket.preprocess_request(&mut self.request, &mut data).await;
-        let response = LocalResponse::new(self.request, move |req| {
-            rocket.dispatch(token, req, data)
+        // Actually dispatch the request, in a span like the server would.
+        let (mut request, mut data) = (self.request, Data::local(self.data));
+        let span = Span::new(request.method(), request.uri());
+        let response = span.scope(async move {
+            let token = rocket.preprocess_request(&mut request, &mut data).await;
+            LocalResponse::new(request, move |req| rocket.dispatch(token, req, data)).await
         }).await;
 
         // If the client is tracking cookies, updates the internal cookie jar
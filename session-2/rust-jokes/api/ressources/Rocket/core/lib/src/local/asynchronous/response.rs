@@ -182,6 +182,66 @@ impl LocalResponse<'_> {
         reader.await.ok()
     }
 
+    /// Returns the client's end of the WebSocket connection this response
+    /// upgraded to, or `None` if the response isn't a [`Channel`] upgrade.
+    ///
+    /// The connection is made over an in-memory pipe, and the server's handler
+    /// runs in a task of its own.
+    ///
+    /// [`Channel`]: crate::websocket::Channel
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::futures::{SinkExt, StreamExt};
+    /// use rocket::http::{Header, Status};
+    /// use rocket::local::asynchronous::Client;
+    /// use rocket::websocket::{WebSocket, Channel, Message};
+    ///
+    /// #[get("/echo")]
+    /// fn echo(ws: WebSocket) -> Channel {
+    ///     ws.channel(|mut stream| async move {
+    ///         while let Some(message) = stream.next().await {
+    ///             stream.send(message?).await?;
+    ///         }
+    ///
+    ///         Ok(())
+    ///     })
+    /// }
+    ///
+    /// # rocket::async_test(async {
+    /// let client = Client::debug_with(routes![echo]).await.unwrap();
+    /// let response = client.get("/echo")
+    ///     .header(Header::new("Connection", "Upgrade"))
+    ///     .header(Header::new("Upgrade", "websocket"))
+    ///     .header(Header::new("Sec-WebSocket-Version", "13"))
+    ///     .header(Header::new("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ=="))
+    ///     .dispatch()
+    ///     .await;
+    ///
+    /// assert_eq!(response.status(), Status::SwitchingProtocols);
+    /// let mut stream = response.into_websocket().await.unwrap();
+    /// stream.send(Message::Text("hi".into())).await.unwrap();
+    /// assert_eq!(stream.next().await.unwrap().unwrap(), Message::Text("hi".into()));
+    /// # });
+    /// ```
+    #[cfg(feature = "websocket")]
+    #[cfg_attr(nightly, doc(cfg(feature = "websocket")))]
+    pub async fn into_websocket(mut self) -> Option<crate::websocket::DuplexStream> {
+        use tokio_tungstenite::tungstenite::protocol::Role;
+
+        let upgrade = self.response.take_upgrade()?;
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(async move {
+            if let Err(e) = upgrade(Box::new(server)).await {
+                error_!("WebSocket connection failed: {}.", e);
+            }
+        });
+
+        Some(crate::websocket::DuplexStream::new(Box::new(client), Role::Client, None).await)
+    }
+
     // Generates the public API methods, which call the private methods above.
     pub_response_impl!("# use rocket::local::asynchronous::Client;\n\
         use rocket::local::asynchronous::LocalResponse;" async await);
@@ -1,12 +1,17 @@
 //! Rocket's logging infrastructure.
 
-use std::fmt;
+use std::fmt::{self, Write};
 use std::str::FromStr;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
+use std::time::Instant;
 
+use futures::Future;
+use parking_lot::Mutex;
 use serde::{de, Serialize, Serializer, Deserialize, Deserializer};
 use yansi::Paint;
 
+use crate::http::Status;
+
 /// Reexport the `log` crate as `private`.
 pub use log as private;
 
@@ -38,6 +43,9 @@ define_log_macro!(launch_info_: warn, "rocket::launch_", $);
 #[derive(Debug)]
 struct RocketLogger;
 
+// Whether records are logged as JSON objects rather than pretty-printed.
+static LOG_JSON: AtomicBool = AtomicBool::new(false);
+
 /// Defines the maximum level of log messages to show.
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum LogLevel {
@@ -51,6 +59,152 @@ pub enum LogLevel {
     Off,
 }
 
+/// Defines how log messages are formatted.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum LogFormat {
+    /// Human-readable, colored output: `"pretty"`.
+    Pretty,
+    /// One JSON object per record, per line: `"json"`.
+    ///
+    /// Each object has a `timestamp`, `level`, `target`, and `message`.
+    /// Records logged while a request is being handled also have a `request`
+    /// object with the request's `id`, `method`, `uri`, matched `route`,
+    /// response `status`, and `latency_ms` so far. `id`, `route`, and `status`
+    /// are `null` until known. The `id` is the request's
+    /// [`RequestId`](crate::request::RequestId).
+    Json,
+}
+
+/// The context of the request being handled by the current task.
+pub(crate) struct Span {
+    id: Mutex<Option<String>>,
+    /// The details logged with each JSON record, captured only when logging
+    /// JSON so that pretty logging doesn't pay for them.
+    details: Option<Details>,
+}
+
+/// The details of a request logged with each JSON record.
+struct Details {
+    method: String,
+    uri: String,
+    start: Instant,
+    route: Mutex<Option<String>>,
+    status: AtomicU16,
+}
+
+tokio::task_local! {
+    static SPAN: Span;
+}
+
+impl Span {
+    /// Creates a span for a request to `uri` with `method`. The request's
+    /// details are captured only if records are being logged as JSON.
+    pub(crate) fn new(method: impl fmt::Display, uri: impl fmt::Display) -> Span {
+        match LOG_JSON.load(Ordering::Acquire) {
+            true => Span::detailed(method, uri),
+            false => Span { id: Mutex::new(None), details: None },
+        }
+    }
+
+    /// Creates a span for a request to `uri` with `method` that captures the
+    /// request's details.
+    fn detailed(method: impl fmt::Display, uri: impl fmt::Display) -> Span {
+        let details = Details {
+            method: method.to_string(),
+            uri: uri.to_string(),
+            start: Instant::now(),
+            route: Mutex::new(None),
+            status: AtomicU16::new(0),
+        };
+
+        Span { id: Mutex::new(None), details: Some(details) }
+    }
+
+    /// Runs `fut` with `self` as the current span.
+    pub(crate) fn scope<F: Future>(self, fut: F) -> impl Future<Output = F::Output> {
+        SPAN.scope(self, fut)
+    }
+
+    /// Calls `f` with the current span, if there is one.
+    pub(crate) fn with<T, F: FnOnce(&Span) -> T>(f: F) -> Option<T> {
+        SPAN.try_with(|span| f(span)).ok()
+    }
+
+    pub(crate) fn id(&self) -> Option<String> {
+        self.id.lock().clone()
+    }
+
+    pub(crate) fn set_id(&self, id: &str) {
+        *self.id.lock() = Some(id.to_string());
+    }
+
+    pub(crate) fn set_route(&self, route: &crate::Route) {
+        if let Some(details) = &self.details {
+            let route = route.name.as_deref()
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| route.uri.to_string());
+
+            *details.route.lock() = Some(route);
+        }
+    }
+
+    pub(crate) fn set_status(&self, status: Status) {
+        if let Some(details) = &self.details {
+            details.status.store(status.code, Ordering::Release);
+        }
+    }
+
+    fn write_json(&self, out: &mut String) {
+        out.push_str("{\"id\":");
+        match &*self.id.lock() {
+            Some(id) => write_json_str(out, id),
+            None => out.push_str("null"),
+        }
+
+        if let Some(details) = &self.details {
+            out.push_str(",\"method\":");
+            write_json_str(out, &details.method);
+            out.push_str(",\"uri\":");
+            write_json_str(out, &details.uri);
+            out.push_str(",\"route\":");
+            match &*details.route.lock() {
+                Some(route) => write_json_str(out, route),
+                None => out.push_str("null"),
+            }
+
+            match details.status.load(Ordering::Acquire) {
+                0 => out.push_str(",\"status\":null"),
+                code => { let _ = write!(out, ",\"status\":{}", code); }
+            }
+
+            let latency = details.start.elapsed().as_secs_f64() * 1000.0;
+            let _ = write!(out, ",\"latency_ms\":{:.3}", latency);
+        }
+
+        out.push('}');
+    }
+}
+
+// Formats `record`, logged at `level`, as a single-line JSON object.
+fn json_record(record: &log::Record<'_>, level: log::Level) -> String {
+    let mut out = String::with_capacity(256);
+    out.push_str("{\"timestamp\":");
+    write_json_str(&mut out, &time::OffsetDateTime::now_utc().format(time::Format::Rfc3339));
+    out.push_str(",\"level\":");
+    write_json_str(&mut out, &level.as_str().to_ascii_lowercase());
+    out.push_str(",\"target\":");
+    write_json_str(&mut out, record.module_path().unwrap_or_else(|| record.target()));
+    out.push_str(",\"message\":");
+    write_json_str(&mut out, &record.args().to_string());
+    Span::with(|span| {
+        out.push_str(",\"request\":");
+        span.write_json(&mut out);
+    });
+
+    out.push('}');
+    out
+}
+
 pub trait PaintExt {
     fn emoji(item: &str) -> Paint<&str>;
 }
@@ -83,17 +237,22 @@ impl log::Log for RocketLogger {
             return;
         }
 
+        // Downgrade a physical launch `warn` to logical `info`.
+        let level = is_launch_record(record.metadata())
+            .then(|| log::Level::Info)
+            .unwrap_or_else(|| record.level());
+
+        if LOG_JSON.load(Ordering::Acquire) {
+            println!("{}", json_record(record, level));
+            return;
+        }
+
         // In Rocket, we abuse targets with suffix "_" to indicate indentation.
         let indented = record.target().ends_with('_');
         if indented {
             print!("   {} ", Paint::default(">>").bold());
         }
 
-        // Downgrade a physical launch `warn` to logical `info`.
-        let level = is_launch_record(record.metadata())
-            .then(|| log::Level::Info)
-            .unwrap_or_else(|| record.level());
-
         match level {
             log::Level::Error if !indented => {
                 println!("{} {}",
@@ -146,9 +305,12 @@ pub(crate) fn init(config: &crate::Config) -> bool {
         return false;
     }
 
+    let json = config.log_format == LogFormat::Json;
+    LOG_JSON.store(json, Ordering::Release);
     if !atty::is(atty::Stream::Stdout)
         || (cfg!(windows) && !Paint::enable_windows_ascii())
         || !config.cli_colors
+        || json
     {
         Paint::disable();
     }
@@ -157,6 +319,24 @@ pub(crate) fn init(config: &crate::Config) -> bool {
     true
 }
 
+// Writes `string` to `out` as a quoted, escaped JSON string.
+pub(crate) fn write_json_str(out: &mut String, string: &str) {
+    out.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => { let _ = write!(out, "\\u{:04x}", c as u32); }
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+}
+
 impl LogLevel {
     fn as_str(&self) -> &str {
         match self {
@@ -215,6 +395,51 @@ impl<'de> Deserialize<'de> for LogLevel {
     }
 }
 
+impl LogFormat {
+    fn as_str(&self) -> &str {
+        match self {
+            LogFormat::Pretty => "pretty",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let format = match &*s.to_ascii_lowercase() {
+            "pretty" => LogFormat::Pretty,
+            "json" => LogFormat::Json,
+            _ => return Err("a log format (pretty, json)")
+        };
+
+        Ok(format)
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Serialize for LogFormat {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for LogFormat {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(de)?;
+        LogFormat::from_str(&string).map_err(|_| de::Error::invalid_value(
+            de::Unexpected::Str(&string),
+            &figment::error::OneOf( &["pretty", "json"])
+        ))
+    }
+}
+
 impl PaintExt for Paint<&str> {
     /// Paint::masked(), but hidden on Windows due to broken output. See #1122.
     fn emoji(_item: &str) -> Paint<&str> {
@@ -222,3 +447,55 @@ impl PaintExt for Paint<&str> {
         #[cfg(not(windows))] { Paint::masked(_item) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(message: &str, f: impl FnOnce(&log::Record<'_>) -> String) -> String {
+        f(&log::Record::builder()
+            .args(format_args!("{}", message))
+            .level(log::Level::Warn)
+            .target("_")
+            .module_path(Some("rocket::server"))
+            .build())
+    }
+
+    #[test]
+    fn test_log_format_from_str() {
+        assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("Pretty".parse::<LogFormat>().unwrap(), LogFormat::Pretty);
+        assert!("yaml".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn test_json_str_escaping() {
+        let mut out = String::new();
+        write_json_str(&mut out, "a \"b\"\\c\n\u{1b}é");
+        assert_eq!(out, r#""a \"b\"\\c\n\u001bé""#);
+
+        let mut out = String::new();
+        write_json_str(&mut out, "line\r\tbreak\u{1}");
+        assert_eq!(out, r#""line\r\tbreak\u0001""#);
+    }
+
+    #[test]
+    fn test_json_record() {
+        let json = record("hi", |r| json_record(r, log::Level::Info));
+        assert!(json.starts_with(r#"{"timestamp":""#));
+        assert!(json.ends_with(r#","level":"info","target":"rocket::server","message":"hi"}"#));
+
+        let span = Span::detailed("GET", "/a?b");
+        let json = crate::async_test(span.scope(async {
+            Span::with(|span| span.set_id("abc"));
+            Span::with(|span| span.set_status(Status::NotFound));
+            record("\"x\"", |r| json_record(r, r.level()))
+        }));
+
+        let (_, request) = json.split_once(r#","message":"\"x\"","request":"#).unwrap();
+        let prefix = r#"{"id":"abc","method":"GET","uri":"/a?b","route":null,"#;
+        assert!(request.starts_with(prefix));
+        assert!(request.contains(r#""status":404,"latency_ms":"#));
+        assert!(Span::with(|_| ()).is_none());
+    }
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+use crate::http::uri::Origin;
+
+/// The metrics configuration: where metrics are served and the histogram
+/// buckets observations are counted in.
+///
+/// Bucket bounds become the `le` labels of the exported histograms, so changing
+/// them changes the series dashboards query. The
+/// [`Metrics`](crate::metrics::Metrics) fairing fails to ignite if `path` isn't
+/// a static route path or bounds aren't finite and increasing.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::metrics::Config;
+///
+/// let figment = rocket::Config::figment()
+///     .merge(("metrics.path", "/internal/metrics"))
+///     .merge(("metrics.latency_buckets", [0.1, 1.0]));
+///
+/// let config: Config = figment.extract_inner("metrics").unwrap();
+/// assert_eq!(config.path, "/internal/metrics");
+/// assert_eq!(config.latency_buckets, vec![0.1, 1.0]);
+/// assert_eq!(config.size_buckets, Config::default().size_buckets);
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// The path metrics are served at. **(default: `/metrics`)**
+    pub path: String,
+    /// The upper bounds, in seconds, of the request latency histogram's
+    /// buckets. **(default: `[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1, 2.5,
+    /// 5, 10]`)**
+    pub latency_buckets: Vec<f64>,
+    /// The upper bounds, in bytes, of the response size histogram's buckets.
+    /// **(default: `[100, 1000, 10000, 100000, 1000000, 10000000]`)**
+    pub size_buckets: Vec<f64>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            path: "/metrics".into(),
+            latency_buckets: vec![0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0],
+            size_buckets: vec![1e2, 1e3, 1e4, 1e5, 1e6, 1e7],
+        }
+    }
+}
+
+impl Config {
+    /// Returns an error message if the path or any bucket list is invalid.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        match Origin::parse_route(&self.path) {
+            Ok(uri) if uri.is_normalized() && !uri.path().contains('<') => {},
+            _ => return Err(format!("invalid path: '{}'", self.path)),
+        }
+
+        let buckets = [("latency", &self.latency_buckets), ("size", &self.size_buckets)];
+        for (name, bounds) in buckets.iter() {
+            if !bounds.windows(2).all(|w| w[0] < w[1]) || bounds.iter().any(|b| !b.is_finite()) {
+                return Err(format!("`{}_buckets` must be finite and increasing", name));
+            }
+        }
+
+        Ok(())
+    }
+}
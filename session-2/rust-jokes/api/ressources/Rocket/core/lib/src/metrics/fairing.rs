@@ -0,0 +1,113 @@
+use crate::{Rocket, Build, Request, Response, Data, Route};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::request::Started;
+use crate::route::{self, Handler, Outcome};
+use crate::http::{ContentType, Method, Status};
+use crate::metrics::Config;
+use crate::metrics::registry::Registry;
+
+/// A [`Fairing`] that records request metrics and serves them to Prometheus.
+///
+/// See the [module docs](crate::metrics) for the recorded metrics and
+/// configuration details.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::metrics::Metrics;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(Metrics::new())
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Metrics {
+    _private: (),
+}
+
+impl Metrics {
+    /// Returns a `Metrics` fairing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::metrics::Metrics;
+    ///
+    /// let metrics = Metrics::new();
+    /// ```
+    pub fn new() -> Self {
+        Metrics { _private: () }
+    }
+}
+
+/// The route handler serving metrics.
+#[derive(Clone)]
+struct Endpoint;
+
+#[crate::async_trait]
+impl Handler for Endpoint {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> route::Outcome<'r> {
+        match req.rocket().state::<Registry>() {
+            Some(registry) => {
+                let content_type = ContentType::with_params("text", "plain", ("version", "0.0.4"));
+
+                Outcome::from(req, (content_type, registry.render()))
+            }
+            None => Outcome::Failure(Status::InternalServerError),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Metrics {
+    fn info(&self) -> Info {
+        Info {
+            name: "Metrics",
+            kind: Kind::Ignite | Kind::Request | Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let config = match crate::config::extract_or_default::<Config>(&rocket, "metrics") {
+            Some(config) => config,
+            None => return Err(rocket),
+        };
+
+        if let Err(e) = config.validate() {
+            error!("invalid metrics configuration: {}", e);
+            return Err(rocket);
+        }
+
+        let mut route = Route::new(Method::Get, &config.path, Endpoint);
+        route.name = Some("Metrics".into());
+        Ok(rocket.manage(Registry::new(&config)).mount("/", vec![route]))
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data<'_>) {
+        if let Some(registry) = req.rocket().state::<Registry>() {
+            registry.start(req.method());
+            Started::record(req);
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let (start, method) = match Started::of(req) {
+            Some(started) => started,
+            None => return,
+        };
+
+        // Label by the route that responded, if any, but not by routes that
+        // forwarded or failed to a catcher.
+        let route = req.responder()
+            .map(|route| route.name.as_deref().unwrap_or_else(|| route.uri.as_str()))
+            .unwrap_or("")
+            .to_string();
+
+        let size = res.body_mut().size().await.map(|size| size as u64);
+        if let Some(registry) = req.rocket().state::<Registry>() {
+            registry.finish(method, route, res.status().code, start.elapsed(), size);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Request metrics in the Prometheus text exposition format.
+//!
+//! The [`Metrics`] fairing records metrics of every request and serves them in
+//! the [Prometheus text exposition format] at `/metrics`, ready to be scraped.
+//!
+//! [Prometheus text exposition format]:
+//! https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format
+//!
+//! # Enabling
+//!
+//! This module is only available when the `metrics` feature is enabled:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket = { version = "0.5.0-rc.1", features = ["metrics"] }
+//! ```
+//!
+//! # Usage
+//!
+//! Attach the fairing:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::metrics::Metrics;
+//!
+//! #[get("/")]
+//! fn index() -> &'static str {
+//!     "Hello, world!"
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![index])
+//!         .attach(Metrics::new())
+//! }
+//! ```
+//!
+//! # Metrics
+//!
+//! The following metrics are recorded:
+//!
+//!   * `http_requests_total`: a counter of handled requests.
+//!   * `http_requests_in_flight`: a gauge of requests being handled.
+//!   * `http_request_duration_seconds`: a histogram of the time between the
+//!     arrival of a request and its response being ready to be written.
+//!   * `http_response_size_bytes`: a histogram of the size of response bodies.
+//!     Streamed bodies, whose size isn't known in advance, aren't observed.
+//!
+//! All metrics are labeled with the request's `method`, as received. Except
+//! for `http_requests_in_flight`, they are also labeled with the `route` that
+//! responded, by name or, for unnamed routes, by URI template, and the
+//! response's `status`. Responses from catchers, including those to requests
+//! every matching route forwarded or failed, have an empty `route`. Labeling by
+//! route rather than by URI keeps the number of series low, no matter which
+//! URIs clients request.
+//!
+//! ```text
+//! http_requests_total{method="GET",route="index",status="200"} 42
+//! http_requests_in_flight{method="GET"} 1
+//! ```
+//!
+//! # Configuration
+//!
+//! The path metrics are served at and the bounds of the histograms' buckets
+//! are read from the `metrics` configuration parameter into a [`Config`]. The
+//! defaults are:
+//!
+//! ```toml
+//! [default.metrics]
+//! path = "/metrics"
+//! latency_buckets = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1, 2.5, 5, 10]
+//! size_buckets = [100, 1000, 10000, 100000, 1000000, 10000000]
+//! ```
+//!
+//! Bucket bounds must be increasing. The endpoint isn't protected in any way:
+//! consider serving it on a path that isn't reachable from the public internet.
+
+mod config;
+mod registry;
+mod fairing;
+
+pub use self::config::Config;
+pub use self::fairing::Metrics;
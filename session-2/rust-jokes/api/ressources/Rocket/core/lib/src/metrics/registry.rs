@@ -0,0 +1,218 @@
+use std::fmt::Write;
+use std::time::Duration;
+use std::collections::BTreeMap;
+
+use parking_lot::Mutex;
+
+use crate::http::Method;
+use crate::metrics::Config;
+
+/// The labels of a series of finished requests.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Labels {
+    method: &'static str,
+    route: String,
+    status: u16,
+}
+
+/// Observation counts per bucket, _not_ cumulative, along with their sum.
+#[derive(Debug)]
+struct Histogram {
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+#[derive(Debug)]
+struct Series {
+    latency: Histogram,
+    size: Histogram,
+}
+
+/// Metrics of all requests, managed by the [`Metrics`] fairing.
+///
+/// [`Metrics`]: crate::metrics::Metrics
+pub(crate) struct Registry {
+    latency_bounds: Vec<f64>,
+    size_bounds: Vec<f64>,
+    in_flight: Mutex<BTreeMap<&'static str, u64>>,
+    series: Mutex<BTreeMap<Labels, Series>>,
+}
+
+impl Histogram {
+    fn new(bounds: &[f64]) -> Histogram {
+        Histogram { buckets: vec![0; bounds.len()], sum: 0.0, count: 0 }
+    }
+
+    fn observe(&mut self, bounds: &[f64], value: f64) {
+        if let Some(i) = bounds.iter().position(|bound| value <= *bound) {
+            self.buckets[i] += 1;
+        }
+
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str, bounds: &[f64]) {
+        let mut cumulative = 0;
+        for (bound, count) in bounds.iter().zip(self.buckets.iter()) {
+            cumulative += count;
+            let _ = writeln!(out, "{}_bucket{{{},le=\"{}\"}} {}", name, labels, bound, cumulative);
+        }
+
+        let _ = writeln!(out, "{}_bucket{{{},le=\"+Inf\"}} {}", name, labels, self.count);
+        let _ = writeln!(out, "{}_sum{{{}}} {}", name, labels, self.sum);
+        let _ = writeln!(out, "{}_count{{{}}} {}", name, labels, self.count);
+    }
+}
+
+impl Labels {
+    fn render(&self) -> String {
+        let mut out = format!("method=\"{}\",route=\"", self.method);
+        for c in self.route.chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                '"' => out.push_str("\\\""),
+                '\n' => out.push_str("\\n"),
+                c => out.push(c),
+            }
+        }
+
+        let _ = write!(out, "\",status=\"{}\"", self.status);
+        out
+    }
+}
+
+impl Registry {
+    pub fn new(config: &Config) -> Registry {
+        Registry {
+            latency_bounds: config.latency_buckets.clone(),
+            size_bounds: config.size_buckets.clone(),
+            in_flight: Mutex::new(BTreeMap::new()),
+            series: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Records the start of a request with `method`.
+    pub fn start(&self, method: Method) {
+        *self.in_flight.lock().entry(method.as_str()).or_default() += 1;
+    }
+
+    /// Records the end of a request with `method`, started with
+    /// [`Registry::start()`], that was handled by `route` and responded to
+    /// with `status` and a body of `size` bytes, if known, after `latency`.
+    pub fn finish(
+        &self,
+        method: Method,
+        route: String,
+        status: u16,
+        latency: Duration,
+        size: Option<u64>,
+    ) {
+        if let Some(in_flight) = self.in_flight.lock().get_mut(method.as_str()) {
+            *in_flight = in_flight.saturating_sub(1);
+        }
+
+        let labels = Labels { method: method.as_str(), route, status };
+        let mut series = self.series.lock();
+        let series = series.entry(labels).or_insert_with(|| Series {
+            latency: Histogram::new(&self.latency_bounds),
+            size: Histogram::new(&self.size_bounds),
+        });
+
+        series.latency.observe(&self.latency_bounds, latency.as_secs_f64());
+        if let Some(size) = size {
+            series.size.observe(&self.size_bounds, size as f64);
+        }
+    }
+
+    /// Renders every metric in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let series = self.series.lock();
+        let labels: Vec<_> = series.iter().map(|(labels, s)| (labels.render(), s)).collect();
+        let mut out = String::with_capacity(256 * (labels.len() + 1));
+
+        out.push_str("# HELP http_requests_total The number of requests handled.\n");
+        out.push_str("# TYPE http_requests_total counter\n");
+        for (labels, series) in &labels {
+            let _ = writeln!(out, "http_requests_total{{{}}} {}", labels, series.latency.count);
+        }
+
+        out.push_str("# HELP http_requests_in_flight The number of requests being handled.\n");
+        out.push_str("# TYPE http_requests_in_flight gauge\n");
+        for (method, count) in self.in_flight.lock().iter() {
+            let _ = writeln!(out, "http_requests_in_flight{{method=\"{}\"}} {}", method, count);
+        }
+
+        let name = "http_request_duration_seconds";
+        out.push_str("# HELP http_request_duration_seconds The latency of requests.\n");
+        out.push_str("# TYPE http_request_duration_seconds histogram\n");
+        for (labels, series) in &labels {
+            series.latency.render(&mut out, name, labels, &self.latency_bounds);
+        }
+
+        let name = "http_response_size_bytes";
+        out.push_str("# HELP http_response_size_bytes The size of response bodies.\n");
+        out.push_str("# TYPE http_response_size_bytes histogram\n");
+        for (labels, series) in labels.iter().filter(|(_, series)| series.size.count > 0) {
+            series.size.render(&mut out, name, labels, &self.size_bounds);
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let config = Config {
+            latency_buckets: vec![0.1, 1.0],
+            size_buckets: vec![10.0],
+            ..Config::default()
+        };
+
+        let registry = Registry::new(&config);
+        registry.start(Method::Get);
+        registry.start(Method::Get);
+        registry.start(Method::Post);
+        registry.finish(Method::Get, "a\"b".into(), 200, Duration::from_millis(500), Some(4));
+        registry.finish(Method::Post, "c".into(), 404, Duration::from_secs(2), None);
+
+        let get = r#"method="GET",route="a\"b",status="200""#;
+        let post = r#"method="POST",route="c",status="404""#;
+        let expected = [
+            "# HELP http_requests_total The number of requests handled.".into(),
+            "# TYPE http_requests_total counter".into(),
+            format!("http_requests_total{{{}}} 1", get),
+            format!("http_requests_total{{{}}} 1", post),
+            "# HELP http_requests_in_flight The number of requests being handled.".into(),
+            "# TYPE http_requests_in_flight gauge".into(),
+            r#"http_requests_in_flight{method="GET"} 1"#.into(),
+            r#"http_requests_in_flight{method="POST"} 0"#.into(),
+            "# HELP http_request_duration_seconds The latency of requests.".into(),
+            "# TYPE http_request_duration_seconds histogram".into(),
+            format!(r#"http_request_duration_seconds_bucket{{{},le="0.1"}} 0"#, get),
+            format!(r#"http_request_duration_seconds_bucket{{{},le="1"}} 1"#, get),
+            format!(r#"http_request_duration_seconds_bucket{{{},le="+Inf"}} 1"#, get),
+            format!("http_request_duration_seconds_sum{{{}}} 0.5", get),
+            format!("http_request_duration_seconds_count{{{}}} 1", get),
+            format!(r#"http_request_duration_seconds_bucket{{{},le="0.1"}} 0"#, post),
+            format!(r#"http_request_duration_seconds_bucket{{{},le="1"}} 0"#, post),
+            format!(r#"http_request_duration_seconds_bucket{{{},le="+Inf"}} 1"#, post),
+            format!("http_request_duration_seconds_sum{{{}}} 2", post),
+            format!("http_request_duration_seconds_count{{{}}} 1", post),
+            "# HELP http_response_size_bytes The size of response bodies.".into(),
+            "# TYPE http_response_size_bytes histogram".into(),
+            format!(r#"http_response_size_bytes_bucket{{{},le="10"}} 1"#, get),
+            format!(r#"http_response_size_bytes_bucket{{{},le="+Inf"}} 1"#, get),
+            format!("http_response_size_bytes_sum{{{}}} 4", get),
+            format!("http_response_size_bytes_count{{{}}} 1", get),
+        ];
+
+        let rendered = registry.render();
+        assert_eq!(rendered.lines().collect::<Vec<_>>(), expected);
+    }
+}
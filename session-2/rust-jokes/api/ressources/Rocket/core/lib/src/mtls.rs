@@ -0,0 +1,277 @@
+//! Support for mutual TLS client certificates.
+//!
+//! # Enabling
+//!
+//! This module is only available when the `mtls` feature is enabled. Enable it
+//! in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket]
+//! version = "0.5.0-rc.1"
+//! features = ["mtls"]
+//! ```
+//!
+//! # Configuration
+//!
+//! Mutual TLS is configured via the `tls.mutual` configuration parameter. See
+//! [`MutualTls`](crate::config::MutualTls) for details. In short, with the
+//! following `Rocket.toml`, clients must present a certificate signed by one of
+//! the CAs in `ca_cert.pem` to connect at all:
+//!
+//! ```toml
+//! [default.tls]
+//! certs = "cert.pem"
+//! key = "key.pem"
+//!
+//! [default.tls.mutual]
+//! ca_certs = "ca_cert.pem"
+//! mandatory = true
+//! ```
+//!
+//! # Usage
+//!
+//! A [`Certificate`] is a request guard that succeeds only if the client
+//! presented a valid certificate chain during the TLS handshake. Use
+//! `Option<Certificate>` when client certificates are not mandatory:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::mtls::Certificate;
+//!
+//! #[get("/auth")]
+//! fn auth(cert: Certificate<'_>) -> String {
+//!     format!("Hello, {}!", cert.subject())
+//! }
+//!
+//! #[get("/maybe")]
+//! fn maybe_auth(cert: Option<Certificate<'_>>) -> &'static str {
+//!     match cert {
+//!         Some(_) => "Hello, authenticated client!",
+//!         None => "Hello, anonymous client!",
+//!     }
+//! }
+//! ```
+//!
+//! Certificates are parsed with [`x509-parser`](x509), which is reexported in
+//! its entirety as `rocket::mtls::x509`.
+
+use std::fmt;
+
+use crate::request::{FromRequest, Outcome, Request};
+use crate::http::Status;
+use crate::http::private::RawCertificate;
+
+#[doc(inline)]
+pub use x509_parser as x509;
+
+use x509::certificate::X509Certificate;
+use x509::error::X509Error;
+use x509::x509::X509Name;
+use x509::num_bigint::BigUint;
+
+/// A request guard for a validated client certificate.
+///
+/// A `Certificate` represents the end-entity certificate presented by a client
+/// during a mutual TLS handshake. By the time a request is routed, the
+/// certificate chain has already been verified against the CA certificates in
+/// the [`MutualTls`](crate::config::MutualTls) configuration, so a
+/// `Certificate` is only ever constructed from a trusted chain.
+///
+/// # Request Guard
+///
+/// The request guard fails with a status of `401 Unauthorized` and an
+/// [`Error`] if the client did not present a certificate or if the presented
+/// certificate could not be parsed. Use `Option<Certificate>` or
+/// `Result<Certificate, Error>` to handle these cases manually.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::mtls::Certificate;
+///
+/// #[get("/whoami")]
+/// fn whoami(cert: Certificate<'_>) -> String {
+///     let cn = cert.subject().iter_common_name()
+///         .next()
+///         .and_then(|cn| cn.as_str().ok())
+///         .unwrap_or("unknown");
+///
+///     format!("{} (serial {}), issued by {}", cn, cert.serial(), cert.issuer())
+/// }
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Certificate<'a> {
+    x509: X509Certificate<'a>,
+    chain: &'a [RawCertificate],
+}
+
+/// An error returned by the [`Certificate`] request guard.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// The client did not present a certificate.
+    Empty,
+    /// The client's certificate could not be parsed.
+    Parse(X509Error),
+    /// The client's certificate was incomplete.
+    Incomplete(x509::nom::Needed),
+    /// The client's certificate contained `.0` bytes of trailing data.
+    Trailing(usize),
+}
+
+impl<'a> Certificate<'a> {
+    /// Parses the end-entity certificate in `chain`, the first certificate.
+    fn parse(chain: &'a [RawCertificate]) -> Result<Certificate<'a>, Error> {
+        let end_entity = chain.first().ok_or(Error::Empty)?;
+        let (rest, x509) = x509::parse_x509_certificate(&end_entity.0)?;
+        if !rest.is_empty() {
+            return Err(Error::Trailing(rest.len()));
+        }
+
+        Ok(Certificate { x509, chain })
+    }
+
+    /// Returns the serial number of the certificate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::mtls::Certificate;
+    ///
+    /// #[get("/auth")]
+    /// fn auth(cert: Certificate<'_>) -> String {
+    ///     cert.serial().to_string()
+    /// }
+    /// ```
+    pub fn serial(&self) -> &BigUint {
+        &self.x509.tbs_certificate.serial
+    }
+
+    /// Returns the subject (the "owner") of the certificate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::mtls::Certificate;
+    ///
+    /// #[get("/auth")]
+    /// fn auth(cert: Certificate<'_>) -> String {
+    ///     cert.subject().to_string()
+    /// }
+    /// ```
+    pub fn subject(&self) -> &X509Name<'_> {
+        self.x509.subject()
+    }
+
+    /// Returns the issuer of the certificate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::mtls::Certificate;
+    ///
+    /// #[get("/auth")]
+    /// fn auth(cert: Certificate<'_>) -> String {
+    ///     cert.issuer().to_string()
+    /// }
+    /// ```
+    pub fn issuer(&self) -> &X509Name<'_> {
+        self.x509.issuer()
+    }
+
+    /// Returns the fully parsed X.509 certificate.
+    pub fn x509(&self) -> &X509Certificate<'a> {
+        &self.x509
+    }
+
+    /// Returns the raw, DER-encoded bytes of the certificate.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        &self.chain[0].0
+    }
+
+    /// Returns an iterator over the raw, DER-encoded bytes of every certificate
+    /// in the chain presented by the client, beginning with this certificate.
+    pub fn chain(&self) -> impl Iterator<Item = &'a [u8]> {
+        self.chain.iter().map(|cert| &*cert.0)
+    }
+}
+
+impl From<x509::nom::Err<X509Error>> for Error {
+    fn from(e: x509::nom::Err<X509Error>) -> Self {
+        match e {
+            x509::nom::Err::Incomplete(needed) => Error::Incomplete(needed),
+            x509::nom::Err::Error(e) | x509::nom::Err::Failure(e) => Error::Parse(e),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Empty => write!(f, "no client certificate was presented"),
+            Error::Parse(e) => write!(f, "invalid client certificate: {}", e),
+            Error::Incomplete(_) => write!(f, "incomplete client certificate"),
+            Error::Trailing(n) => write!(f, "client certificate has {} trailing bytes", n),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Certificate<'r> {
+    type Error = Error;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let chain = match req.client_certificates() {
+            Some(chain) => chain,
+            None => return Outcome::Failure((Status::Unauthorized, Error::Empty)),
+        };
+
+        match Certificate::parse(chain) {
+            Ok(cert) => Outcome::Success(cert),
+            Err(e) => Outcome::Failure((Status::Unauthorized, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn der(pem: &[u8]) -> RawCertificate {
+        let (_, pem) = x509::pem::parse_x509_pem(pem).expect("valid PEM");
+        RawCertificate(pem.contents)
+    }
+
+    #[test]
+    fn parse_certificate() {
+        let chain = [der(include_bytes!("../../../examples/tls/private/rsa_sha256_cert.pem"))];
+        let cert = Certificate::parse(&chain).unwrap();
+
+        let cn = |name: &X509Name<'_>| name.iter_common_name()
+            .next()
+            .and_then(|cn| cn.as_str().ok())
+            .map(|cn| cn.to_string());
+
+        assert_eq!(cn(cert.subject()).as_deref(), Some("localhost"));
+        assert_eq!(cn(cert.issuer()).as_deref(), Some("Rocket Root CA"));
+        assert_eq!(cert.as_bytes(), &*chain[0].0);
+        assert_eq!(cert.chain().count(), 1);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Certificate::parse(&[]).unwrap_err(), Error::Empty);
+
+        let garbage = [RawCertificate(vec![0xde, 0xad, 0xbe, 0xef])];
+        assert!(Certificate::parse(&garbage).is_err());
+
+        let mut cert = der(include_bytes!("../../../examples/tls/private/rsa_sha256_cert.pem"));
+        cert.0.extend_from_slice(&[0, 0, 0]);
+        assert_eq!(Certificate::parse(&[cert]).unwrap_err(), Error::Trailing(3));
+    }
+}
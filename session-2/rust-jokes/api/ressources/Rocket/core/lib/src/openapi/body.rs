@@ -0,0 +1,99 @@
+use std::borrow::Cow;
+
+use serde_json::{json, Value};
+
+use crate::openapi::{Generator, Schema};
+
+/// A data guard with a request body description.
+///
+/// Implementations are provided for [`Form<T>`](crate::form::Form) and
+/// [`Json<T>`](crate::serde::json::Json) where `T` implements [`Schema`], for
+/// strings and bytes, and for [`Data`](crate::Data). `Option<T>` describes an
+/// optional body.
+///
+/// # Example
+///
+/// A data guard for CSV files:
+///
+/// ```rust
+/// use rocket::openapi::{Generator, RequestBody};
+/// use rocket::serde::json::{json, Value};
+///
+/// struct Csv(Vec<Vec<String>>);
+///
+/// impl RequestBody for Csv {
+///     fn request_body(_: &mut Generator) -> Value {
+///         json!({
+///             "required": true,
+///             "content": { "text/csv": { "schema": { "type": "string" } } }
+///         })
+///     }
+/// }
+/// ```
+pub trait RequestBody {
+    /// Returns the request body object describing `Self`.
+    fn request_body(gen: &mut Generator) -> Value;
+}
+
+/// Returns a required request body with a `schema` for each of `media_types`.
+fn body(media_types: &[&str], schema: Value) -> Value {
+    let content: serde_json::Map<_, _> = media_types.iter()
+        .map(|media_type| (media_type.to_string(), json!({ "schema": schema })))
+        .collect();
+
+    json!({ "required": true, "content": content })
+}
+
+macro_rules! impl_body {
+    ($($T:ty),* => $media_type:expr, $schema:tt) => ($(
+        impl RequestBody for $T {
+            fn request_body(_: &mut Generator) -> Value {
+                body(&[$media_type], json!($schema))
+            }
+        }
+    )*)
+}
+
+impl_body!(&str, String, Cow<'_, str> => "text/plain", { "type": "string" });
+impl_body!(&[u8], Vec<u8>, crate::Data<'_>, crate::fs::TempFile<'_>
+    => "application/octet-stream", { "type": "string", "format": "binary" });
+
+impl<T: Schema> RequestBody for crate::form::Form<T> {
+    fn request_body(gen: &mut Generator) -> Value {
+        let schema = gen.schema::<T>();
+        body(&["application/x-www-form-urlencoded", "multipart/form-data"], schema)
+    }
+}
+
+impl<T: Schema> RequestBody for crate::serde::json::Json<T> {
+    fn request_body(gen: &mut Generator) -> Value {
+        body(&["application/json"], gen.schema::<T>())
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl<T: Schema> RequestBody for crate::serde::msgpack::MsgPack<T> {
+    fn request_body(gen: &mut Generator) -> Value {
+        body(&["application/msgpack"], gen.schema::<T>())
+    }
+}
+
+impl<T: RequestBody> RequestBody for crate::data::Capped<T> {
+    fn request_body(gen: &mut Generator) -> Value {
+        T::request_body(gen)
+    }
+}
+
+impl<T: RequestBody, E> RequestBody for Result<T, E> {
+    fn request_body(gen: &mut Generator) -> Value {
+        T::request_body(gen)
+    }
+}
+
+impl<T: RequestBody> RequestBody for Option<T> {
+    fn request_body(gen: &mut Generator) -> Value {
+        let mut body = T::request_body(gen);
+        body["required"] = false.into();
+        body
+    }
+}
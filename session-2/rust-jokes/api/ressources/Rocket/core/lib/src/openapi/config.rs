@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// The OpenAPI configuration: where the document is served and what it says
+/// about the API.
+///
+/// `title`, `version`, and `description` make up the document's `info`
+/// object. The [`OpenApi`](crate::openapi::OpenApi) fairing fails to ignite if
+/// `path` isn't a static route path.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::openapi::Config;
+///
+/// let figment = rocket::Config::figment()
+///     .merge(("openapi.path", "/api/spec.json"))
+///     .merge(("openapi.title", "Jokes"));
+///
+/// let config: Config = figment.extract_inner("openapi").unwrap();
+/// assert_eq!(config.path, "/api/spec.json");
+/// assert_eq!(config.title, "Jokes");
+/// assert_eq!(config.version, "0.1.0");
+/// assert!(config.description.is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// The path the document is served at. **(default: `"/openapi.json"`)**
+    pub path: String,
+    /// The title of the API. **(default: `"API"`)**
+    pub title: String,
+    /// The version of the API. **(default: `"0.1.0"`)**
+    pub version: String,
+    /// A description of the API, if any. **(default: `None`)**
+    pub description: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            path: "/openapi.json".into(),
+            title: "API".into(),
+            version: "0.1.0".into(),
+            description: None,
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use serde_json::{json, Map};
+use state::Storage;
+
+use crate::{Rocket, Build, Orbit, Request, Data, Route};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::route::{self, Handler, Outcome};
+use crate::http::{ContentType, Method, uri::Origin};
+use crate::openapi::{Config, Generator};
+
+/// Holds the configuration and the rendered document. Managed by the
+/// [`OpenApi`] fairing.
+pub(crate) struct Manager {
+    config: Config,
+    document: Storage<String>,
+}
+
+impl Manager {
+    /// Returns the document describing the routes of `rocket`, rendering it
+    /// first if it hasn't been yet.
+    fn document<P: crate::phase::Phase>(&self, rocket: &Rocket<P>) -> &str {
+        self.document.get_or_set(|| render(&self.config, rocket))
+    }
+}
+
+/// Returns the path template of `route`, as OpenAPI expects it: `/a/{b}`.
+fn template(route: &Route) -> String {
+    let segments: Vec<_> = route.uri.metadata.path_segs.iter()
+        .map(|s| match s.dynamic {
+            true => format!("{{{}}}", s.value),
+            false => s.value.clone(),
+        })
+        .collect();
+
+    format!("/{}", segments.join("/"))
+}
+
+/// Renders the document describing the described routes of `rocket`.
+fn render<P: crate::phase::Phase>(config: &Config, rocket: &Rocket<P>) -> String {
+    let mut gen = Generator::new();
+    let mut paths = Map::new();
+    for route in rocket.routes() {
+        let describe = match route.operation {
+            Some(describe) => describe,
+            None => continue,
+        };
+
+        let mut operation = describe(&mut gen);
+        if let Some(ref format) = route.format {
+            if route.method.supports_payload() {
+                operation.restrict_body(&format.to_string());
+            }
+        }
+
+        let item = paths.entry(template(route)).or_insert_with(|| json!({}));
+        let method = route.method.as_str().to_lowercase();
+        if item.get(&method).is_some() {
+            warn_!("OpenAPI: multiple routes describe {} {}; using the first.",
+                route.method, template(route));
+            continue;
+        }
+
+        item[method] = operation.into_value();
+    }
+
+    let mut info = json!({ "title": config.title, "version": config.version });
+    if let Some(ref description) = config.description {
+        info["description"] = description.as_str().into();
+    }
+
+    let document = json!({
+        "openapi": "3.0.3",
+        "info": info,
+        "paths": paths,
+        "components": { "schemas": gen.into_schemas() },
+    });
+
+    document.to_string()
+}
+
+/// Serves the rendered document.
+#[derive(Clone)]
+struct Document;
+
+#[crate::async_trait]
+impl Handler for Document {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> route::Outcome<'r> {
+        match req.rocket().state::<Manager>() {
+            Some(manager) => {
+                let document = manager.document(req.rocket());
+                Outcome::from(req, (ContentType::JSON, document))
+            }
+            None => Outcome::Forward(data),
+        }
+    }
+}
+
+/// A [`Fairing`] that serves an OpenAPI 3 document describing the application's
+/// routes.
+///
+/// See the [module docs](crate::openapi) for usage, configuration, and
+/// behavior details.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::openapi::OpenApi;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(OpenApi::new())
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct OpenApi {
+    _private: (),
+}
+
+impl OpenApi {
+    /// Returns an `OpenApi` fairing that reads its [`Config`] from the
+    /// `openapi` configuration parameter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::openapi::OpenApi;
+    ///
+    /// let openapi = OpenApi::new();
+    /// ```
+    pub fn new() -> Self {
+        OpenApi { _private: () }
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for OpenApi {
+    fn info(&self) -> Info {
+        Info {
+            name: "OpenAPI",
+            kind: Kind::Ignite | Kind::Liftoff | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let config = match crate::config::extract_or_default::<Config>(&rocket, "openapi") {
+            Some(config) => config,
+            None => return Err(rocket),
+        };
+
+        match Origin::parse_route(&config.path) {
+            Ok(uri) if uri.is_normalized() && !uri.path().contains('<') => {},
+            _ => {
+                error!("invalid OpenAPI document path: '{}'", config.path);
+                return Err(rocket);
+            }
+        }
+
+        let mut route = Route::new(Method::Get, &config.path, Document);
+        route.name = Some("OpenApi".into());
+        let manager = Manager { config, document: Storage::new() };
+        Ok(rocket.manage(manager).mount("/", vec![route]))
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        if let Some(manager) = rocket.state::<Manager>() {
+            let paths = rocket.routes().filter(|r| r.operation.is_some()).count();
+            manager.document(rocket);
+            info_!("OpenAPI: described {} route(s) at {}.", paths, manager.config.path);
+        }
+    }
+}
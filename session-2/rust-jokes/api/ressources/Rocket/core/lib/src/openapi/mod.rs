@@ -0,0 +1,136 @@
+//! OpenAPI 3 document generation from route attributes.
+//!
+//! Routes marked with `#[openapi]` describe themselves: their method, path,
+//! parameters, request body, and responses, documented by the handler's doc
+//! comments. The [`OpenApi`] fairing collects the descriptions of all mounted
+//! routes into an [OpenAPI 3] document at launch and serves it as JSON, so API
+//! clients can be generated from it.
+//!
+//! [OpenAPI 3]: https://spec.openapis.org/oas/v3.0.3
+//!
+//! # Enabling
+//!
+//! This module is only available when the `openapi` feature is enabled, which
+//! in turn enables the `json` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket = { version = "0.5.0-rc.1", features = ["openapi"] }
+//! ```
+//!
+//! # Usage
+//!
+//! Add `#[openapi]` _below_ the route attribute of each route to describe and
+//! attach the fairing. The document is served at `/openapi.json` by default:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::form::Form;
+//! use rocket::serde::{Serialize, json::Json};
+//! use rocket::openapi::{OpenApi, Schema};
+//!
+//! #[derive(FromForm, Schema)]
+//! struct NewJoke<'r> {
+//!     /// The joke's setup.
+//!     setup: &'r str,
+//!     punchline: &'r str,
+//! }
+//!
+//! #[derive(Serialize, Schema)]
+//! #[serde(crate = "rocket::serde")]
+//! struct Joke {
+//!     id: u32,
+//!     setup: String,
+//!     punchline: String,
+//! }
+//!
+//! /// Returns the joke with ID `id`.
+//! #[get("/jokes/<id>")]
+//! #[openapi(tag = "jokes")]
+//! fn joke(id: u32) -> Option<Json<Joke>> {
+//!     # let _ = id;
+//!     /* .. */
+//!     # None
+//! }
+//!
+//! /// Adds a joke.
+//! ///
+//! /// The new joke is returned with its ID.
+//! #[post("/jokes", data = "<joke>")]
+//! #[openapi(tag = "jokes")]
+//! fn add(joke: Form<NewJoke<'_>>) -> Json<Joke> {
+//!     /* .. */
+//!     # Json(Joke { id: 0, setup: joke.setup.into(), punchline: joke.punchline.into() })
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![joke, add])
+//!         .attach(OpenApi::new())
+//! }
+//! ```
+//!
+//! The attribute accepts two optional parameters:
+//!
+//!   * `tag = "name"`: groups the operation under the tag `name`.
+//!   * `deprecated`: marks the operation as deprecated.
+//!
+//! # Descriptions
+//!
+//! Each described route becomes an operation on the route's path, with
+//! dynamic segments written as `{name}`, identified by the handler's name.
+//! The first paragraph of the handler's doc comment is the operation's
+//! summary; the rest is its description.
+//!
+//! Path and query parameter types must implement [`Schema`], the data guard
+//! type [`RequestBody`], and the return type [`Responses`]. Trailing query
+//! parameters (`<param..>`) are expanded into one query parameter per
+//! property of their schema. Handlers that return `impl Responder` have no
+//! described responses. Request guards aren't described.
+//!
+//! Schemas are provided for primitive types, strings, and collections and can
+//! be derived for structures and enums. `Schema` is named as `serde` would name
+//! it, so types can derive it alongside `Serialize`, `Deserialize`, and
+//! `FromForm`. Bodies are described for forms, JSON, strings, and bytes;
+//! responses for Rocket's responders and, by deriving `Responses`, custom
+//! [`Responder`](crate::response::Responder)s.
+//!
+//! # Configuration
+//!
+//! The fairing is configured by the `openapi` configuration parameter. For
+//! example, in `Rocket.toml`:
+//!
+//! ```toml
+//! [default.openapi]
+//! path = "/api/openapi.json"
+//! title = "Jokes"
+//! version = "1.2.0"
+//! description = "Jokes, on demand."
+//! ```
+//!
+//! See [`Config`] for all parameters and defaults.
+
+mod schema;
+mod body;
+mod responses;
+mod operation;
+mod config;
+mod fairing;
+
+pub use schema::{Generator, Schema};
+pub use body::RequestBody;
+pub use responses::{Responses, response, with_status, with_media_type};
+pub use operation::Operation;
+pub use config::Config;
+pub use fairing::OpenApi;
+
+#[doc(inline)]
+pub use rocket_codegen::{Schema, Responses};
+
+#[doc(hidden)]
+pub mod private {
+    pub use super::schema::{describe, object, strings, tuple, one_of};
+    pub use super::responses::{describe as describe_responses, union};
+    pub use serde_json::{Map, Value};
+}
@@ -0,0 +1,232 @@
+use serde_json::{json, Map, Value};
+
+use crate::openapi::{Generator, Schema, RequestBody, Responses};
+
+/// The description of a route: an OpenAPI operation object.
+///
+/// An `Operation` is built by the code generated for routes marked with
+/// `#[openapi]` and is added to the document by the
+/// [`OpenApi`](crate::openapi::OpenApi) fairing. It's built from the handler's
+/// doc comments, the route's parameters and data guard, and the handler's
+/// return type.
+///
+/// # Example
+///
+/// Describing a route by hand:
+///
+/// ```rust
+/// use rocket::openapi::{Generator, Operation};
+/// use rocket::serde::json::Json;
+///
+/// let mut gen = Generator::new();
+/// let operation = Operation::new("get_user")
+///     .doc("Fetches a user.\n\nReturns `404` if there's no such user.")
+///     .tag("users")
+///     .path_param::<u32>(&mut gen, "id")
+///     .responses::<Option<Json<String>>>(&mut gen);
+///
+/// assert_eq!(operation.summary(), Some("Fetches a user."));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operation {
+    operation_id: String,
+    summary: Option<String>,
+    description: Option<String>,
+    tags: Vec<String>,
+    deprecated: bool,
+    parameters: Vec<Value>,
+    request_body: Option<Value>,
+    responses: Map<String, Value>,
+}
+
+impl Operation {
+    /// Returns an operation with the ID `operation_id` and nothing else.
+    pub fn new<S: Into<String>>(operation_id: S) -> Self {
+        Operation {
+            operation_id: operation_id.into(),
+            summary: None,
+            description: None,
+            tags: vec![],
+            deprecated: false,
+            parameters: vec![],
+            request_body: None,
+            responses: Map::new(),
+        }
+    }
+
+    /// Sets the summary to the first paragraph of `doc` and the description
+    /// to the rest, if any.
+    pub fn doc(mut self, doc: &str) -> Self {
+        let doc = doc.trim();
+        let (summary, description) = match doc.find("\n\n") {
+            Some(i) => (&doc[..i], doc[i..].trim()),
+            None => (doc, ""),
+        };
+
+        let summary = summary.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+        self.summary = Some(summary).filter(|s| !s.is_empty());
+        self.description = Some(description.to_string()).filter(|s| !s.is_empty());
+        self
+    }
+
+    /// Adds the tag `tag`.
+    pub fn tag<S: Into<String>>(mut self, tag: S) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Sets whether the operation is deprecated.
+    pub fn deprecated(mut self, deprecated: bool) -> Self {
+        self.deprecated = deprecated;
+        self
+    }
+
+    /// Adds the path parameter `name` of type `T`.
+    pub fn path_param<T: Schema + ?Sized>(self, gen: &mut Generator, name: &str) -> Self {
+        let schema = gen.schema::<T>();
+        self.param("path", name, true, schema)
+    }
+
+    /// Adds the query parameter `name` of type `T`.
+    pub fn query_param<T: Schema + ?Sized>(self, gen: &mut Generator, name: &str) -> Self {
+        let schema = gen.schema::<T>();
+        self.param("query", name, T::required(), schema)
+    }
+
+    /// Adds a query parameter for each property of the object schema of `T`,
+    /// as parsed by a trailing query parameter `<param..>`.
+    pub fn query_params<T: Schema + ?Sized>(mut self, gen: &mut Generator) -> Self {
+        let schema = gen.schema::<T>();
+        let schema = gen.resolve(&schema).clone();
+        let required = schema.get("required").and_then(|r| r.as_array());
+        let properties = match schema.get("properties").and_then(|p| p.as_object()) {
+            Some(properties) => properties,
+            None => return self,
+        };
+
+        for (name, schema) in properties {
+            let required = required.map_or(false, |r| r.iter().any(|r| r == name));
+            self = self.param("query", name, required, schema.clone());
+        }
+
+        self
+    }
+
+    fn param(mut self, location: &str, name: &str, required: bool, schema: Value) -> Self {
+        self.parameters.push(json!({
+            "name": name,
+            "in": location,
+            "required": required,
+            "schema": schema,
+        }));
+
+        self
+    }
+
+    /// Sets the request body to the one `T` describes.
+    pub fn request_body<T: RequestBody>(mut self, gen: &mut Generator) -> Self {
+        self.request_body = Some(T::request_body(gen));
+        self
+    }
+
+    /// Adds the responses `T` describes.
+    pub fn responses<T: Responses>(mut self, gen: &mut Generator) -> Self {
+        for (status, response) in T::responses(gen) {
+            self.responses.entry(status).or_insert(response);
+        }
+
+        self
+    }
+
+    /// The operation ID: the name of the handler.
+    pub fn operation_id(&self) -> &str {
+        &self.operation_id
+    }
+
+    /// The summary, if any.
+    pub fn summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+
+    /// Restricts the request body to the media type `format`, if it lists
+    /// content for it.
+    pub(crate) fn restrict_body(&mut self, format: &str) {
+        let content = self.request_body.as_mut()
+            .and_then(|body| body.get_mut("content"))
+            .and_then(|content| content.as_object_mut());
+
+        if let Some(content) = content {
+            if let Some(value) = content.remove(format) {
+                content.clear();
+                content.insert(format.into(), value);
+            }
+        }
+    }
+
+    /// Returns the operation object.
+    pub(crate) fn into_value(self) -> Value {
+        let mut op = json!({ "operationId": self.operation_id });
+        if let Some(summary) = self.summary {
+            op["summary"] = summary.into();
+        }
+
+        if let Some(description) = self.description {
+            op["description"] = description.into();
+        }
+
+        if !self.tags.is_empty() {
+            op["tags"] = self.tags.into();
+        }
+
+        if self.deprecated {
+            op["deprecated"] = true.into();
+        }
+
+        if !self.parameters.is_empty() {
+            op["parameters"] = self.parameters.into();
+        }
+
+        if let Some(body) = self.request_body {
+            op["requestBody"] = body;
+        }
+
+        let mut responses = self.responses;
+        if responses.is_empty() {
+            responses.insert("default".into(), json!({ "description": "Response" }));
+        }
+
+        op["responses"] = responses.into();
+        op
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doc() {
+        let op = Operation::new("a").doc(" Short\n summary.\n\n Longer\n description.\n");
+        assert_eq!(op.summary(), Some("Short summary."));
+        assert_eq!(op.description.as_deref(), Some("Longer\n description."));
+
+        let op = Operation::new("a").doc("");
+        assert!(op.summary.is_none() && op.description.is_none());
+    }
+
+    #[test]
+    fn test_into_value() {
+        let mut gen = Generator::new();
+        let op = Operation::new("a")
+            .path_param::<u8>(&mut gen, "id")
+            .query_param::<Option<String>>(&mut gen, "q")
+            .request_body::<String>(&mut gen)
+            .into_value();
+
+        assert_eq!(op["parameters"][0]["in"], "path");
+        assert_eq!(op["parameters"][1]["required"], false);
+        assert_eq!(op["requestBody"]["content"]["text/plain"]["schema"]["type"], "string");
+        assert_eq!(op["responses"]["default"]["description"], "Response");
+        assert!(op.get("tags").is_none());
+    }
+}
@@ -0,0 +1,399 @@
+use std::borrow::Cow;
+
+use serde_json::{json, Map, Value};
+
+use crate::http::Status;
+use crate::openapi::{Generator, Schema};
+use crate::response::{content, status, Redirect, Flash, Debug};
+
+/// A responder with a description of the responses it generates.
+///
+/// The description is a map from a status code, or `"default"`, to a
+/// response object. Implementations are provided for most of Rocket's
+/// responders: strings and bytes respond with `200`, [`Json<T>`] with `200`
+/// and the schema of `T`, `Option<R>` with `R`'s responses or `404`,
+/// `Result<T, E>` with the union of `T`'s and `E`'s, and so on.
+///
+/// [`Json<T>`]: crate::serde::json::Json
+///
+/// # Deriving
+///
+/// Custom responders derived with [`Responder`](crate::response::Responder)
+/// can also derive `Responses`. Each variant, or the structure, contributes
+/// the responses of its first field, moved to the status set by its
+/// `#[response(status = ...)]` attribute and with the media type set by its
+/// `#[response(content_type = ...)]` attribute, if any:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::openapi::Responses;
+/// use rocket::serde::json::Json;
+///
+/// #[derive(Responder, Responses)]
+/// enum UserResponse {
+///     Found(Json<String>),
+///     /// The user doesn't exist.
+///     #[response(status = 404)]
+///     Missing(&'static str),
+///     #[response(status = 500, content_type = "json")]
+///     Failed(String),
+/// }
+/// ```
+///
+/// Doc comments on variants describe their responses.
+///
+/// # Implementing
+///
+/// Use [`response()`], [`with_status()`], and [`with_media_type()`] to build
+/// descriptions:
+///
+/// ```rust
+/// use rocket::http::Status;
+/// use rocket::openapi::{self, Generator, Responses};
+/// use rocket::serde::json::{json, serde_json::Map, Value};
+///
+/// struct Csv(Vec<Vec<String>>);
+///
+/// impl Responses for Csv {
+///     fn responses(_: &mut Generator) -> Map<String, Value> {
+///         let schema = json!({ "type": "string" });
+///         openapi::response(Some(Status::Ok), None, Some(("text/csv", schema)))
+///     }
+/// }
+/// ```
+pub trait Responses {
+    /// Returns the responses `Self` generates.
+    fn responses(gen: &mut Generator) -> Map<String, Value>;
+}
+
+/// Returns the key for `status` in a responses object.
+fn key(status: Option<Status>) -> String {
+    status.map_or_else(|| "default".into(), |s| s.code.to_string())
+}
+
+/// Returns a responses object with a single response for `status`, or the
+/// default response if `status` is `None`, with `description`, or the status'
+/// reason phrase if there's none, and, optionally, content of a media type and
+/// schema.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::http::Status;
+/// use rocket::openapi;
+/// use rocket::serde::json::json;
+///
+/// let schema = json!({ "type": "string" });
+/// let responses = openapi::response(Some(Status::Ok), None, Some(("text/plain", schema)));
+/// assert_eq!(responses["200"]["description"], "OK");
+/// assert_eq!(responses["200"]["content"]["text/plain"]["schema"]["type"], "string");
+/// ```
+pub fn response(
+    status: Option<Status>,
+    description: Option<&str>,
+    content: Option<(&str, Value)>,
+) -> Map<String, Value> {
+    let description = description
+        .or_else(|| status.map(|s| s.reason_lossy()))
+        .unwrap_or("Response");
+
+    let mut response = json!({ "description": description });
+    if let Some((media_type, schema)) = content {
+        response["content"] = json!({ media_type: { "schema": schema } });
+    }
+
+    let mut responses = Map::new();
+    responses.insert(key(status), response);
+    responses
+}
+
+/// Merges every response in `responses` into a single response for `status`,
+/// or the default response if `status` is `None`. The merged response has
+/// `description`, if it's `Some`, and the content of all responses.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::http::Status;
+/// use rocket::openapi;
+/// use rocket::serde::json::json;
+///
+/// let schema = json!({ "type": "string" });
+/// let responses = openapi::response(Some(Status::Ok), None, Some(("text/plain", schema)));
+/// let responses = openapi::with_status(responses, Some(Status::Created), None);
+/// assert_eq!(responses["201"]["description"], "Created");
+/// assert_eq!(responses["201"]["content"]["text/plain"]["schema"]["type"], "string");
+/// ```
+pub fn with_status(
+    responses: Map<String, Value>,
+    status: Option<Status>,
+    description: Option<&str>,
+) -> Map<String, Value> {
+    let mut merged = response(status, description, None);
+    let response = merged.values_mut().next().expect("one response");
+    for (_, mut other) in responses {
+        if let Some(Value::Object(content)) = other.get_mut("content").map(Value::take) {
+            let merged_content = response.as_object_mut()
+                .expect("response object")
+                .entry("content")
+                .or_insert_with(|| json!({}));
+
+            for (media_type, value) in content {
+                merged_content.as_object_mut()
+                    .expect("content object")
+                    .entry(media_type)
+                    .or_insert(value);
+            }
+        }
+    }
+
+    merged
+}
+
+/// Replaces the media type of the content of every response in `responses`
+/// with `media_type`.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::http::Status;
+/// use rocket::openapi;
+/// use rocket::serde::json::json;
+///
+/// let schema = json!({ "type": "string" });
+/// let responses = openapi::response(Some(Status::Ok), None, Some(("text/plain", schema)));
+/// let responses = openapi::with_media_type(responses, "text/html");
+/// assert_eq!(responses["200"]["content"]["text/html"]["schema"]["type"], "string");
+/// ```
+pub fn with_media_type(mut responses: Map<String, Value>, media_type: &str) -> Map<String, Value> {
+    for response in responses.values_mut() {
+        let content = match response.get_mut("content").and_then(|c| c.as_object_mut()) {
+            Some(content) => content,
+            None => continue,
+        };
+
+        if let Some((_, value)) = content.iter().next() {
+            let value = value.clone();
+            content.clear();
+            content.insert(media_type.into(), value);
+        }
+    }
+
+    responses
+}
+
+/// Adds the responses in `b` whose status isn't in `a` to `a`.
+pub fn union(mut a: Map<String, Value>, b: Map<String, Value>) -> Map<String, Value> {
+    for (status, response) in b {
+        a.entry(status).or_insert(response);
+    }
+
+    a
+}
+
+/// Sets the description of every response in `responses` to `description`.
+pub fn describe(mut responses: Map<String, Value>, description: &str) -> Map<String, Value> {
+    for response in responses.values_mut() {
+        response["description"] = description.into();
+    }
+
+    responses
+}
+
+fn ok(media_type: &str, schema: Value) -> Map<String, Value> {
+    response(Some(Status::Ok), None, Some((media_type, schema)))
+}
+
+impl Responses for () {
+    fn responses(_: &mut Generator) -> Map<String, Value> {
+        response(Some(Status::Ok), None, None)
+    }
+}
+
+impl Responses for Status {
+    fn responses(_: &mut Generator) -> Map<String, Value> {
+        response(None, None, None)
+    }
+}
+
+impl Responses for Redirect {
+    fn responses(_: &mut Generator) -> Map<String, Value> {
+        let mut responses = Map::new();
+        responses.insert("3XX".into(), json!({ "description": "Redirection" }));
+        responses
+    }
+}
+
+impl Responses for crate::fs::NamedFile {
+    fn responses(gen: &mut Generator) -> Map<String, Value> {
+        <Vec<u8>>::responses(gen)
+    }
+}
+
+macro_rules! impl_responses {
+    ($($T:ty),* => $media_type:expr, $schema:tt) => ($(
+        impl Responses for $T {
+            fn responses(_: &mut Generator) -> Map<String, Value> {
+                ok($media_type, json!($schema))
+            }
+        }
+    )*)
+}
+
+impl_responses!(&str, String, Cow<'_, str> => "text/plain", { "type": "string" });
+impl_responses!(&[u8], Vec<u8>, std::fs::File, tokio::fs::File
+    => "application/octet-stream", { "type": "string", "format": "binary" });
+impl_responses!(std::io::Error => "text/plain", { "type": "string" });
+
+impl<T: Schema> Responses for crate::serde::json::Json<T> {
+    fn responses(gen: &mut Generator) -> Map<String, Value> {
+        ok("application/json", gen.schema::<T>())
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl<T: Schema> Responses for crate::serde::msgpack::MsgPack<T> {
+    fn responses(gen: &mut Generator) -> Map<String, Value> {
+        ok("application/msgpack", gen.schema::<T>())
+    }
+}
+
+impl<S> Responses for crate::response::stream::EventStream<S> {
+    fn responses(_: &mut Generator) -> Map<String, Value> {
+        ok("text/event-stream", json!({ "type": "string" }))
+    }
+}
+
+impl<S> Responses for crate::response::stream::TextStream<S> {
+    fn responses(gen: &mut Generator) -> Map<String, Value> {
+        String::responses(gen)
+    }
+}
+
+impl<S> Responses for crate::response::stream::ByteStream<S> {
+    fn responses(gen: &mut Generator) -> Map<String, Value> {
+        <Vec<u8>>::responses(gen)
+    }
+}
+
+macro_rules! impl_content_responses {
+    ($($T:ident: $media_type:expr),*) => ($(
+        impl<R: Responses> Responses for content::$T<R> {
+            fn responses(gen: &mut Generator) -> Map<String, Value> {
+                with_media_type(R::responses(gen), $media_type)
+            }
+        }
+    )*)
+}
+
+impl_content_responses! {
+    Json: "application/json",
+    Xml: "text/xml",
+    MsgPack: "application/msgpack",
+    Html: "text/html",
+    Plain: "text/plain",
+    Css: "text/css",
+    JavaScript: "application/javascript"
+}
+
+macro_rules! impl_status_responses {
+    ($($T:ident: $status:ident),*) => ($(
+        impl<R: Responses> Responses for status::$T<R> {
+            fn responses(gen: &mut Generator) -> Map<String, Value> {
+                with_status(R::responses(gen), Some(Status::$status), None)
+            }
+        }
+    )*)
+}
+
+impl_status_responses! {
+    Created: Created,
+    Accepted: Accepted,
+    BadRequest: BadRequest,
+    Unauthorized: Unauthorized,
+    Forbidden: Forbidden,
+    NotFound: NotFound,
+    Conflict: Conflict
+}
+
+impl Responses for status::NoContent {
+    fn responses(_: &mut Generator) -> Map<String, Value> {
+        response(Some(Status::NoContent), None, None)
+    }
+}
+
+impl<R: Responses> Responses for status::Custom<R> {
+    fn responses(gen: &mut Generator) -> Map<String, Value> {
+        with_status(R::responses(gen), None, None)
+    }
+}
+
+impl<R: Responses> Responses for (Status, R) {
+    fn responses(gen: &mut Generator) -> Map<String, Value> {
+        with_status(R::responses(gen), None, None)
+    }
+}
+
+impl<R: Responses> Responses for content::Custom<R> {
+    fn responses(gen: &mut Generator) -> Map<String, Value> {
+        R::responses(gen)
+    }
+}
+
+impl<R: Responses> Responses for (crate::http::ContentType, R) {
+    fn responses(gen: &mut Generator) -> Map<String, Value> {
+        R::responses(gen)
+    }
+}
+
+impl<R: Responses> Responses for Flash<R> {
+    fn responses(gen: &mut Generator) -> Map<String, Value> {
+        R::responses(gen)
+    }
+}
+
+impl<E> Responses for Debug<E> {
+    fn responses(_: &mut Generator) -> Map<String, Value> {
+        response(Some(Status::InternalServerError), None, None)
+    }
+}
+
+impl<R: Responses> Responses for Option<R> {
+    fn responses(gen: &mut Generator) -> Map<String, Value> {
+        union(R::responses(gen), response(Some(Status::NotFound), None, None))
+    }
+}
+
+impl<T: Responses, E: Responses> Responses for Result<T, E> {
+    fn responses(gen: &mut Generator) -> Map<String, Value> {
+        let ok = T::responses(gen);
+        union(ok, E::responses(gen))
+    }
+}
+
+impl<T: Responses, E: Responses> Responses for crate::Either<T, E> {
+    fn responses(gen: &mut Generator) -> Map<String, Value> {
+        let left = T::responses(gen);
+        union(left, E::responses(gen))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serde::json::Json;
+
+    #[test]
+    fn test_responses() {
+        let mut gen = Generator::new();
+        let responses = <Result<Option<Json<u8>>, status::Custom<String>>>::responses(&mut gen);
+        let statuses: Vec<_> = responses.keys().map(|k| k.as_str()).collect();
+        assert_eq!(statuses, &["200", "404", "default"]);
+        assert_eq!(responses["200"]["content"]["application/json"]["schema"]["type"], "integer");
+        assert_eq!(responses["default"]["content"]["text/plain"]["schema"]["type"], "string");
+
+        let responses = <status::Created<content::Html<&str>>>::responses(&mut gen);
+        assert_eq!(responses["201"]["content"]["text/html"]["schema"]["type"], "string");
+        assert!(responses.get("200").is_none());
+    }
+}
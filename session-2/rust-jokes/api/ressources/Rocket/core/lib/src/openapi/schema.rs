@@ -0,0 +1,344 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Map, Value};
+
+/// Collects the named schemas referenced while describing operations.
+///
+/// A `Generator` is passed to every [`Schema`], [`RequestBody`], and
+/// [`Responses`] implementation. Schemas of types with a
+/// [`name()`](Schema::name()) are generated once, stored as components of the
+/// document, and referenced by name; all others are inlined.
+///
+/// [`RequestBody`]: crate::openapi::RequestBody
+/// [`Responses`]: crate::openapi::Responses
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::openapi::{Generator, Schema};
+///
+/// #[derive(Schema)]
+/// struct User {
+///     name: String,
+///     age: Option<u8>,
+/// }
+///
+/// let mut gen = Generator::new();
+/// let schema = gen.schema::<Vec<User>>();
+/// assert_eq!(schema["items"]["$ref"], "#/components/schemas/User");
+/// assert_eq!(gen.schemas()["User"]["required"][0], "name");
+/// ```
+#[derive(Debug, Default)]
+pub struct Generator {
+    schemas: Map<String, Value>,
+}
+
+impl Generator {
+    /// Returns a `Generator` with no named schemas.
+    pub fn new() -> Self {
+        Generator::default()
+    }
+
+    /// Returns the schema for `T`: a reference to the named schema if `T` has
+    /// a name, generating it first if needed, or the schema itself otherwise.
+    pub fn schema<T: Schema + ?Sized>(&mut self) -> Value {
+        let name = match T::name() {
+            Some(name) => name,
+            None => return T::schema(self),
+        };
+
+        if !self.schemas.contains_key(&*name) {
+            // Insert a placeholder first so recursive types terminate.
+            self.schemas.insert(name.to_string(), Value::Null);
+            let schema = T::schema(self);
+            self.schemas.insert(name.to_string(), schema);
+        }
+
+        json!({ "$ref": format!("#/components/schemas/{}", name) })
+    }
+
+    /// Returns the schema `schema` refers to if it's a reference to a named
+    /// schema, and `schema` itself otherwise.
+    pub fn resolve<'a>(&'a self, schema: &'a Value) -> &'a Value {
+        schema.get("$ref")
+            .and_then(|r| r.as_str())
+            .and_then(|r| r.strip_prefix("#/components/schemas/"))
+            .and_then(|name| self.schemas.get(name))
+            .unwrap_or(schema)
+    }
+
+    /// Returns the named schemas generated so far.
+    pub fn schemas(&self) -> &Map<String, Value> {
+        &self.schemas
+    }
+
+    pub(crate) fn into_schemas(self) -> Map<String, Value> {
+        self.schemas
+    }
+}
+
+/// A type with a JSON schema.
+///
+/// Schemas describe path and query parameters, form fields, and JSON bodies.
+/// Implementations are provided for primitive types, strings, collections, and
+/// form wrappers. For structures and enums, derive the trait: fields and
+/// variants are named as `serde` would name them, honoring `rename`,
+/// `rename_all`, and `skip`, falling back to the names `FromForm` and
+/// `FromFormField` use. Fields of type `Option<T>` and those marked with
+/// `#[serde(default)]` are optional. Doc comments become descriptions.
+///
+/// ```rust
+/// use rocket::openapi::Schema;
+///
+/// /// A task to do.
+/// #[derive(Schema)]
+/// struct Task {
+///     /// What to do.
+///     description: String,
+///     #[field(name = "done")]
+///     completed: bool,
+///     priority: Option<Priority>,
+/// }
+///
+/// #[derive(Schema)]
+/// #[serde(rename_all = "lowercase")]
+/// enum Priority {
+///     Low,
+///     High,
+/// }
+/// ```
+///
+/// Derived schemas of types without type parameters are named after the type.
+///
+/// # Implementing
+///
+/// Implement [`schema()`](Schema::schema()) to return the schema, using the
+/// generator for the schemas of other types. Implement
+/// [`name()`](Schema::name()) to have the schema stored once and referenced.
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use rocket::openapi::{Generator, Schema};
+/// use rocket::serde::json::{json, Value};
+///
+/// struct Color(u8, u8, u8);
+///
+/// impl Schema for Color {
+///     fn name() -> Option<Cow<'static, str>> {
+///         Some("Color".into())
+///     }
+///
+///     fn schema(_: &mut Generator) -> Value {
+///         json!({ "type": "string", "pattern": "^#[0-9a-f]{6}$" })
+///     }
+/// }
+/// ```
+pub trait Schema {
+    /// The name to store the schema under, if any. **(default: `None`)**
+    fn name() -> Option<Cow<'static, str>> {
+        None
+    }
+
+    /// Whether a field or parameter of this type must be present. **(default:
+    /// `true`)**
+    fn required() -> bool {
+        true
+    }
+
+    /// Returns the schema for `Self`.
+    fn schema(gen: &mut Generator) -> Value;
+}
+
+macro_rules! impl_schema {
+    ($($T:ty),* => $schema:tt) => ($(
+        impl Schema for $T {
+            fn schema(_: &mut Generator) -> Value {
+                json!($schema)
+            }
+        }
+    )*)
+}
+
+impl_schema!(bool => { "type": "boolean" });
+impl_schema!(i8, i16, i32 => { "type": "integer", "format": "int32" });
+impl_schema!(i64, i128, isize => { "type": "integer", "format": "int64" });
+impl_schema!(u8, u16, u32 => { "type": "integer", "format": "int32", "minimum": 0 });
+impl_schema!(u64, u128, usize => { "type": "integer", "format": "int64", "minimum": 0 });
+impl_schema!(f32 => { "type": "number", "format": "float" });
+impl_schema!(f64 => { "type": "number", "format": "double" });
+impl_schema!(char => { "type": "string", "minLength": 1, "maxLength": 1 });
+impl_schema!(str, String, Path, PathBuf, crate::http::RawStr => { "type": "string" });
+impl_schema!(IpAddr => { "type": "string", "format": "ip" });
+impl_schema!(Ipv4Addr => { "type": "string", "format": "ipv4" });
+impl_schema!(Ipv6Addr => { "type": "string", "format": "ipv6" });
+impl_schema!(SocketAddr, SocketAddrV4, SocketAddrV6 => { "type": "string" });
+impl_schema!(crate::fs::TempFile<'_> => { "type": "string", "format": "binary" });
+impl_schema!(crate::http::uri::Segments<'_, crate::http::uri::fmt::Path> => { "type": "string" });
+
+#[cfg(feature = "uuid")]
+impl_schema!(crate::serde::uuid::Uuid => { "type": "string", "format": "uuid" });
+
+macro_rules! impl_delegating_schema {
+    (?Sized: $($T:ty),*) => ($(
+        impl<T: Schema + ?Sized> Schema for $T {
+            fn name() -> Option<Cow<'static, str>> { T::name() }
+            fn required() -> bool { T::required() }
+            fn schema(gen: &mut Generator) -> Value { T::schema(gen) }
+        }
+    )*);
+    ($($T:ty),*) => ($(
+        impl<T: Schema> Schema for $T {
+            fn name() -> Option<Cow<'static, str>> { T::name() }
+            fn required() -> bool { T::required() }
+            fn schema(gen: &mut Generator) -> Value { T::schema(gen) }
+        }
+    )*)
+}
+
+impl_delegating_schema!(?Sized: &T, &mut T, Box<T>, std::sync::Arc<T>, std::rc::Rc<T>);
+impl_delegating_schema!(crate::form::Strict<T>, crate::form::Lenient<T>);
+
+impl<T: Schema + ToOwned + ?Sized> Schema for Cow<'_, T> {
+    fn name() -> Option<Cow<'static, str>> { T::name() }
+    fn required() -> bool { T::required() }
+    fn schema(gen: &mut Generator) -> Value { T::schema(gen) }
+}
+
+impl<T: Schema> Schema for Option<T> {
+    fn required() -> bool {
+        false
+    }
+
+    fn schema(gen: &mut Generator) -> Value {
+        gen.schema::<T>()
+    }
+}
+
+impl<T: Schema, E> Schema for Result<T, E> {
+    fn schema(gen: &mut Generator) -> Value {
+        gen.schema::<T>()
+    }
+}
+
+macro_rules! impl_array_schema {
+    ($($T:ty => $unique:expr),*) => ($(
+        impl<T: Schema> Schema for $T {
+            fn schema(gen: &mut Generator) -> Value {
+                let mut schema = json!({ "type": "array", "items": gen.schema::<T>() });
+                if $unique {
+                    schema["uniqueItems"] = true.into();
+                }
+
+                schema
+            }
+        }
+    )*)
+}
+
+impl_array_schema!([T] => false, Vec<T> => false, HashSet<T> => true, BTreeSet<T> => true);
+
+macro_rules! impl_map_schema {
+    ($($T:ty),*) => ($(
+        impl<K, V: Schema> Schema for $T {
+            fn schema(gen: &mut Generator) -> Value {
+                json!({ "type": "object", "additionalProperties": gen.schema::<V>() })
+            }
+        }
+    )*)
+}
+
+impl_map_schema!(HashMap<K, V>, BTreeMap<K, V>);
+
+/// Returns `schema` with `description`, unless it's empty. References are
+/// wrapped in `allOf` since siblings of `$ref` are ignored.
+pub fn describe(schema: Value, description: &str) -> Value {
+    match schema {
+        _ if description.is_empty() => schema,
+        Value::Object(ref map) if map.contains_key("$ref") => {
+            json!({ "allOf": [schema], "description": description })
+        }
+        Value::Object(mut map) => {
+            map.insert("description".into(), description.into());
+            Value::Object(map)
+        }
+        _ => schema,
+    }
+}
+
+/// Returns an object schema with `properties`, each a `(name, schema,
+/// required, description)`.
+pub fn object(properties: Vec<(&str, Value, bool, &str)>) -> Value {
+    let mut props = Map::new();
+    let mut required = vec![];
+    for (name, schema, is_required, description) in properties {
+        props.insert(name.into(), describe(schema, description));
+        if is_required {
+            required.push(Value::from(name));
+        }
+    }
+
+    let mut schema = json!({ "type": "object", "properties": props });
+    if !required.is_empty() {
+        schema["required"] = required.into();
+    }
+
+    schema
+}
+
+/// Returns a string schema with the values `values`.
+pub fn strings(values: &[&str]) -> Value {
+    json!({ "type": "string", "enum": values })
+}
+
+/// Returns an array schema of `items.len()` items, each one of `items`.
+pub fn tuple(items: Vec<Value>) -> Value {
+    let len = items.len();
+    json!({ "type": "array", "items": { "oneOf": items }, "minItems": len, "maxItems": len })
+}
+
+/// Returns a schema matching exactly one of `schemas`.
+pub fn one_of(schemas: Vec<Value>) -> Value {
+    json!({ "oneOf": schemas })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Node;
+
+    impl Schema for Node {
+        fn name() -> Option<Cow<'static, str>> {
+            Some("Node".into())
+        }
+
+        fn schema(gen: &mut Generator) -> Value {
+            json!({ "type": "object", "properties": { "children": gen.schema::<Vec<Node>>() } })
+        }
+    }
+
+    #[test]
+    fn test_named_schemas() {
+        let mut gen = Generator::new();
+        let schema = gen.schema::<Option<Box<Node>>>();
+        assert_eq!(schema, json!({ "$ref": "#/components/schemas/Node" }));
+        assert_eq!(gen.resolve(&schema)["properties"]["children"]["items"], schema);
+        assert_eq!(gen.schemas().len(), 1);
+    }
+
+    #[test]
+    fn test_primitive_schemas() {
+        let mut gen = Generator::new();
+        assert_eq!(gen.schema::<u16>()["minimum"], 0);
+        assert_eq!(gen.schema::<&str>(), json!({ "type": "string" }));
+        assert_eq!(gen.schema::<HashSet<bool>>()["uniqueItems"], true);
+        let map = gen.schema::<HashMap<String, f64>>();
+        assert_eq!(map["additionalProperties"]["format"], "double");
+        assert!(!<Option<i32>>::required());
+        assert!(gen.schemas().is_empty());
+    }
+}
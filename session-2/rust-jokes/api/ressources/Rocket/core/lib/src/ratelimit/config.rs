@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The rate limiting configuration: an optional global [`Limit`] and
+/// per-route limits keyed by route name.
+///
+/// Limits are fixed once the [`RateLimit`](crate::ratelimit::RateLimit)
+/// fairing ignites, which fails if any `limit` or `period` is zero. Without a
+/// global or route limit, no request is limited.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::ratelimit::{Config, Limit};
+///
+/// let figment = rocket::Config::figment()
+///     .merge(("ratelimit.global", Limit::new(600, 60)))
+///     .merge(("ratelimit.routes.login", Limit::new(5, 60)));
+///
+/// let config: Config = figment.extract_inner("ratelimit").unwrap();
+/// assert_eq!(config.global, Some(Limit::new(600, 60)));
+/// assert_eq!(config.routes["login"], Limit::new(5, 60));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Config {
+    /// The limit applied to all requests, if any. **(default: `None`)**
+    #[serde(default)]
+    pub global: Option<Limit>,
+    /// Limits applied to requests handled by routes with a [`Quota`] guard,
+    /// keyed by route name. **(default: empty)**
+    ///
+    /// [`Quota`]: crate::ratelimit::Quota
+    #[serde(default)]
+    pub routes: HashMap<String, Limit>,
+}
+
+/// A limit of `limit` requests per `period` seconds.
+///
+/// Up to `limit` requests are allowed in a burst. Thereafter, a request is
+/// allowed every `period / limit` seconds.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Limit {
+    /// The maximum number of requests in a period. Must be nonzero.
+    pub limit: u32,
+    /// The length of the period in seconds. Must be nonzero.
+    pub period: u64,
+}
+
+impl Limit {
+    /// Returns a limit of `limit` requests per `period` seconds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::ratelimit::Limit;
+    ///
+    /// // 100 requests per minute.
+    /// let limit = Limit::new(100, 60);
+    /// ```
+    pub fn new(limit: u32, period: u64) -> Limit {
+        Limit { limit, period }
+    }
+
+    /// The number of tokens a bucket gains per second.
+    pub(crate) fn rate(&self) -> f64 {
+        self.limit as f64 / self.period as f64
+    }
+}
+
+impl Config {
+    /// Returns an error message if any limit is invalid.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        let limits = self.global.iter().map(|limit| ("global", limit))
+            .chain(self.routes.iter().map(|(name, limit)| (name.as_str(), limit)));
+
+        for (name, limit) in limits {
+            if limit.limit == 0 || limit.period == 0 {
+                return Err(format!("`{}` limit and period must be nonzero", name));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use crate::{Rocket, Build, Request, Response, Data};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::http::Status;
+use crate::ratelimit::Config;
+use crate::ratelimit::limiter::{Limiter, KeyFn};
+
+/// A [`Fairing`] that limits the rate of requests per client.
+///
+/// See the [module docs](crate::ratelimit) for usage, configuration, and
+/// behavior details.
+///
+/// # Example
+///
+/// Limit requests per client IP, as configured in `Rocket.toml`:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::ratelimit::RateLimit;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(RateLimit::new())
+/// }
+/// ```
+///
+/// Limit requests per API key instead, not limiting requests without one:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::ratelimit::RateLimit;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let rate_limit = RateLimit::new()
+///         .key(|req| req.headers().get_one("X-Api-Key").map(String::from));
+///
+///     rocket::build().attach(rate_limit)
+/// }
+/// ```
+pub struct RateLimit {
+    key: KeyFn,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        RateLimit::new()
+    }
+}
+
+impl RateLimit {
+    /// Returns a `RateLimit` fairing that keys clients by their IP address
+    /// as reported by [`Request::client_ip()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::ratelimit::RateLimit;
+    ///
+    /// let rate_limit = RateLimit::new();
+    /// ```
+    pub fn new() -> Self {
+        RateLimit { key: Arc::new(|req| req.client_ip().map(|ip| ip.to_string())) }
+    }
+
+    /// Keys clients by the value `f` returns for each request. Requests for
+    /// which `f` returns `None` are not limited.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::ratelimit::RateLimit;
+    ///
+    /// // Limit per user, sharing one bucket among anonymous users.
+    /// let rate_limit = RateLimit::new().key(|req| {
+    ///     let user = req.headers().get_one("X-User").unwrap_or("anonymous");
+    ///     Some(user.to_string())
+    /// });
+    /// ```
+    pub fn key<F>(mut self, f: F) -> Self
+        where F: Fn(&Request<'_>) -> Option<String> + Send + Sync + 'static
+    {
+        self.key = Arc::new(f);
+        self
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for RateLimit {
+    fn info(&self) -> Info {
+        Info {
+            name: "Rate Limit",
+            kind: Kind::Ignite | Kind::Request | Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let config = match crate::config::extract_or_default::<Config>(&rocket, "ratelimit") {
+            Some(config) => config,
+            None => return Err(rocket),
+        };
+
+        if let Err(e) = config.validate() {
+            error!("invalid rate limit configuration: {}", e);
+            return Err(rocket);
+        }
+
+        Ok(rocket.manage(Limiter::new(config, self.key.clone())))
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data<'_>) {
+        let usage = req.rocket().state::<Limiter>().and_then(|limiter| limiter.global(req));
+        if usage.map_or(false, |usage| usage.retry_after.is_some()) {
+            req.fail(Status::TooManyRequests);
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        if let Some(usage) = Limiter::recorded(req) {
+            for header in usage.headers() {
+                res.set_header(header);
+            }
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::Request;
+use crate::http::Header;
+use crate::ratelimit::{Config, Limit};
+
+/// Computes the key identifying the client that made a request, if any.
+pub(crate) type KeyFn = Arc<dyn Fn(&Request<'_>) -> Option<String> + Send + Sync>;
+
+/// The number of acquisitions between sweeps for full, and thus redundant,
+/// buckets.
+const SWEEP_INTERVAL: usize = 1024;
+
+/// The state of a bucket after a token was requested from it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct Usage {
+    /// The bucket's capacity.
+    pub limit: u32,
+    /// The number of whole tokens left in the bucket.
+    pub remaining: u32,
+    /// The time until the bucket is full.
+    pub reset: Duration,
+    /// The time until a token is available if none was, i.e, the request was
+    /// denied.
+    pub retry_after: Option<Duration>,
+}
+
+impl Usage {
+    /// Returns `true` if `self` is more restrictive than `other`.
+    fn is_stricter_than(&self, other: &Usage) -> bool {
+        match (self.retry_after, other.retry_after) {
+            (Some(a), Some(b)) => a > b,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => (self.remaining, other.reset) < (other.remaining, self.reset),
+        }
+    }
+
+    /// The `RateLimit-*` headers and, if the request was denied, the
+    /// `Retry-After` header describing `self`.
+    pub fn headers(&self) -> Vec<Header<'static>> {
+        let mut headers = vec![
+            Header::new("RateLimit-Limit", self.limit.to_string()),
+            Header::new("RateLimit-Remaining", self.remaining.to_string()),
+            Header::new("RateLimit-Reset", ceil_secs(self.reset).to_string()),
+        ];
+
+        if let Some(retry_after) = self.retry_after {
+            headers.push(Header::new("Retry-After", ceil_secs(retry_after).max(1).to_string()));
+        }
+
+        headers
+    }
+}
+
+fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_secs() + (duration.subsec_nanos() > 0) as u64
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    full_at: Instant,
+}
+
+#[derive(Default)]
+struct Buckets {
+    /// Buckets by route name, or `None` for the global bucket, and key.
+    map: HashMap<(Option<String>, String), Bucket>,
+    /// Acquisitions since the last sweep.
+    acquisitions: usize,
+}
+
+/// The managed state of the [`RateLimit`](crate::ratelimit::RateLimit)
+/// fairing: the configuration and every client's buckets.
+pub(crate) struct Limiter {
+    config: Config,
+    key: KeyFn,
+    buckets: Mutex<Buckets>,
+}
+
+/// The most restrictive usage of any bucket a request consumed from.
+#[derive(Default)]
+struct Recorded(Mutex<Option<Usage>>);
+
+impl Limiter {
+    pub fn new(config: Config, key: KeyFn) -> Self {
+        Limiter { config, key, buckets: Mutex::new(Buckets::default()) }
+    }
+
+    /// Consumes a token from the global bucket for the client that made
+    /// `req`. Returns `None` if there's no global limit or the client has no
+    /// key.
+    pub fn global(&self, req: &Request<'_>) -> Option<Usage> {
+        let limit = self.config.global.as_ref()?;
+        self.acquire(None, limit, req)
+    }
+
+    /// Consumes a token from the bucket for route `name` for the client that
+    /// made `req`. Returns `None` if there's no limit for `name` or the
+    /// client has no key.
+    pub fn route(&self, name: &str, req: &Request<'_>) -> Option<Usage> {
+        let limit = self.config.routes.get(name)?;
+        self.acquire(Some(name), limit, req)
+    }
+
+    /// Returns the most restrictive usage recorded for `req`, if any.
+    pub fn recorded(req: &Request<'_>) -> Option<Usage> {
+        *req.local_cache(Recorded::default).0.lock()
+    }
+
+    fn acquire(&self, route: Option<&str>, limit: &Limit, req: &Request<'_>) -> Option<Usage> {
+        let key = (self.key)(req)?;
+        let usage = self.acquire_at(route, limit, key, Instant::now());
+
+        let mut recorded = req.local_cache(Recorded::default).0.lock();
+        let stricter = match *recorded {
+            Some(ref recorded) => usage.is_stricter_than(recorded),
+            None => true,
+        };
+
+        if stricter {
+            *recorded = Some(usage);
+        }
+
+        Some(usage)
+    }
+
+    fn acquire_at(&self, route: Option<&str>, limit: &Limit, key: String, now: Instant) -> Usage {
+        let (capacity, rate) = (limit.limit as f64, limit.rate());
+        let mut buckets = self.buckets.lock();
+
+        buckets.acquisitions += 1;
+        if buckets.acquisitions >= SWEEP_INTERVAL {
+            buckets.acquisitions = 0;
+            buckets.map.retain(|_, bucket| bucket.full_at > now);
+        }
+
+        let bucket = buckets.map.entry((route.map(String::from), key))
+            .or_insert(Bucket { tokens: capacity, updated: now, full_at: now });
+
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity);
+        bucket.updated = now;
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+
+        let reset = Duration::from_secs_f64((capacity - bucket.tokens) / rate);
+        bucket.full_at = now + reset;
+        Usage {
+            limit: limit.limit,
+            remaining: bucket.tokens as u32,
+            reset,
+            retry_after: match allowed {
+                true => None,
+                false => Some(Duration::from_secs_f64((1.0 - bucket.tokens) / rate)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(global: Limit) -> Limiter {
+        let config = Config { global: Some(global), ..Config::default() };
+        Limiter::new(config, Arc::new(|_| None))
+    }
+
+    #[test]
+    fn test_bucket_refills() {
+        let limit = Limit::new(2, 10);
+        let limiter = limiter(limit);
+        let start = Instant::now();
+        let acquire = |secs: f64| {
+            let now = start + Duration::from_secs_f64(secs);
+            limiter.acquire_at(None, &limit, "client".into(), now)
+        };
+
+        let usage = acquire(0.0);
+        assert_eq!((usage.remaining, usage.retry_after), (1, None));
+        assert_eq!(usage.reset, Duration::from_secs(5));
+
+        let usage = acquire(0.0);
+        assert_eq!((usage.remaining, usage.retry_after), (0, None));
+        assert_eq!(usage.reset, Duration::from_secs(10));
+
+        let usage = acquire(1.0);
+        assert_eq!(usage.remaining, 0);
+        assert_eq!(usage.retry_after, Some(Duration::from_secs(4)));
+
+        let usage = acquire(5.0);
+        assert_eq!((usage.remaining, usage.retry_after), (0, None));
+
+        let usage = acquire(60.0);
+        assert_eq!((usage.remaining, usage.retry_after), (1, None));
+    }
+
+    #[test]
+    fn test_buckets_are_per_key_and_scope() {
+        let limit = Limit::new(1, 60);
+        let limiter = limiter(limit);
+        let now = Instant::now();
+
+        assert!(limiter.acquire_at(None, &limit, "a".into(), now).retry_after.is_none());
+        assert!(limiter.acquire_at(None, &limit, "a".into(), now).retry_after.is_some());
+        assert!(limiter.acquire_at(None, &limit, "b".into(), now).retry_after.is_none());
+        assert!(limiter.acquire_at(Some("r"), &limit, "a".into(), now).retry_after.is_none());
+    }
+
+    #[test]
+    fn test_headers() {
+        let usage = Usage {
+            limit: 10,
+            remaining: 0,
+            reset: Duration::from_millis(2500),
+            retry_after: Some(Duration::from_millis(100)),
+        };
+
+        let headers = usage.headers();
+        let value = |name| headers.iter().find(|h| h.name() == name).unwrap().value();
+        assert_eq!(value("RateLimit-Limit"), "10");
+        assert_eq!(value("RateLimit-Remaining"), "0");
+        assert_eq!(value("RateLimit-Reset"), "3");
+        assert_eq!(value("Retry-After"), "1");
+    }
+}
@@ -0,0 +1,96 @@
+//! Token-bucket rate limiting keyed by client IP or a custom key.
+//!
+//! The [`RateLimit`] fairing limits how often clients can make requests. Each
+//! client, identified by a _key_, has a _bucket_ of tokens for each configured
+//! [`Limit`]. A request consumes one token from every bucket that applies to
+//! it; when a bucket is empty, the request fails with a `429 Too Many
+//! Requests` error, which is handled by the corresponding catcher like any
+//! other error. Buckets refill continuously over time.
+//!
+//! Two kinds of buckets are supported:
+//!
+//!   * **global**: applies to every request, whether or not it's routed.
+//!   * **per-route**: applies to requests handled by a route with a given
+//!     name, i.e, the route function's name, as long as the route has a
+//!     [`Quota`] request guard.
+//!
+//! # Enabling
+//!
+//! This module is only available when the `ratelimit` feature is enabled:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket = { version = "0.5.0-rc.1", features = ["ratelimit"] }
+//! ```
+//!
+//! # Usage
+//!
+//! Attach the fairing and add a [`Quota`] guard to every route that should be
+//! subject to a per-route limit:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::ratelimit::{RateLimit, Quota};
+//!
+//! #[post("/login")]
+//! fn login(_quota: Quota) -> &'static str {
+//!     "welcome!"
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![login])
+//!         .attach(RateLimit::new())
+//! }
+//! ```
+//!
+//! # Configuration
+//!
+//! Limits are read from the `ratelimit` configuration parameter. A [`Limit`]
+//! of `limit` requests per `period` seconds can be set globally and for
+//! individual routes, by name:
+//!
+//! ```toml
+//! [default.ratelimit.global]
+//! limit = 600
+//! period = 60
+//!
+//! [default.ratelimit.routes.login]
+//! limit = 5
+//! period = 60
+//! ```
+//!
+//! A client can make up to `limit` requests in a burst, after which it can make
+//! one request every `period / limit` seconds. Without any configured limits,
+//! the fairing has no effect.
+//!
+//! # Keys
+//!
+//! By default, clients are keyed by their IP address as reported by
+//! [`Request::client_ip()`], which prefers the `X-Real-IP` header. Requests
+//! without a known client IP are not limited. To key clients differently, by
+//! an API token, for instance, use [`RateLimit::key()`].
+//!
+//! # Headers
+//!
+//! Every response to a request subject to a limit includes the following
+//! headers describing the most restrictive applicable bucket:
+//!
+//!   * `RateLimit-Limit`: the bucket's `limit`.
+//!   * `RateLimit-Remaining`: the number of requests left in the bucket.
+//!   * `RateLimit-Reset`: the number of seconds until the bucket is full.
+//!
+//! A `429` response additionally includes a `Retry-After` header with the
+//! number of seconds until the next request will be allowed.
+//!
+//! [`Request::client_ip()`]: crate::Request::client_ip()
+
+mod config;
+mod limiter;
+mod fairing;
+mod quota;
+
+pub use self::config::{Config, Limit};
+pub use self::fairing::RateLimit;
+pub use self::quota::Quota;
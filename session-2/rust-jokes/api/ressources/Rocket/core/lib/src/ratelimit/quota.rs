@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use crate::{Request, Rocket, Ignite, Sentinel};
+use crate::request::{self, FromRequest};
+use crate::outcome::Outcome;
+use crate::http::Status;
+use crate::ratelimit::limiter::{Limiter, Usage};
+
+/// A request guard that consumes a token from the requesting client's bucket
+/// for the current route.
+///
+/// The bucket's [`Limit`](crate::ratelimit::Limit) is the one configured for
+/// the route's name under `ratelimit.routes`. If the bucket is empty, the
+/// guard fails with a status of `429 Too Many Requests` and the time until a
+/// token is available. If no limit is configured for the route, or the client
+/// has no key, the guard always succeeds, and the quota is unlimited.
+///
+/// Using a `Quota` requires attaching the
+/// [`RateLimit`](crate::ratelimit::RateLimit) fairing; launch is aborted
+/// otherwise.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::ratelimit::Quota;
+///
+/// // Limited according to `ratelimit.routes.search`.
+/// #[get("/search?<q>")]
+/// fn search(q: &str, quota: Quota) -> String {
+///     match quota.remaining() {
+///         Some(n) => format!("results for {} ({} searches left)", q, n),
+///         None => format!("results for {}", q),
+///     }
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Quota {
+    usage: Option<Usage>,
+}
+
+impl Quota {
+    /// Returns the route's limit, or `None` if the quota is unlimited.
+    pub fn limit(&self) -> Option<u32> {
+        self.usage.map(|usage| usage.limit)
+    }
+
+    /// Returns the number of requests the client can make to the route before
+    /// being limited, or `None` if the quota is unlimited.
+    pub fn remaining(&self) -> Option<u32> {
+        self.usage.map(|usage| usage.remaining)
+    }
+
+    /// Returns the time until the client's bucket is full, or `None` if the
+    /// quota is unlimited.
+    pub fn reset(&self) -> Option<Duration> {
+        self.usage.map(|usage| usage.reset)
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Quota {
+    type Error = Duration;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let limiter = match req.rocket().state::<Limiter>() {
+            Some(limiter) => limiter,
+            None => {
+                error_!("`Quota` guard used without attaching the `RateLimit` fairing.");
+                return Outcome::Failure((Status::InternalServerError, Duration::from_secs(0)));
+            }
+        };
+
+        let name = req.route().and_then(|route| route.name.as_deref());
+        let usage = name.and_then(|name| limiter.route(name, req));
+        match usage.and_then(|usage| usage.retry_after) {
+            Some(retry_after) => Outcome::Failure((Status::TooManyRequests, retry_after)),
+            None => Outcome::Success(Quota { usage }),
+        }
+    }
+}
+
+impl Sentinel for Quota {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        if rocket.state::<Limiter>().is_none() {
+            error!("launching with a `Quota` guard without the `RateLimit` fairing.");
+            info_!("Using `Quota` requires attaching `RateLimit`.");
+            return true;
+        }
+
+        false
+    }
+}
@@ -151,6 +151,13 @@ impl<S, E> IntoOutcome<S, (Status, E), ()> for Result<S, E> {
 ///
 ///     _This implementation always returns successfully._
 ///
+///   * **&[`RequestId`]**
+///
+///     Extracts the id of the incoming request, read from its request id
+///     header or generated.
+///
+///     _This implementation always returns successfully._
+///
 ///   * **ContentType**
 ///
 ///     Extracts the [`ContentType`] from the incoming request. If the request
@@ -185,6 +192,7 @@ impl<S, E> IntoOutcome<S, (Status, E), ()> for Result<S, E> {
 ///     forwarded.
 ///
 /// [`Config`]: crate::config::Config
+/// [`RequestId`]: crate::request::RequestId
 ///
 /// # Example
 ///
@@ -3,13 +3,21 @@
 mod request;
 mod from_param;
 mod from_request;
+mod request_id;
+
+#[cfg(any(feature = "metrics", feature = "access-log"))]
+mod started;
 
 #[cfg(test)]
 mod tests;
 
 pub use self::request::Request;
+pub(crate) use self::request::ConnectionMeta;
 pub use self::from_request::{FromRequest, Outcome};
 pub use self::from_param::{FromParam, FromSegments};
+pub use self::request_id::RequestId;
+#[cfg(any(feature = "metrics", feature = "access-log"))]
+pub(crate) use self::started::Started;
 
 #[doc(inline)]
 pub use crate::response::flash::FlashMessage;
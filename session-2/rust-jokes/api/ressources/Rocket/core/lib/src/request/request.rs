@@ -14,7 +14,8 @@ use crate::form::{self, ValueField, FromForm};
 
 use crate::{Rocket, Route, Orbit};
 use crate::http::{hyper, uri::{Origin, Segments, fmt::Path}, uncased::UncasedStr};
-use crate::http::{Method, Header, HeaderMap};
+use crate::http::{Method, Header, HeaderMap, Status};
+use crate::http::private::{Connection, RawCertificate};
 use crate::http::{ContentType, Accept, MediaType, CookieJar, Cookie};
 use crate::data::Limits;
 
@@ -28,13 +29,32 @@ pub struct Request<'r> {
     method: Atomic<Method>,
     uri: Origin<'r>,
     headers: HeaderMap<'r>,
-    remote: Option<SocketAddr>,
+    pub(crate) connection: ConnectionMeta,
     pub(crate) state: RequestState<'r>,
 }
 
+/// Information about the connection a request was received on.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ConnectionMeta {
+    pub remote: Option<SocketAddr>,
+    #[cfg_attr(not(feature = "mtls"), allow(dead_code))]
+    pub client_certificates: Option<Arc<Vec<RawCertificate>>>,
+}
+
+impl<C: Connection> From<&C> for ConnectionMeta {
+    fn from(conn: &C) -> Self {
+        ConnectionMeta {
+            remote: conn.remote_addr(),
+            client_certificates: conn.peer_certificates().map(Arc::new),
+        }
+    }
+}
+
 pub(crate) struct RequestState<'r> {
     pub rocket: &'r Rocket<Orbit>,
     pub route: Atomic<Option<&'r Route>>,
+    pub responder: Atomic<Option<&'r Route>>,
+    pub failure: Atomic<Option<Status>>,
     pub cookies: CookieJar<'r>,
     pub accept: Storage<Option<Accept>>,
     pub content_type: Storage<Option<ContentType>>,
@@ -47,7 +67,7 @@ impl Request<'_> {
             method: Atomic::new(self.method()),
             uri: self.uri.clone(),
             headers: self.headers.clone(),
-            remote: self.remote.clone(),
+            connection: self.connection.clone(),
             state: self.state.clone(),
         }
     }
@@ -58,6 +78,8 @@ impl RequestState<'_> {
         RequestState {
             rocket: self.rocket,
             route: Atomic::new(self.route.load(Ordering::Acquire)),
+            responder: Atomic::new(self.responder.load(Ordering::Acquire)),
+            failure: Atomic::new(self.failure.load(Ordering::Acquire)),
             cookies: self.cookies.clone(),
             accept: self.accept.clone(),
             content_type: self.content_type.clone(),
@@ -78,10 +100,12 @@ impl<'r> Request<'r> {
             uri,
             method: Atomic::new(method),
             headers: HeaderMap::new(),
-            remote: None,
+            connection: ConnectionMeta::default(),
             state: RequestState {
                 rocket,
                 route: Atomic::new(None),
+                responder: Atomic::new(None),
+                failure: Atomic::new(None),
                 cookies: CookieJar::new(rocket.config()),
                 accept: Storage::new(),
                 content_type: Storage::new(),
@@ -170,7 +194,8 @@ impl<'r> Request<'r> {
 
     /// Returns the raw address of the remote connection that initiated this
     /// request if the address is known. If the address is not known, `None` is
-    /// returned.
+    /// returned. This is always the case for requests received over a Unix
+    /// domain socket as such peers have no IP socket address.
     ///
     /// Because it is common for proxies to forward connections for clients, the
     /// remote address may contain information about the proxy instead of the
@@ -197,7 +222,7 @@ impl<'r> Request<'r> {
     /// ```
     #[inline(always)]
     pub fn remote(&self) -> Option<SocketAddr> {
-        self.remote
+        self.connection.remote
     }
 
     /// Sets the remote address of `self` to `address`.
@@ -220,7 +245,7 @@ impl<'r> Request<'r> {
     /// ```
     #[inline(always)]
     pub fn set_remote(&mut self, address: SocketAddr) {
-        self.remote = Some(address);
+        self.connection.remote = Some(address);
     }
 
     /// Returns the IP address in the "X-Real-IP" header of the request if such
@@ -818,6 +843,36 @@ impl<'r> Request<'r> {
         self.state.route.store(Some(route), Ordering::Release)
     }
 
+    /// Records that `route`'s handler responded to `self`.
+    #[inline(always)]
+    pub(crate) fn set_responder(&self, route: &'r Route) {
+        self.state.responder.store(Some(route), Ordering::Release)
+    }
+
+    /// The route whose handler responded to `self`, if any. Unlike
+    /// [`Request::route()`], this is `None` if every matching route forwarded
+    /// or failed, and so a catcher or Rocket itself responded.
+    #[inline(always)]
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    pub(crate) fn responder(&self) -> Option<&'r Route> {
+        self.state.responder.load(Ordering::Acquire)
+    }
+
+    /// Fails `self` with `status` before it's routed: instead of calling any
+    /// route handlers, Rocket responds with the catcher for `status`. Used by
+    /// request fairings, which can't otherwise respond to a request.
+    #[inline(always)]
+    #[cfg_attr(not(feature = "ratelimit"), allow(dead_code))]
+    pub(crate) fn fail(&self, status: Status) {
+        self.state.failure.store(Some(status), Ordering::Release)
+    }
+
+    /// The status `self` was failed with via [`Request::fail()`], if any.
+    #[inline(always)]
+    pub(crate) fn failure(&self) -> Option<Status> {
+        self.state.failure.load(Ordering::Acquire)
+    }
+
     /// Set the method of `self`, even when `self` is a shared reference. Used
     /// during routing to override methods for re-routing.
     #[inline(always)]
@@ -825,6 +880,12 @@ impl<'r> Request<'r> {
         self.method.store(method, Ordering::Release)
     }
 
+    /// Returns the certificate chain presented by the client, if any.
+    #[cfg(feature = "mtls")]
+    pub(crate) fn client_certificates(&self) -> Option<&[RawCertificate]> {
+        self.connection.client_certificates.as_deref().map(|certs| &**certs)
+    }
+
     pub(crate) fn cookies_mut(&mut self) -> &mut CookieJar<'r> {
         &mut self.state.cookies
     }
@@ -833,7 +894,7 @@ impl<'r> Request<'r> {
     pub(crate) fn from_hyp(
         rocket: &'r Rocket<Orbit>,
         hyper: &'r hyper::RequestParts,
-        addr: SocketAddr
+        connection: ConnectionMeta,
     ) -> Result<Request<'r>, Error<'r>> {
         // Ensure that the method is known. TODO: Allow made-up methods?
         let method = Method::from_hyp(&hyper.method)
@@ -848,7 +909,7 @@ impl<'r> Request<'r> {
 
         // Construct the request object.
         let mut request = Request::new(rocket, method, uri);
-        request.set_remote(addr);
+        request.connection = connection;
 
         // Set the request cookies, if they exist.
         for header in hyper.headers.get_all("Cookie") {
@@ -0,0 +1,127 @@
+use std::fmt;
+
+use crate::Request;
+use crate::http::Header;
+use crate::log::Span;
+use crate::outcome::Outcome;
+use crate::request::{self, FromRequest};
+
+/// The maximum length of a request id accepted from a client.
+const MAX_LEN: usize = 200;
+
+/// A request guard identifying the request across services.
+///
+/// Every request is assigned an id. If the request carries a header named by
+/// [`Config::request_id_header`](crate::Config::request_id_header),
+/// `X-Request-Id` by default, with a value of at most 200 visible ASCII
+/// characters, that value is used. Otherwise, Rocket generates a random
+/// alphanumeric id.
+///
+/// The id is echoed on the response in the same header, unless the response
+/// already has one, and is attached to every record logged while the request
+/// is handled as the `id` of the `request` when the log format is
+/// [`json`](crate::config::LogFormat::Json). To trace a request further,
+/// forward the id on outbound calls with [`RequestId::header()`]. Code without
+/// access to the request retrieves the id with [`RequestId::current()`].
+///
+/// _This guard always returns successfully._
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::RequestId;
+///
+/// #[get("/")]
+/// fn index(id: &RequestId) -> String {
+///     format!("handling request {}", id)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId {
+    header: String,
+    id: String,
+}
+
+impl RequestId {
+    /// Resolves the id of `req` from its request id header, generating one if
+    /// the header is missing or invalid.
+    pub(crate) fn resolve(req: &Request<'_>) -> RequestId {
+        let header = &req.rocket().config().request_id_header;
+        let id = req.headers().get_one(header)
+            .filter(|id| !id.is_empty() && id.len() <= MAX_LEN)
+            .filter(|id| id.bytes().all(|b| b.is_ascii_graphic()))
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| crate::ext::random_token(16));
+
+        RequestId { header: header.clone(), id }
+    }
+
+    /// Returns the id of the request being handled by the current task, if
+    /// any. Tasks spawned by a handler are _not_ handling the request.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::RequestId;
+    ///
+    /// async fn call_inventory_service() {
+    ///     if let Some(id) = RequestId::current() {
+    ///         // forward `id` in the request to the other service
+    ///     }
+    /// }
+    /// ```
+    pub fn current() -> Option<String> {
+        Span::with(|span| span.id()).flatten()
+    }
+
+    /// Returns the id as a string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::RequestId;
+    ///
+    /// fn tag(id: &RequestId, message: &str) -> String {
+    ///     format!("[{}] {}", id.as_str(), message)
+    /// }
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the request id header carrying this id, to be forwarded on
+    /// outbound calls.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::RequestId;
+    ///
+    /// #[get("/")]
+    /// fn index(id: &RequestId) -> String {
+    ///     // With the default configuration, this is `X-Request-Id: <id>`.
+    ///     let header = id.header();
+    ///     format!("{}: {}", header.name(), header.value())
+    /// }
+    /// ```
+    pub fn header(&self) -> Header<'static> {
+        Header::new(self.header.clone(), self.id.clone())
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.id.fmt(f)
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for &'r RequestId {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        Outcome::Success(req.local_cache(|| RequestId::resolve(req)))
+    }
+}
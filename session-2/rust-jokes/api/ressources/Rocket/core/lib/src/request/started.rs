@@ -0,0 +1,21 @@
+use std::time::Instant;
+
+use crate::Request;
+use crate::http::Method;
+
+/// When a request started and with which method, before any rewriting of the
+/// method by Rocket, such as for `HEAD` requests handled by `GET` routes.
+/// Shared by the fairings that time requests so they agree on both.
+pub(crate) struct Started(Option<(Instant, Method)>);
+
+impl Started {
+    /// Records that `req` started now, unless its start is already recorded.
+    pub fn record(req: &Request<'_>) {
+        req.local_cache(|| Started(Some((Instant::now(), req.method()))));
+    }
+
+    /// Returns when `req` started and with which method, if it's recorded.
+    pub fn of(req: &Request<'_>) -> Option<(Instant, Method)> {
+        req.local_cache(|| Started(None)).0
+    }
+}
@@ -2,6 +2,7 @@ use std::net::{Ipv4Addr, SocketAddrV4};
 use std::collections::HashMap;
 
 use crate::Request;
+use crate::request::ConnectionMeta;
 use crate::local::blocking::Client;
 use crate::http::hyper;
 
@@ -19,7 +20,8 @@ macro_rules! assert_headers {
         let client = Client::debug_with(vec![]).unwrap();
         let addr = SocketAddrV4::new(Ipv4Addr::LOCALHOST, 8000).into();
         let hyper = req.into_parts().0;
-        let req = Request::from_hyp(client.rocket(), &hyper, addr).unwrap();
+        let conn = ConnectionMeta { remote: Some(addr), ..Default::default() };
+        let req = Request::from_hyp(client.rocket(), &hyper, conn).unwrap();
 
         // Dispatch the request and check that the headers match.
         let actual_headers = req.headers();
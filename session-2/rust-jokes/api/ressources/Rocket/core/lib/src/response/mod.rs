@@ -30,6 +30,8 @@ pub mod stream;
 pub use rocket_codegen::Responder;
 
 pub use self::response::{Response, Builder};
+#[cfg(feature = "websocket")]
+pub(crate) use self::response::Io;
 pub use self::body::Body;
 pub use self::responder::Responder;
 pub use self::redirect::Redirect;
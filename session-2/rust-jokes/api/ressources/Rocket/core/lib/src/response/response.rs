@@ -1,7 +1,8 @@
-use std::{fmt, str};
+use std::{fmt, io, str};
 use std::borrow::Cow;
 
-use tokio::io::{AsyncRead, AsyncSeek};
+use futures::future::BoxFuture;
+use tokio::io::{AsyncRead, AsyncSeek, AsyncWrite};
 
 use crate::http::{Header, HeaderMap, Status, ContentType, Cookie};
 use crate::response::Body;
@@ -404,6 +405,15 @@ impl<'r> Builder<'r> {
     }
 }
 
+/// The I/O stream of a connection that was upgraded away from HTTP.
+pub(crate) trait Io: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> Io for T {}
+
+/// Handles the I/O of a connection once a `101 Switching Protocols` response
+/// carrying it has been written out.
+pub(crate) type Upgrade = Box<dyn FnOnce(Box<dyn Io>) -> BoxFuture<'static, io::Result<()>> + Send>;
+
 /// A response, as returned by types implementing
 /// [`Responder`](crate::response::Responder).
 ///
@@ -413,6 +423,7 @@ pub struct Response<'r> {
     status: Option<Status>,
     headers: HeaderMap<'r>,
     body: Body<'r>,
+    upgrade: Option<Upgrade>,
 }
 
 impl<'r> Response<'r> {
@@ -752,6 +763,23 @@ impl<'r> Response<'r> {
         self.body.strip();
     }
 
+    /// Sets the handler of the connection that `self`, a `101 Switching
+    /// Protocols` response, upgrades.
+    #[inline(always)]
+    #[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+    pub(crate) fn set_upgrade(&mut self, upgrade: Upgrade) {
+        self.upgrade = Some(upgrade);
+    }
+
+    /// Takes the connection's upgrade handler, if there is one and `self`'s
+    /// status is still `101 Switching Protocols`.
+    pub(crate) fn take_upgrade(&mut self) -> Option<Upgrade> {
+        match self.status() == Status::SwitchingProtocols {
+            true => self.upgrade.take(),
+            false => None,
+        }
+    }
+
     /// Sets the body of `self` to be the fixed-sized `body` with size
     /// `size`, which may be `None`. If `size` is `None`, the body's size will
     /// be computing with calls to `seek` just before being written out in a
@@ -877,6 +905,10 @@ impl<'r> Response<'r> {
             self.body = other.body;
         }
 
+        if other.upgrade.is_some() {
+            self.upgrade = other.upgrade;
+        }
+
         for (name, values) in other.headers.into_iter_raw() {
             self.headers.replace_all(name.into_cow(), values);
         }
@@ -922,6 +954,10 @@ impl<'r> Response<'r> {
             self.body = other.body;
         }
 
+        if self.upgrade.is_none() {
+            self.upgrade = other.upgrade;
+        }
+
         for (name, mut values) in other.headers.into_iter_raw() {
             self.headers.add_all(name.into_cow(), &mut values);
         }
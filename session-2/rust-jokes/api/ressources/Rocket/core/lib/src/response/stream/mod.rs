@@ -135,6 +135,7 @@ mod text;
 mod one;
 mod sse;
 mod raw_sse;
+mod replay;
 
 pub(crate) use self::raw_sse::*;
 
@@ -142,7 +143,8 @@ pub use self::one::One;
 pub use self::text::TextStream;
 pub use self::bytes::ByteStream;
 pub use self::reader::ReaderStream;
-pub use self::sse::{Event, EventStream};
+pub use self::sse::{Event, EventStream, LastEventId};
+pub use self::replay::Replay;
 
 crate::export! {
     /// Retrofitted support for [`Stream`]s with `yield`, `for await` syntax.
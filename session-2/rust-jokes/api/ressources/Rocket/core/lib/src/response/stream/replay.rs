@@ -0,0 +1,252 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use futures::stream::{Stream, StreamExt};
+use parking_lot::Mutex;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::response::stream::{Event, EventStream, LastEventId};
+
+/// A bounded buffer of broadcast values that subscribers can resume from.
+///
+/// A `Replay` assigns every value [sent](Replay::send()) to it a sequential id
+/// and broadcasts it to every subscriber. It also keeps the last `capacity`
+/// values so that a client reconnecting with the id of the last value it
+/// received, usually via a [`LastEventId`], is first sent the values it
+/// missed. Values older than the buffer's capacity are lost.
+///
+/// Clones of a `Replay` share the same buffer. A `Replay` is typically placed
+/// in managed state and shared by the handlers that send values and those that
+/// stream them.
+///
+/// # Example
+///
+/// A chat where reconnecting clients receive the messages they missed:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::State;
+/// use rocket::response::stream::{Event, EventStream, LastEventId, Replay};
+///
+/// #[post("/message", data = "<message>")]
+/// fn post(message: String, replay: &State<Replay>) {
+///     replay.send(Event::data(message));
+/// }
+///
+/// #[get("/events")]
+/// fn events(last: Option<LastEventId<'_>>, replay: &State<Replay>) -> EventStream![] {
+///     replay.events(last)
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .manage(Replay::<Event>::new(128))
+///         .mount("/", routes![post, events])
+/// }
+/// ```
+pub struct Replay<T = Event> {
+    buffer: Arc<Mutex<Buffer<T>>>,
+    tx: broadcast::Sender<(u64, T)>,
+}
+
+struct Buffer<T> {
+    values: VecDeque<(u64, T)>,
+    capacity: usize,
+    next_id: u64,
+}
+
+impl<T: Clone> Buffer<T> {
+    /// Returns the buffered values with an id of at least `id`.
+    fn since(&self, id: u64) -> Vec<(u64, T)> {
+        self.values.iter().filter(|(i, _)| *i >= id).cloned().collect()
+    }
+}
+
+impl<T: Clone + Send + 'static> Replay<T> {
+    /// Creates a `Replay` that keeps the last `capacity` values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::stream::Replay;
+    ///
+    /// let replay = Replay::<String>::new(64);
+    /// ```
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "replay capacity must be non-zero");
+        let buffer = Buffer { values: VecDeque::with_capacity(capacity), capacity, next_id: 0 };
+        let (tx, _) = broadcast::channel(capacity);
+        Replay { buffer: Arc::new(Mutex::new(buffer)), tx }
+    }
+
+    /// Buffers `value` and broadcasts it to every subscriber, returning the id
+    /// assigned to it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::stream::Replay;
+    ///
+    /// let replay = Replay::new(64);
+    /// assert_eq!(replay.send("hello"), 0);
+    /// assert_eq!(replay.send("world"), 1);
+    /// ```
+    pub fn send(&self, value: T) -> u64 {
+        let mut buffer = self.buffer.lock();
+        let id = buffer.next_id;
+        buffer.next_id += 1;
+        if buffer.values.len() == buffer.capacity {
+            buffer.values.pop_front();
+        }
+
+        buffer.values.push_back((id, value.clone()));
+
+        // Sending fails if there are no subscribers. That's okay.
+        let _ = self.tx.send((id, value));
+        id
+    }
+
+    /// Returns an infinite stream of values, paired with their ids, sent after
+    /// the value with the id in `last`. If `last` is `None` or isn't the id of a
+    /// sent value, the stream starts with the next value sent.
+    ///
+    /// A subscriber that falls behind resumes from the buffer rather than
+    /// skipping values, as long as they are still buffered.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # rocket::async_test(async {
+    /// use rocket::futures::StreamExt;
+    /// use rocket::response::stream::Replay;
+    ///
+    /// let replay = Replay::new(64);
+    /// replay.send("hello");
+    ///
+    /// let mut values = replay.subscribe(None);
+    /// replay.send("world");
+    /// assert_eq!(values.next().await, Some((1, "world")));
+    /// # });
+    /// ```
+    pub fn subscribe(
+        &self,
+        last: Option<LastEventId<'_>>
+    ) -> impl Stream<Item = (u64, T)> + Send + Unpin + 'static {
+        // Subscribe while holding the lock so no value is missed or repeated.
+        let buffer = self.buffer.clone();
+        let (mut next, missed, mut rx) = {
+            let locked = buffer.lock();
+            let last = last.and_then(|id| id.as_str().parse::<u64>().ok())
+                .filter(|&id| id < locked.next_id);
+
+            let next = last.map_or(locked.next_id, |id| id + 1);
+            (next, locked.since(next), self.tx.subscribe())
+        };
+
+        Box::pin(async_stream::stream! {
+            let mut missed = missed;
+            loop {
+                for (id, value) in missed.drain(..) {
+                    next = id + 1;
+                    yield (id, value);
+                }
+
+                match rx.recv().await {
+                    Ok((id, value)) if id >= next => {
+                        next = id + 1;
+                        yield (id, value);
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(_)) => missed = buffer.lock().since(next),
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+impl Replay<Event> {
+    /// Returns an [`struct@EventStream`] of the events sent after the event
+    /// with the id in `last`, as in [`Replay::subscribe()`], each with its id
+    /// set to the one assigned by the `Replay`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::get;
+    /// use rocket::State;
+    /// use rocket::response::stream::{Event, EventStream, LastEventId, Replay};
+    ///
+    /// #[get("/events")]
+    /// fn events(last: Option<LastEventId<'_>>, replay: &State<Replay>) -> EventStream![] {
+    ///     replay.events(last)
+    /// }
+    /// ```
+    pub fn events(
+        &self,
+        last: Option<LastEventId<'_>>
+    ) -> EventStream<impl Stream<Item = Event> + Send + 'static> {
+        EventStream::from(self.subscribe(last).map(|(id, event)| event.id(id.to_string())))
+    }
+}
+
+impl<T> Clone for Replay<T> {
+    fn clone(&self) -> Self {
+        Replay { buffer: self.buffer.clone(), tx: self.tx.clone() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream::StreamExt;
+
+    use super::Replay;
+    use crate::response::stream::LastEventId;
+
+    fn values(replay: &Replay<u8>, last: Option<&str>, n: usize) -> Vec<(u64, u8)> {
+        let values = replay.subscribe(last.map(LastEventId)).take(n).collect();
+        crate::async_test(values)
+    }
+
+    #[test]
+    fn replays_missed_values() {
+        let replay = Replay::new(3);
+        for i in 0..5 {
+            replay.send(i);
+        }
+
+        assert_eq!(values(&replay, Some("2"), 2), vec![(3, 3), (4, 4)]);
+        assert_eq!(values(&replay, Some("0"), 3), vec![(2, 2), (3, 3), (4, 4)]);
+    }
+
+    #[test]
+    fn unknown_ids_start_at_next_value() {
+        let replay = Replay::new(3);
+        replay.send(0);
+
+        for last in &[None, Some("1"), Some("100"), Some("x")] {
+            let values = replay.subscribe(last.map(LastEventId));
+            replay.send(7);
+            let values: Vec<_> = crate::async_test(values.take(1).collect());
+            assert_eq!(values.len(), 1);
+            assert_eq!(values[0].1, 7);
+        }
+    }
+
+    #[test]
+    fn lagging_subscribers_resume_from_buffer() {
+        let replay = Replay::new(4);
+        let values = replay.subscribe(None);
+        for i in 0..6 {
+            replay.send(i);
+        }
+
+        let values: Vec<_> = crate::async_test(values.take(4).collect());
+        assert_eq!(values, vec![(2, 2), (3, 3), (4, 4), (5, 5)]);
+    }
+}
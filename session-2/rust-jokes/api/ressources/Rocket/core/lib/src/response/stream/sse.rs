@@ -6,8 +6,9 @@ use tokio::time::Duration;
 use futures::stream::{self, Stream, StreamExt};
 use futures::future::ready;
 
-use crate::request::Request;
+use crate::request::{self as request, Request, FromRequest};
 use crate::response::{self, Response, Responder, stream::{ReaderStream, RawLinedEvent}};
+use crate::outcome::Outcome;
 use crate::http::ContentType;
 
 /// A Server-Sent `Event` (SSE) in a Server-Sent [`struct@EventStream`].
@@ -75,8 +76,11 @@ use crate::http::ContentType;
 ///     Sets the event id to associate all subsequent fields with. This value
 ///     cannot be retrieved directly via most clients, including JavaScript
 ///     `EventSource`. Instead, it is sent by the implementation on reconnection
-///     via the `Last-Event-ID` header. An `id` can be attached to other fields
-///     via the [`Event::id()`] builder method.
+///     via the `Last-Event-ID` header, which the [`LastEventId`] guard reads.
+///     An `id` can be attached to other fields via the [`Event::id()`] builder
+///     method. A [`Replay`] assigns ids and resumes streams from them.
+///
+///     [`Replay`]: crate::response::stream::Replay
 ///
 ///   * `event`
 ///
@@ -327,6 +331,68 @@ impl Event {
     }
 }
 
+/// A request guard for the `Last-Event-ID` header sent by reconnecting clients.
+///
+/// When a connection to an [`struct@EventStream`] is lost, clients like
+/// JavaScript's `EventSource` reconnect and send the [id](Event::id()) of the
+/// last event they received in a `Last-Event-ID` header. The guard forwards if
+/// the header is missing, so it's typically used as an
+/// `Option<LastEventId<'_>>`.
+///
+/// To resume a stream with the events the client missed, use a [`Replay`].
+///
+/// [`Replay`]: crate::response::stream::Replay
+///
+/// # Example
+///
+/// ```rust
+/// # use rocket::get;
+/// use rocket::response::stream::{Event, EventStream, LastEventId};
+///
+/// #[get("/count")]
+/// fn count(last: Option<LastEventId<'_>>) -> EventStream![] {
+///     let start = last.and_then(|id| id.as_str().parse().ok()).map_or(0, |n: u32| n + 1);
+///     EventStream! {
+///         for i in start..10 {
+///             yield Event::data(i.to_string()).id(i.to_string());
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LastEventId<'r>(pub(crate) &'r str);
+
+impl<'r> LastEventId<'r> {
+    /// Returns the id of the last event the client received.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::get;
+    /// use rocket::response::stream::LastEventId;
+    ///
+    /// #[get("/last")]
+    /// fn last(id: LastEventId<'_>) -> String {
+    ///     format!("resuming after {}", id.as_str())
+    /// }
+    /// ```
+    pub fn as_str(&self) -> &'r str {
+        self.0
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for LastEventId<'r> {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match req.headers().get_one("Last-Event-ID") {
+            Some(id) => Outcome::Success(LastEventId(id)),
+            None => Outcome::Forward(()),
+        }
+    }
+}
+
 /// A potentially infinite stream of Server-Sent [`Event`]s (SSE).
 ///
 /// An `EventStream` can be constructed from any [`Stream`] of items of type
@@ -7,6 +7,7 @@ use either::Either;
 use figment::{Figment, Provider};
 
 use crate::{Catcher, Config, Route, Shutdown, sentinel, shield::Shield};
+use crate::config::Address;
 use crate::router::Router;
 use crate::trip_wire::TripWire;
 use crate::fairing::{Fairing, Fairings};
@@ -620,7 +621,11 @@ impl Rocket<Ignite> {
             rkt.fairings.handle_liftoff(&rkt).await;
 
             let proto = rkt.config.tls_enabled().then(|| "https").unwrap_or("http");
-            let addr = format!("{}://{}:{}", proto, rkt.config.address, rkt.config.port);
+            let addr = match rkt.config.address {
+                Address::Ip(ip) => format!("{}://{}:{}", proto, ip, rkt.config.port),
+                Address::Unix(ref path) => format!("{}://unix:{}", proto, path.display()),
+            };
+
             launch_info!("{}{} {}",
                 Paint::emoji("🚀 "),
                 Paint::default("Rocket has launched from").bold(),
@@ -8,6 +8,11 @@ use crate::http::{uri, Method, MediaType};
 use crate::route::{Handler, RouteUri, BoxFuture};
 use crate::sentinel::Sentry;
 
+/// The rank of [filters](Route::filter()): lower than that of any application
+/// route, so that filters see every request first.
+#[cfg_attr(not(feature = "cors"), allow(dead_code))]
+pub(crate) const FILTER_RANK: isize = isize::MIN;
+
 /// A request handling route.
 ///
 /// A route consists of exactly the information in its fields. While a `Route`
@@ -188,8 +193,16 @@ pub struct Route {
     pub rank: isize,
     /// The media type this route matches against, if any.
     pub format: Option<MediaType>,
+    /// The timeout, in seconds, for this route's handler, if any. Overrides
+    /// [`Config::timeout`](crate::Config::timeout). Disabled when `Some(0)`.
+    pub timeout: Option<u32>,
     /// The discovered sentinels.
     pub(crate) sentinels: Vec<Sentry>,
+    /// Whether this route is a filter. See [`Route::filter()`].
+    pub(crate) filter: bool,
+    /// Describes the route's OpenAPI operation, if it's described.
+    #[cfg_attr(not(feature = "openapi"), allow(dead_code))]
+    pub(crate) operation: Option<Describe>,
 }
 
 impl Route {
@@ -250,12 +263,26 @@ impl Route {
         Route {
             name: None,
             format: None,
+            timeout: None,
             sentinels: Vec::new(),
+            filter: false,
+            operation: None,
             handler: Box::new(handler),
             rank, uri, method,
         }
     }
 
+    /// Creates a filter: a catch-all route for `method`, mounted by a fairing,
+    /// that only fails or forwards requests. Filters are ranked
+    /// [`FILTER_RANK`], ahead of any application route, and don't count toward
+    /// the methods a URI supports. Two filters for the same method collide.
+    #[cfg_attr(not(feature = "cors"), allow(dead_code))]
+    pub(crate) fn filter<H: Handler>(method: Method, handler: H) -> Route {
+        let mut route = Route::ranked(FILTER_RANK, method, "/<_..>", handler);
+        route.filter = true;
+        route
+    }
+
     /// Maps the `base` of this route using `mapper`, returning a new `Route`
     /// with the returned base.
     ///
@@ -311,6 +338,10 @@ impl fmt::Display for Route {
             write!(f, " {}", Paint::yellow(format))?;
         }
 
+        if let Some(timeout) = self.timeout {
+            write!(f, " {}", Paint::magenta(format!("{}s", timeout)))?;
+        }
+
         Ok(())
     }
 }
@@ -323,6 +354,7 @@ impl fmt::Debug for Route {
             .field("uri", &self.uri)
             .field("rank", &self.rank)
             .field("format", &self.format)
+            .field("timeout", &self.timeout)
             .finish()
     }
 }
@@ -342,11 +374,25 @@ pub struct StaticInfo {
     pub handler: for<'r> fn(&'r crate::Request<'_>, crate::Data<'r>) -> BoxFuture<'r>,
     /// The route's rank, if any.
     pub rank: Option<isize>,
+    /// The route's handler timeout in seconds, if any.
+    pub timeout: Option<u32>,
     /// Route-derived sentinels, if any.
     /// This isn't `&'static [SentryInfo]` because `type_name()` isn't `const`.
     pub sentinels: Vec<Sentry>,
+    /// The route's OpenAPI operation describer, if it's described.
+    pub operation: Option<Describe>,
 }
 
+/// Describes a route's OpenAPI operation. Generated by `#[openapi]`.
+#[doc(hidden)]
+#[cfg(feature = "openapi")]
+pub type Describe = fn(&mut crate::openapi::Generator) -> crate::openapi::Operation;
+
+/// A describer that can't be called: the `openapi` feature is disabled.
+#[doc(hidden)]
+#[cfg(not(feature = "openapi"))]
+pub type Describe = fn(std::convert::Infallible);
+
 #[doc(hidden)]
 impl From<StaticInfo> for Route {
     fn from(info: StaticInfo) -> Route {
@@ -359,7 +405,10 @@ impl From<StaticInfo> for Route {
             handler: Box::new(info.handler),
             rank: info.rank.unwrap_or_else(|| uri.default_rank()),
             format: info.format,
+            timeout: info.timeout,
             sentinels: info.sentinels.into_iter().collect(),
+            filter: false,
+            operation: info.operation,
             uri,
         }
     }
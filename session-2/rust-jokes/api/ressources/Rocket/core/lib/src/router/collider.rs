@@ -102,6 +102,12 @@ impl Route {
             && queries_match(self, req)
             && formats_match(self, req)
     }
+
+    /// Determines if this route's path and query match those of `req`,
+    /// regardless of the method and format of either.
+    pub(crate) fn matches_uri(&self, req: &Request<'_>) -> bool {
+        paths_match(self, req) && queries_match(self, req)
+    }
 }
 
 fn paths_match(route: &Route, req: &Request<'_>) -> bool {
@@ -1,14 +1,18 @@
 use std::collections::HashMap;
 
+use state::Storage;
+
 use crate::request::Request;
 use crate::http::{Method, Status};
 
 use crate::{Route, Catcher};
-use crate::router::Collide;
+use crate::router::{Collide, Trie};
 
 #[derive(Debug, Default)]
 pub(crate) struct Router {
     routes: HashMap<Method, Vec<Route>>,
+    /// Built from `routes` on first use, once all routes have been added.
+    tries: Storage<HashMap<Method, Trie>>,
     catchers: HashMap<Option<u16>, Vec<Catcher>>,
 }
 
@@ -27,6 +31,7 @@ impl Router {
         let routes = self.routes.entry(route.method).or_default();
         routes.push(route);
         routes.sort_by_key(|r| r.rank);
+        self.tries = Storage::new();
     }
 
     pub fn add_catcher(&mut self, catcher: Catcher) {
@@ -45,14 +50,60 @@ impl Router {
         self.catchers.values().flat_map(|v| v.iter())
     }
 
+    /// Returns the path tries for the routes of each method, building them
+    /// from the rank-sorted routes if they haven't been yet.
+    fn tries(&self) -> &HashMap<Method, Trie> {
+        self.tries.get_or_set(|| {
+            self.routes.iter()
+                .map(|(method, routes)| {
+                    let mut trie = Trie::default();
+                    for (i, route) in routes.iter().enumerate() {
+                        trie.insert(&route.uri.metadata.path_segs, i);
+                    }
+
+                    (*method, trie)
+                })
+                .collect()
+        })
+    }
+
     pub fn route<'r, 'a: 'r>(
         &'a self,
         req: &'r Request<'r>
     ) -> impl Iterator<Item = &'a Route> + 'r {
-        // Note that routes are presorted by ascending rank on each `add`.
-        self.routes.get(&req.method())
-            .into_iter()
-            .flat_map(move |routes| routes.iter().filter(move |r| r.matches(req)))
+        // The trie only considers paths, so candidates must still be checked
+        // against the full request. Since routes are presorted by ascending
+        // rank on each `add`, so are the candidate indices.
+        let routes = self.routes.get(&req.method()).map_or(&[][..], |r| &r[..]);
+        let candidates = self.tries().get(&req.method())
+            .map(|trie| trie.matches(&req.uri().path().segments()))
+            .unwrap_or_default();
+
+        candidates.into_iter()
+            .map(move |i| &routes[i])
+            .filter(move |r| r.matches(req))
+    }
+
+    /// Returns the methods, in no particular order, of the routes other than
+    /// filters whose URI matches that of `req`. `HEAD` is included when `GET`
+    /// is since Rocket answers the former with the latter.
+    pub fn methods(&self, req: &Request<'_>) -> Vec<Method> {
+        let segments = req.uri().path().segments();
+        let mut methods: Vec<Method> = self.tries().iter()
+            .filter(|(method, trie)| {
+                let routes = &self.routes[method];
+                trie.matches(&segments).into_iter()
+                    .map(|i| &routes[i])
+                    .any(|r| !r.filter && r.matches_uri(req))
+            })
+            .map(|(method, _)| *method)
+            .collect();
+
+        if methods.contains(&Method::Get) && !methods.contains(&Method::Head) {
+            methods.push(Method::Head);
+        }
+
+        methods
     }
 
     // For many catchers, using aho-corasick or similar should be much faster.
@@ -97,6 +148,8 @@ impl Router {
             return Err(Collisions { routes, catchers })
         }
 
+        // Build the tries now instead of on the first request.
+        self.tries();
         Ok(())
     }
 }
@@ -440,6 +493,16 @@ mod test {
         })
     }
 
+    #[test]
+    fn test_routing_after_adding_routes() {
+        let mut router = router_with_ranked_routes(&[(2, "/a/<b>")]);
+        assert_eq!(route(&router, Get, "/a/b").unwrap().rank, 2);
+
+        router.add_route(Route::ranked(1, Get, "/a/b", dummy_handler));
+        let ranks: Vec<_> = matches(&router, Get, "/a/b").iter().map(|r| r.rank).collect();
+        assert_eq!(ranks, vec![1, 2]);
+    }
+
     #[test]
     fn test_ranked_routing() {
         assert_ranked_routing!(
@@ -503,6 +566,27 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_mixed_segment_routing() {
+        assert_ranked_routing!(
+            to: "/a/b/c",
+            with: [(3, "/<a..>"), (1, "/a/<b>/c"), (2, "/a/b/<c..>"), (0, "/a/b/c")],
+            expect: (0, "/a/b/c"), (1, "/a/<b>/c"), (2, "/a/b/<c..>"), (3, "/<a..>")
+        );
+
+        assert_ranked_routing!(
+            to: "/a/b",
+            with: [(2, "/a/<b>/<c..>"), (1, "/<a>/b"), (0, "/a/b/c"), (1, "/a/<b>/c")],
+            expect: (1, "/<a>/b"), (2, "/a/<b>/<c..>")
+        );
+
+        assert_ranked_routing!(
+            to: "/x/y",
+            with: [(1, "/a/<b>"), (-1, "/<a>/y"), (1, "/<a..>"), (0, "/x/<y>")],
+            expect: (-1, "/<a>/y"), (0, "/x/<y>"), (1, "/<a..>")
+        );
+    }
+
     macro_rules! assert_default_ranked_routing {
         (to: $to:expr, with: $routes:expr, expect: $($want:expr),+) => ({
             let router = router_with_routes(&$routes);
@@ -548,6 +632,31 @@ mod test {
         );
     }
 
+    fn methods(router: &Router, uri: &str) -> Vec<Method> {
+        let client = Client::debug_with(vec![]).expect("client");
+        let request = client.req(Options, Origin::parse(uri).unwrap());
+        let mut methods = router.methods(&request);
+        methods.sort_by_key(|m| m.as_str());
+        methods
+    }
+
+    #[test]
+    fn test_methods() {
+        let mut router = Router::new();
+        router.add_route(Route::new(Get, "/hello", dummy_handler));
+        router.add_route(Route::new(Post, "/hello/<name>", dummy_handler));
+        router.add_route(Route::new(Put, "/<a>/bob", dummy_handler));
+        router.add_route(Route::new(Delete, "/hello?secret", dummy_handler));
+
+        router.add_route(Route::filter(Patch, dummy_handler));
+
+        assert_eq!(methods(&router, "/hello"), vec![Get, Head]);
+        assert_eq!(methods(&router, "/hello?secret"), vec![Delete, Get, Head]);
+        assert_eq!(methods(&router, "/hello/bob"), vec![Post, Put]);
+        assert_eq!(methods(&router, "/hi/bob"), vec![Put]);
+        assert!(methods(&router, "/").is_empty());
+    }
+
     fn router_with_catchers(catchers: &[(Option<u16>, &str)]) -> Router {
         let mut router = Router::new();
         for (code, base) in catchers {
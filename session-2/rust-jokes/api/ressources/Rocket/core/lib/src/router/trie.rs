@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use crate::route::Segment;
+use crate::http::uri::{Segments, fmt::Path};
+
+/// A trie over route path segments used to quickly narrow down the routes that
+/// can possibly match a request's path.
+///
+/// Each node corresponds to a sequence of route path segments. A route is
+/// stored, by its index, in exactly one node: the node for its full path or,
+/// if its last segment is a trailing `<param..>`, the node for the path that
+/// precedes the trailing segment. Static segments are keyed by their value
+/// while all dynamic `<param>` segments share a single child.
+#[derive(Debug, Default)]
+pub(crate) struct Trie {
+    /// Routes whose path ends exactly at this node.
+    exact: Vec<usize>,
+    /// Routes with a trailing `<param..>` segment following this node.
+    trailing: Vec<usize>,
+    /// Children reached through a static segment.
+    statics: HashMap<String, Trie>,
+    /// Child reached through a dynamic `<param>` segment.
+    dynamic: Option<Box<Trie>>,
+}
+
+impl Trie {
+    /// Inserts the route at `index` with path segments `segments`.
+    pub fn insert(&mut self, segments: &[Segment], index: usize) {
+        match segments.split_first() {
+            None => self.exact.push(index),
+            Some((seg, _)) if seg.trailing => self.trailing.push(index),
+            Some((seg, rest)) if seg.dynamic => {
+                self.dynamic.get_or_insert_with(Default::default).insert(rest, index)
+            }
+            Some((seg, rest)) => {
+                self.statics.entry(seg.value.clone()).or_default().insert(rest, index)
+            }
+        }
+    }
+
+    /// Returns the indices, in ascending order, of all of the routes whose
+    /// path matches the path `segments`.
+    pub fn matches(&self, segments: &Segments<'_, Path>) -> Vec<usize> {
+        let mut indices = vec![];
+        self.collect(segments, 0, &mut indices);
+        indices.sort_unstable();
+        indices
+    }
+
+    fn collect(&self, segments: &Segments<'_, Path>, depth: usize, out: &mut Vec<usize>) {
+        // A trailing segment matches any number of remaining segments, even 0.
+        out.extend_from_slice(&self.trailing);
+
+        match segments.get(depth) {
+            None => out.extend_from_slice(&self.exact),
+            Some(segment) => {
+                if let Some(child) = self.statics.get(segment) {
+                    child.collect(segments, depth + 1, out);
+                }
+
+                if let Some(child) = self.dynamic.as_ref() {
+                    child.collect(segments, depth + 1, out);
+                }
+            }
+        }
+    }
+}
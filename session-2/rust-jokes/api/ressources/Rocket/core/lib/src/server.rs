@@ -1,5 +1,6 @@
 use std::io;
 use std::sync::Arc;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use yansi::Paint;
@@ -8,13 +9,16 @@ use futures::stream::StreamExt;
 use futures::future::{self, FutureExt, Future, TryFutureExt, BoxFuture};
 
 use crate::{Rocket, Orbit, Request, Response, Data, route};
+use crate::request::{ConnectionMeta, RequestId};
 use crate::form::Form;
 use crate::outcome::Outcome;
 use crate::error::{Error, ErrorKind};
+use crate::config::Address;
+use crate::log::Span;
 use crate::ext::{AsyncReadExt, CancellableListener, CancellableIo};
 
 use crate::http::{Method, Status, Header, hyper};
-use crate::http::private::{Listener, Connection, Incoming};
+use crate::http::private::{Listener, Incoming};
 use crate::http::uri::Origin;
 use crate::http::private::bind_tcp;
 
@@ -63,7 +67,7 @@ async fn handle<Fut, T, F>(name: Option<&str>, run: F) -> Option<T>
 // `HyperResponse` type, this function does the actual response processing.
 async fn hyper_service_fn(
     rocket: Arc<Rocket<Orbit>>,
-    addr: std::net::SocketAddr,
+    conn: ConnectionMeta,
     hyp_req: hyper::Request<hyper::Body>,
 ) -> Result<hyper::Response<hyper::Body>, io::Error> {
     // This future must return a hyper::Response, but the response body might
@@ -71,10 +75,13 @@ async fn hyper_service_fn(
     // sends the response metadata (and a body channel) prior.
     let (tx, rx) = oneshot::channel();
 
-    tokio::spawn(async move {
+    // Attach the request's context to everything logged while handling it.
+    let span = Span::new(hyp_req.method(), hyp_req.uri());
+    tokio::spawn(span.scope(async move {
         // Convert a Hyper request into a Rocket request.
-        let (h_parts, mut h_body) = hyp_req.into_parts();
-        let mut req = match Request::from_hyp(&rocket, &h_parts, addr) {
+        let (mut h_parts, mut h_body) = hyp_req.into_parts();
+        let on_upgrade = h_parts.extensions.remove::<hyper::OnUpgrade>();
+        let mut req = match Request::from_hyp(&rocket, &h_parts, conn) {
             Ok(req) => req,
             Err(e) => {
                 error!("Bad incoming request: {}", e);
@@ -84,7 +91,7 @@ async fn hyper_service_fn(
                 // handler) instead of doing this.
                 let dummy = Request::new(&rocket, Method::Get, Origin::ROOT);
                 let r = rocket.handle_error(Status::BadRequest, &dummy).await;
-                return rocket.send_response(r, tx).await;
+                return rocket.send_response(r, tx, None).await;
             }
         };
 
@@ -94,8 +101,8 @@ async fn hyper_service_fn(
         // Dispatch the request to get a response, then write that response out.
         let token = rocket.preprocess_request(&mut req, &mut data).await;
         let r = rocket.dispatch(token, &mut req, data).await;
-        rocket.send_response(r, tx).await;
-    });
+        rocket.send_response(r, tx, on_upgrade).await;
+    }));
 
     // Receive the response written to `tx` by the task above.
     rx.await.map_err(|e| io::Error::new(io::ErrorKind::Other, e))
@@ -108,19 +115,24 @@ impl Rocket<Orbit> {
         &self,
         response: Response<'_>,
         tx: oneshot::Sender<hyper::Response<hyper::Body>>,
+        on_upgrade: Option<hyper::OnUpgrade>,
     ) {
-        match self.make_response(response, tx).await {
+        match self.make_response(response, tx, on_upgrade).await {
             Ok(()) => info_!("{}", Paint::green("Response succeeded.")),
             Err(e) => error_!("Failed to write response: {}.", e),
         }
     }
 
     /// Attempts to create a hyper response from `response` and send it to `tx`.
+    /// If `response` upgrades the connection, the response is sent without a
+    /// body and the connection is handed to its upgrade handler via
+    /// `on_upgrade`.
     #[inline]
     async fn make_response(
         &self,
         mut response: Response<'_>,
         tx: oneshot::Sender<hyper::Response<hyper::Body>>,
+        on_upgrade: Option<hyper::OnUpgrade>,
     ) -> io::Result<()> {
         let mut hyp_res = hyper::Response::builder()
             .status(response.status().code);
@@ -141,6 +153,18 @@ impl Rocket<Orbit> {
             })
         };
 
+        if let Some(upgrade) = response.take_upgrade() {
+            let on_upgrade = on_upgrade.ok_or_else(|| {
+                let msg = "connection cannot be upgraded";
+                io::Error::new(io::ErrorKind::Other, msg)
+            })?;
+
+            send_response(hyp_res, hyper::Body::empty())?;
+            let io = on_upgrade.await.map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            info_!("Connection upgraded.");
+            return upgrade(Box::new(io)).await;
+        }
+
         let body = response.body_mut();
         if let Some(n) = body.size().await {
             hyp_res = hyp_res.header(hyper::header::CONTENT_LENGTH, n);
@@ -187,6 +211,10 @@ impl Rocket<Orbit> {
             }
         }
 
+        // Identify the request, including in everything logged from now on.
+        let id = req.local_cache(|| RequestId::resolve(req));
+        Span::with(|span| span.set_id(id.as_str()));
+
         // Run request fairings.
         self.fairings.handle_request(req, data).await;
 
@@ -216,8 +244,15 @@ impl Rocket<Orbit> {
             }
         }
 
+        // Echo the request's id unless the response already carries one.
+        let id = request.local_cache(|| RequestId::resolve(request));
+        if !response.headers().contains(&self.config.request_id_header) {
+            response.set_header(id.header());
+        }
+
         // Run the response fairings.
         self.fairings.handle_response(request, &mut response).await;
+        Span::with(|span| span.set_status(response.status()));
 
         // Strip the body if this is a `HEAD` request.
         if was_head_request {
@@ -242,10 +277,10 @@ impl Rocket<Orbit> {
                 match self.route(request, data).await {
                     Outcome::Success(response) => response,
                     Outcome::Failure(status) => self.handle_error(status, request).await,
-                    Outcome::Forward(_) => self.handle_error(Status::NotFound, request).await,
+                    Outcome::Forward(_) => self.handle_unrouted(request).await,
                 }
             }
-            Outcome::Forward(_) => self.handle_error(Status::NotFound, request).await,
+            Outcome::Forward(_) => self.handle_unrouted(request).await,
             Outcome::Failure(status) => self.handle_error(status, request).await,
         };
 
@@ -270,22 +305,48 @@ impl Rocket<Orbit> {
         request: &'r Request<'s>,
         mut data: Data<'r>,
     ) -> route::Outcome<'r> {
+        // A request fairing may have already failed the request.
+        if let Some(status) = request.failure() {
+            info_!("{} {}", Paint::default("Failed by a fairing:").bold(), status);
+            return Outcome::Failure(status);
+        }
+
         // Go through the list of matching routes until we fail or succeed.
         for route in self.router.route(request) {
             // Retrieve and set the requests parameters.
             info_!("Matched: {}", route);
             request.set_route(route);
+            Span::with(|span| span.set_route(route));
 
             let name = route.name.as_deref();
-            let outcome = handle(name, || route.handler.handle(request, data)).await
-                .unwrap_or_else(|| Outcome::Failure(Status::InternalServerError));
+            let run = handle(name, || route.handler.handle(request, data));
+            let outcome = match route.timeout.unwrap_or(self.config.timeout) {
+                0 => run.await,
+                secs => match tokio::time::timeout(Duration::from_secs(secs.into()), run).await {
+                    Ok(outcome) => outcome,
+                    Err(_) => {
+                        match name {
+                            Some(name) => error_!("Handler {} timed out after {}s.",
+                                Paint::white(name), secs),
+                            None => error_!("A handler timed out after {}s.", secs),
+                        }
+
+                        warn_!("A timeout is treated as a service unavailable error.");
+                        Some(Outcome::Failure(Status::ServiceUnavailable))
+                    }
+                }
+            }.unwrap_or_else(|| Outcome::Failure(Status::InternalServerError));
 
             // Check if the request processing completed (Some) or if the
             // request needs to be forwarded. If it does, continue the loop
             // (None) to try again.
             info_!("{} {}", Paint::default("Outcome:").bold(), outcome);
             match outcome {
-                o@Outcome::Success(_) | o@Outcome::Failure(_) => return o,
+                Outcome::Success(response) => {
+                    request.set_responder(route);
+                    return Outcome::Success(response);
+                }
+                o@Outcome::Failure(_) => return o,
                 Outcome::Forward(unused_data) => data = unused_data,
             }
         }
@@ -294,6 +355,37 @@ impl Rocket<Orbit> {
         Outcome::Forward(data)
     }
 
+    /// Responds to a request no route handled. If routes for other methods
+    /// match its URI, responds to an `OPTIONS` request with `204 No Content`
+    /// and to any other with `405 Method Not Allowed`, listing the supported
+    /// methods in an `Allow` header. Otherwise, responds with `404 Not Found`.
+    async fn handle_unrouted<'s, 'r: 's>(
+        &'s self,
+        request: &'r Request<'s>
+    ) -> Response<'r> {
+        let mut methods = self.router.methods(request);
+        if methods.is_empty() || methods.contains(&request.method()) {
+            return self.handle_error(Status::NotFound, request).await;
+        }
+
+        if !methods.contains(&Method::Options) {
+            methods.push(Method::Options);
+        }
+
+        methods.sort_by_key(|m| m.as_str());
+        let allow: Vec<_> = methods.iter().map(|m| m.as_str()).collect();
+        let mut response = match request.method() {
+            Method::Options => {
+                info_!("Autohandling {} request.", Paint::default("OPTIONS").bold());
+                Response::build().status(Status::NoContent).finalize()
+            }
+            _ => self.handle_error(Status::MethodNotAllowed, request).await,
+        };
+
+        response.set_header(Header::new("Allow", allow.join(", ")));
+        response
+    }
+
     /// Invokes the handler with `req` for catcher with status `status`.
     ///
     /// In order of preference, invoked handler is:
@@ -360,8 +452,16 @@ impl Rocket<Orbit> {
     {
         use std::net::ToSocketAddrs;
 
+        let ip = match self.config.address {
+            Address::Ip(ip) => ip,
+            Address::Unix(ref path) => {
+                let path = path.clone();
+                return self.default_unix_http_server(path, ready).await;
+            }
+        };
+
         // Determine the address we're going to serve on.
-        let addr = format!("{}:{}", self.config.address, self.config.port);
+        let addr = format!("{}:{}", ip, self.config.port);
         let mut addr = addr.to_socket_addrs()
             .map(|mut addrs| addrs.next().expect(">= 1 socket addr"))
             .map_err(|e| Error::new(ErrorKind::Io(e)))?;
@@ -371,9 +471,10 @@ impl Rocket<Orbit> {
             use crate::http::private::tls::bind_tls;
 
             let (certs, key) = config.to_readers().map_err(ErrorKind::Io)?;
-            let l = bind_tls(addr, certs, key).await.map_err(ErrorKind::Bind)?;
+            let client_auth = config.client_auth().map_err(ErrorKind::Io)?;
+            let l = bind_tls(addr, certs, key, client_auth).await.map_err(ErrorKind::Bind)?;
             addr = l.local_addr().unwrap_or(addr);
-            self.config.address = addr.ip();
+            self.config.address = addr.ip().into();
             self.config.port = addr.port();
             ready(&mut self).await;
             return self.http_server(l).await;
@@ -381,12 +482,38 @@ impl Rocket<Orbit> {
 
         let l = bind_tcp(addr).await.map_err(ErrorKind::Bind)?;
         addr = l.local_addr().unwrap_or(addr);
-        self.config.address = addr.ip();
+        self.config.address = addr.ip().into();
         self.config.port = addr.port();
         ready(&mut self).await;
         self.http_server(l).await
     }
 
+    #[cfg(unix)]
+    async fn default_unix_http_server<C>(mut self, path: PathBuf, ready: C) -> Result<(), Error>
+        where C: for<'a> Fn(&'a Self) -> BoxFuture<'a, ()>
+    {
+        use crate::http::private::bind_unix;
+
+        if self.config.tls_enabled() {
+            let msg = "TLS is not supported on Unix domain sockets";
+            let e = io::Error::new(io::ErrorKind::Other, msg);
+            return Err(Error::new(ErrorKind::Bind(e)));
+        }
+
+        let l = bind_unix(&path, self.config.socket_mode).await.map_err(ErrorKind::Bind)?;
+        ready(&mut self).await;
+        self.http_server(l).await
+    }
+
+    #[cfg(not(unix))]
+    async fn default_unix_http_server<C>(self, _: PathBuf, _: C) -> Result<(), Error>
+        where C: for<'a> Fn(&'a Self) -> BoxFuture<'a, ()>
+    {
+        let msg = "Unix domain sockets are only supported on Unix platforms";
+        let e = io::Error::new(io::ErrorKind::Other, msg);
+        Err(Error::new(ErrorKind::Bind(e)))
+    }
+
     // TODO.async: Solidify the Listener APIs and make this function public
     pub(crate) async fn http_server<L>(self, listener: L) -> Result<(), Error>
         where L: Listener + Send, <L as Listener>::Connection: Send + Unpin + 'static
@@ -411,10 +538,10 @@ impl Rocket<Orbit> {
         let rocket = Arc::new(self);
         let service_fn = move |conn: &CancellableIo<_, L::Connection>| {
             let rocket = rocket.clone();
-            let remote = conn.remote_addr().unwrap_or_else(|| ([0, 0, 0, 0], 0).into());
+            let connection = ConnectionMeta::from(conn);
             async move {
                 Ok::<_, std::convert::Infallible>(hyper::service_fn(move |req| {
-                    hyper_service_fn(rocket.clone(), remote, req)
+                    hyper_service_fn(rocket.clone(), connection.clone(), req)
                 }))
             }
         };
@@ -0,0 +1,84 @@
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::session::Record;
+
+/// The session configuration: the session cookie's name and when sessions
+/// expire.
+///
+/// Sessions end at whichever timeout elapses first. Renaming `cookie_name`
+/// effectively ends every existing session, since browsers keep sending the
+/// old cookie, which is then ignored.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::session::Config;
+///
+/// let figment = rocket::Config::figment()
+///     .merge(("session.cookie_name", "sid"))
+///     .merge(("session.idle_timeout", 600));
+///
+/// let config: Config = figment.extract_inner("session").unwrap();
+/// assert_eq!(config.cookie_name, "sid");
+/// assert_eq!(config.idle_timeout, 600);
+/// assert_eq!(config.absolute_timeout, 86400);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// The name of the private cookie holding the session id. **(default:
+    /// `"rocket_session"`)**
+    pub cookie_name: String,
+    /// Seconds after its last use that a session expires, `0` to disable.
+    /// **(default: `1800`)**
+    pub idle_timeout: u64,
+    /// Seconds after its creation that a session expires regardless of use,
+    /// `0` to disable. **(default: `86400`)**
+    pub absolute_timeout: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            cookie_name: "rocket_session".into(),
+            idle_timeout: 1800,
+            absolute_timeout: 86400,
+        }
+    }
+}
+
+impl Config {
+    /// Returns `true` if `record` has expired as of `now`.
+    pub(crate) fn is_expired(&self, record: &Record, now: SystemTime) -> bool {
+        let elapsed = |since: SystemTime| now.duration_since(since).unwrap_or_default();
+        let exceeds = |elapsed: Duration, timeout: u64| {
+            timeout != 0 && elapsed > Duration::from_secs(timeout)
+        };
+
+        exceeds(elapsed(record.accessed), self.idle_timeout)
+            || exceeds(elapsed(record.created), self.absolute_timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expiry() {
+        let now = SystemTime::now();
+        let ago = |secs| now - Duration::from_secs(secs);
+        let record = |created, accessed| Record { data: "{}".into(), created, accessed };
+        let config = Config { idle_timeout: 60, absolute_timeout: 3600, ..Config::default() };
+
+        assert!(!config.is_expired(&record(now, now), now));
+        assert!(!config.is_expired(&record(ago(3000), ago(30)), now));
+        assert!(config.is_expired(&record(ago(3000), ago(90)), now));
+        assert!(config.is_expired(&record(ago(3700), ago(30)), now));
+
+        let config = Config { idle_timeout: 0, absolute_timeout: 0, ..config };
+        assert!(!config.is_expired(&record(ago(1 << 30), ago(1 << 30)), now));
+    }
+}
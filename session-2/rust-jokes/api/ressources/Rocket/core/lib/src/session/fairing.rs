@@ -0,0 +1,158 @@
+use std::io;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{Rocket, Build, Orbit, Request, Response};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::session::{Config, Record, SessionStore};
+use crate::session::session::{Slot, State};
+
+/// How often expired sessions are purged from the store.
+const PURGE_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Loads and persists sessions. Managed by the [`Sessions`] fairing.
+pub(crate) struct Manager {
+    pub store: Arc<dyn SessionStore>,
+    pub config: Config,
+}
+
+impl Manager {
+    /// Loads the session identified by `req`'s session cookie. Starts a new
+    /// session if there's no cookie, or if the session is missing, expired,
+    /// unreadable, or can't be deserialized as a `T`.
+    pub async fn load<T>(&self, req: &Request<'_>) -> io::Result<State>
+        where T: Serialize + DeserializeOwned + Default + Send + Sync + 'static
+    {
+        let now = SystemTime::now();
+        let id = match req.cookies().get_private(&self.config.cookie_name) {
+            Some(cookie) => cookie.value().to_string(),
+            None => return Ok(State::new(None, now, T::default(), vec![])),
+        };
+
+        let record = match self.store.load(&id).await {
+            Ok(Some(record)) if !self.config.is_expired(&record, now) => record,
+            Ok(_) => return Ok(State::new(None, now, T::default(), vec![id])),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                warn_!("Discarding session that failed to load: {}", e);
+                return Ok(State::new(None, now, T::default(), vec![id]));
+            }
+            Err(e) => return Err(e),
+        };
+
+        match serde_json::from_str::<T>(&record.data) {
+            Ok(data) => Ok(State::new(Some(id), record.created, data, vec![])),
+            Err(e) => {
+                warn_!("Discarding session that failed to deserialize: {}", e);
+                Ok(State::new(None, now, T::default(), vec![id]))
+            }
+        }
+    }
+
+    /// Removes stale sessions and saves the current one, if it exists.
+    async fn persist(&self, state: State) -> io::Result<()> {
+        for id in &state.stale {
+            self.store.remove(id).await?;
+        }
+
+        if let Some(id) = &state.id {
+            let data = (state.serialize)(&state.data)?;
+            let record = Record { data, created: state.created, accessed: SystemTime::now() };
+            self.store.save(id, record).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A [`Fairing`] that provides server-side sessions backed by a
+/// [`SessionStore`].
+///
+/// See the [module docs](crate::session) for usage, configuration, and
+/// behavior details.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::session::{Sessions, MemoryStore};
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(Sessions::new(MemoryStore::new()))
+/// }
+/// ```
+pub struct Sessions {
+    store: Arc<dyn SessionStore>,
+}
+
+impl Sessions {
+    /// Returns a `Sessions` fairing keeping sessions in `store`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::session::{Sessions, FileStore};
+    ///
+    /// let sessions = Sessions::new(FileStore::new("sessions"));
+    /// ```
+    pub fn new<S: SessionStore>(store: S) -> Self {
+        Sessions { store: Arc::new(store) }
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Sessions {
+    fn info(&self) -> Info {
+        Info {
+            name: "Sessions",
+            kind: Kind::Ignite | Kind::Liftoff | Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let config = match crate::config::extract_or_default::<Config>(&rocket, "session") {
+            Some(config) => config,
+            None => return Err(rocket),
+        };
+
+        Ok(rocket.manage(Manager { store: self.store.clone(), config }))
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let manager = match rocket.state::<Manager>() {
+            Some(manager) => manager,
+            None => return,
+        };
+
+        let config = manager.config.clone();
+        if config.idle_timeout == 0 && config.absolute_timeout == 0 {
+            return;
+        }
+
+        let store = manager.store.clone();
+        rocket.shutdown().spawn_periodic(PURGE_INTERVAL, move || {
+            let (store, config) = (store.clone(), config.clone());
+            async move {
+                let now = SystemTime::now();
+                if let Err(e) = store.purge(&|record| config.is_expired(record, now)).await {
+                    error!("failed to purge expired sessions: {}", e);
+                }
+            }
+        });
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, _: &mut Response<'r>) {
+        let state = match req.local_cache(Slot::default).0.lock().take() {
+            Some(state) => state,
+            None => return,
+        };
+
+        if let Some(manager) = req.rocket().state::<Manager>() {
+            if let Err(e) = manager.persist(state).await {
+                error_!("Failed to persist session: {}", e);
+            }
+        }
+    }
+}
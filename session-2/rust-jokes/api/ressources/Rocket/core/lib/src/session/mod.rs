@@ -0,0 +1,99 @@
+//! Server-side sessions with pluggable storage backends.
+//!
+//! The [`Session`] request guard gives handlers access to data associated with
+//! the requesting client that persists across requests. Unlike data kept in
+//! cookies, session data lives on the server, in a [`SessionStore`]; the client
+//! only holds a random session id in a [private cookie]. Sessions are enabled
+//! by attaching the [`Sessions`] fairing with a store.
+//!
+//! Rocket provides two stores: a [`MemoryStore`], which keeps sessions in
+//! memory, and a [`FileStore`], which keeps each session in a file. Other
+//! backends can be used by implementing [`SessionStore`].
+//!
+//! # Enabling
+//!
+//! This module is only available when the `sessions` feature is enabled, which
+//! in turn enables the `secrets` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket = { version = "0.5.0-rc.1", features = ["sessions"] }
+//! ```
+//!
+//! As with all private cookies, a [`secret_key`] must be configured in
+//! production.
+//!
+//! # Usage
+//!
+//! Attach the fairing and use a [`Session`] guard, with any serializable data
+//! type implementing `Default`, in handlers:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::session::{Session, Sessions, MemoryStore};
+//!
+//! #[post("/login/<user>")]
+//! fn login(user: String, session: Session<'_, Option<String>>) {
+//!     // Always rotate the session id when privileges change.
+//!     session.rotate();
+//!     session.set(Some(user));
+//! }
+//!
+//! #[get("/")]
+//! fn index(session: Session<'_, Option<String>>) -> String {
+//!     match session.get() {
+//!         Some(user) => format!("Hello, {}!", user),
+//!         None => "Hello, stranger!".into(),
+//!     }
+//! }
+//!
+//! #[post("/logout")]
+//! fn logout(session: Session<'_, Option<String>>) {
+//!     session.destroy();
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![login, index, logout])
+//!         .attach(Sessions::new(MemoryStore::new()))
+//! }
+//! ```
+//!
+//! # Configuration
+//!
+//! The session cookie's name and session timeouts, in seconds, are read from
+//! the `session` configuration parameter into a [`Config`]. The defaults are:
+//!
+//! ```toml
+//! [default.session]
+//! cookie_name = "rocket_session"
+//! idle_timeout = 1800
+//! absolute_timeout = 86400
+//! ```
+//!
+//! # Behavior
+//!
+//! A session is created, and its cookie set, the first time its data is
+//! written to. Reading a session that doesn't exist yields `T::default()`
+//! without creating it. After the response is generated, the fairing saves
+//! the sessions used in the request, removing any rotated or destroyed ones.
+//!
+//! A session expires when it hasn't been used for `idle_timeout` seconds, or
+//! when it was created more than `absolute_timeout` seconds ago, whichever is
+//! first. Requests with an expired session are treated as requests without
+//! one. Expired sessions are also periodically purged from the store while
+//! the application is running.
+//!
+//! [private cookie]: crate::http::CookieJar::add_private()
+//! [`secret_key`]: crate::Config::secret_key
+
+mod config;
+mod store;
+mod session;
+mod fairing;
+
+pub use self::config::Config;
+pub use self::store::{SessionStore, Record, Expired, MemoryStore, FileStore};
+pub use self::session::Session;
+pub use self::fairing::Sessions;
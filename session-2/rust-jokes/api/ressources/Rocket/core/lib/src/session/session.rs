@@ -0,0 +1,334 @@
+use std::io;
+use std::any::Any;
+use std::marker::PhantomData;
+use std::time::{Duration, SystemTime};
+
+use parking_lot::{Mutex, MappedMutexGuard, MutexGuard};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{Request, Rocket, Ignite, Sentinel};
+use crate::request::{self, FromRequest};
+use crate::outcome::Outcome;
+use crate::http::{Cookie, CookieJar, SameSite, Status};
+use crate::session::fairing::Manager;
+
+/// Session data, type-erased so the fairing can persist it.
+type Data = Box<dyn Any + Send + Sync>;
+
+/// The state of a request's session, from when it's first used by a guard
+/// until it's persisted by the fairing.
+pub(crate) struct State {
+    /// The session's id, or `None` if it hasn't been assigned one yet.
+    pub id: Option<String>,
+    /// When the session was created.
+    pub created: SystemTime,
+    /// The session's data, of the type of the first `Session` guard used.
+    pub data: Data,
+    /// Serializes `data`.
+    pub serialize: fn(&Data) -> serde_json::Result<String>,
+    /// Whether no session was loaded from the store.
+    pub new: bool,
+    /// Ids of sessions to remove from the store: expired, rotated, or
+    /// destroyed ones.
+    pub stale: Vec<String>,
+}
+
+/// The session of a request, if any has been loaded.
+#[derive(Default)]
+pub(crate) struct Slot(pub Mutex<Option<State>>);
+
+impl State {
+    pub fn new<T>(id: Option<String>, created: SystemTime, data: T, stale: Vec<String>) -> State
+        where T: Serialize + Send + Sync + 'static
+    {
+        fn serialize<T: Serialize + 'static>(data: &Data) -> serde_json::Result<String> {
+            serde_json::to_string(data.downcast_ref::<T>().expect("session data type"))
+        }
+
+        State {
+            new: id.is_none(),
+            data: Box::new(data),
+            serialize: serialize::<T>,
+            id, created, stale,
+        }
+    }
+}
+
+/// A request guard for the requesting client's server-side session, holding
+/// data of type `T`.
+///
+/// The session is identified by a random id in a private cookie, and its data
+/// is kept in the [`SessionStore`] given to the
+/// [`Sessions`](crate::session::Sessions) fairing. The guard loads the data
+/// when first used in a request; changes are persisted by the fairing after
+/// the response is generated. A client without a live session gets a new one
+/// with `T::default()` data, which isn't stored, and doesn't get a cookie,
+/// until it's written to via [`set()`](Session::set()) or
+/// [`update()`](Session::update()).
+///
+/// All `Session` guards in an application should use the same `T`. The guard
+/// fails with `500 Internal Server Error` if the session can't be loaded or if
+/// another `Session` guard in the same request used another type.
+///
+/// Using a `Session` requires attaching the `Sessions` fairing; launch is
+/// aborted otherwise.
+///
+/// [`SessionStore`]: crate::session::SessionStore
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::serde::{Serialize, Deserialize};
+/// use rocket::session::Session;
+///
+/// #[derive(Default, Clone, Serialize, Deserialize)]
+/// #[serde(crate = "rocket::serde")]
+/// struct Cart {
+///     items: Vec<String>,
+/// }
+///
+/// #[post("/cart/<item>")]
+/// fn add(item: String, session: Session<'_, Cart>) -> String {
+///     let count = session.update(|cart| {
+///         cart.items.push(item);
+///         cart.items.len()
+///     });
+///
+///     format!("{} items in cart", count)
+/// }
+///
+/// #[get("/cart")]
+/// fn cart(session: Session<'_, Cart>) -> String {
+///     session.get().items.join(", ")
+/// }
+/// ```
+pub struct Session<'r, T> {
+    slot: &'r Slot,
+    jar: &'r CookieJar<'r>,
+    manager: &'r Manager,
+    secure: bool,
+    _data: PhantomData<fn() -> T>,
+}
+
+impl<'r, T> Session<'r, T>
+    where T: Serialize + DeserializeOwned + Default + Send + Sync + 'static
+{
+    fn state(&self) -> MappedMutexGuard<'r, State> {
+        MutexGuard::map(self.slot.0.lock(), |state| {
+            state.as_mut().expect("session state is present until the response")
+        })
+    }
+
+    /// Assigns `state` a fresh id and sets the session cookie to it.
+    fn assign_id(&self, state: &mut State) {
+        let id = crate::ext::random_token(32);
+
+        let config = &self.manager.config;
+        let mut cookie = Cookie::build(config.cookie_name.clone(), id.clone())
+            .path("/")
+            .same_site(SameSite::Lax)
+            .http_only(true)
+            .secure(self.secure)
+            .finish();
+
+        if config.absolute_timeout != 0 {
+            let expires = state.created + Duration::from_secs(config.absolute_timeout);
+            cookie.set_expires(time::OffsetDateTime::from(expires));
+        }
+
+        self.jar.add_private(cookie);
+        state.id = Some(id);
+    }
+
+    /// Returns a copy of the session's data.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::session::Session;
+    ///
+    /// #[get("/")]
+    /// fn visits(session: Session<'_, usize>) -> String {
+    ///     format!("{} visits", session.get())
+    /// }
+    /// ```
+    pub fn get(&self) -> T where T: Clone {
+        self.read(T::clone)
+    }
+
+    /// Returns the result of calling `f` with a reference to the session's
+    /// data.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::session::Session;
+    ///
+    /// #[get("/")]
+    /// fn visits(session: Session<'_, Vec<String>>) -> String {
+    ///     session.read(|pages| pages.join(", "))
+    /// }
+    /// ```
+    pub fn read<R, F: FnOnce(&T) -> R>(&self, f: F) -> R {
+        let state = self.state();
+        f(state.data.downcast_ref().expect("session data type"))
+    }
+
+    /// Replaces the session's data with `value`, creating the session if it
+    /// doesn't exist.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::session::Session;
+    ///
+    /// #[post("/login/<user>")]
+    /// fn login(user: String, session: Session<'_, Option<String>>) {
+    ///     session.rotate();
+    ///     session.set(Some(user));
+    /// }
+    /// ```
+    pub fn set(&self, value: T) {
+        self.update(|data| *data = value)
+    }
+
+    /// Returns the result of calling `f` with a mutable reference to the
+    /// session's data, creating the session if it doesn't exist.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::session::Session;
+    ///
+    /// #[get("/")]
+    /// fn visit(session: Session<'_, usize>) -> String {
+    ///     let visits = session.update(|visits| { *visits += 1; *visits });
+    ///     format!("visit #{}", visits)
+    /// }
+    /// ```
+    pub fn update<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> R {
+        let mut state = self.state();
+        if state.id.is_none() {
+            self.assign_id(&mut state);
+        }
+
+        f(state.data.downcast_mut().expect("session data type"))
+    }
+
+    /// Returns `true` if the request didn't carry the id of a live session.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::session::Session;
+    ///
+    /// #[get("/")]
+    /// fn index(session: Session<'_, usize>) -> &'static str {
+    ///     if session.is_new() { "Welcome!" } else { "Welcome back!" }
+    /// }
+    /// ```
+    pub fn is_new(&self) -> bool {
+        self.state().new
+    }
+
+    /// Moves the session, data and all, to a new id, and removes the old id.
+    /// Does nothing if the session doesn't exist yet.
+    ///
+    /// Rotate the session whenever the privilege level of the client changes,
+    /// such as on login, to prevent [session fixation].
+    ///
+    /// [session fixation]: https://owasp.org/www-community/attacks/Session_fixation
+    pub fn rotate(&self) {
+        let mut state = self.state();
+        if let Some(id) = state.id.take() {
+            state.stale.push(id);
+            self.assign_id(&mut state);
+        }
+    }
+
+    /// Destroys the session: removes it from the store, removes the session
+    /// cookie, and resets the data to `T::default()`. Writing to the session
+    /// afterwards creates a new one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::session::Session;
+    ///
+    /// #[post("/logout")]
+    /// fn logout(session: Session<'_, Option<String>>) {
+    ///     session.destroy();
+    /// }
+    /// ```
+    pub fn destroy(&self) {
+        let mut state = self.state();
+        if let Some(id) = state.id.take() {
+            state.stale.push(id);
+            self.jar.remove_private(Cookie::named(self.manager.config.cookie_name.clone()));
+        }
+
+        state.data = Box::new(T::default());
+        state.created = SystemTime::now();
+    }
+}
+
+#[crate::async_trait]
+impl<'r, T> FromRequest<'r> for Session<'r, T>
+    where T: Serialize + DeserializeOwned + Default + Send + Sync + 'static
+{
+    type Error = io::Error;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let manager = match req.rocket().state::<Manager>() {
+            Some(manager) => manager,
+            None => {
+                error_!("`Session` guard used without attaching the `Sessions` fairing.");
+                let error = io::Error::new(io::ErrorKind::Other, "missing `Sessions` fairing");
+                return Outcome::Failure((Status::InternalServerError, error));
+            }
+        };
+
+        let slot = req.local_cache(Slot::default);
+        if slot.0.lock().is_none() {
+            let state = match manager.load::<T>(req).await {
+                Ok(state) => state,
+                Err(e) => {
+                    error_!("Failed to load session: {}", e);
+                    return Outcome::Failure((Status::InternalServerError, e));
+                }
+            };
+
+            slot.0.lock().get_or_insert(state);
+        }
+
+        if !matches!(&*slot.0.lock(), Some(state) if state.data.is::<T>()) {
+            let ty = std::any::type_name::<T>();
+            let msg = format!("`Session<{}>` used with another session type", ty);
+            error_!("{}.", msg);
+            let error = io::Error::new(io::ErrorKind::InvalidData, msg);
+            return Outcome::Failure((Status::InternalServerError, error));
+        }
+
+        let secure = req.rocket().config().tls_enabled();
+        Outcome::Success(Session { slot, jar: req.cookies(), manager, secure, _data: PhantomData })
+    }
+}
+
+impl<T> Sentinel for Session<'_, T> {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        if rocket.state::<Manager>().is_none() {
+            error!("launching with a `Session` guard without the `Sessions` fairing.");
+            info_!("Using `Session` requires attaching `Sessions`.");
+            return true;
+        }
+
+        false
+    }
+}
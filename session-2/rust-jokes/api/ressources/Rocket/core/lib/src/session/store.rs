@@ -0,0 +1,358 @@
+use std::io;
+use std::sync::Arc;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+/// A stored session: its serialized data and timestamps.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Record {
+    /// The session data, serialized as JSON.
+    pub data: String,
+    /// When the session was created.
+    pub created: SystemTime,
+    /// When the session was last used.
+    pub accessed: SystemTime,
+}
+
+/// The predicate passed to [`SessionStore::purge()`]: returns `true` if a
+/// [`Record`] has expired.
+pub type Expired<'a> = &'a (dyn Fn(&Record) -> bool + Send + Sync + 'a);
+
+/// A backend that persists session [`Record`]s by session id.
+///
+/// Session ids are random alphanumeric strings generated by Rocket. A store
+/// need not check for expiry: the [`Sessions`](crate::session::Sessions)
+/// fairing discards expired records on load and periodically calls
+/// [`purge()`](SessionStore::purge()) to remove them.
+///
+/// Rocket provides a [`MemoryStore`] and a [`FileStore`]. Other backends,
+/// such as a database, can be used by implementing this trait.
+///
+/// # Example
+///
+/// A store that delegates to another while counting saves:
+///
+/// ```rust
+/// use std::io;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// use rocket::session::{SessionStore, MemoryStore, Record, Expired};
+///
+/// #[derive(Default)]
+/// struct CountingStore(MemoryStore, AtomicUsize);
+///
+/// #[rocket::async_trait]
+/// impl SessionStore for CountingStore {
+///     async fn load(&self, id: &str) -> io::Result<Option<Record>> {
+///         self.0.load(id).await
+///     }
+///
+///     async fn save(&self, id: &str, record: Record) -> io::Result<()> {
+///         self.1.fetch_add(1, Ordering::Relaxed);
+///         self.0.save(id, record).await
+///     }
+///
+///     async fn remove(&self, id: &str) -> io::Result<()> {
+///         self.0.remove(id).await
+///     }
+///
+///     async fn purge(&self, expired: Expired<'_>) -> io::Result<()> {
+///         self.0.purge(expired).await
+///     }
+/// }
+/// ```
+#[crate::async_trait]
+pub trait SessionStore: Send + Sync + 'static {
+    /// Returns the record for session `id`, or `None` if there is none.
+    ///
+    /// A record that exists but can't be read, for instance because it's
+    /// corrupt, should be reported as an error of kind
+    /// [`InvalidData`](io::ErrorKind::InvalidData). Rocket then discards the
+    /// session and starts a new one instead of failing the request.
+    async fn load(&self, id: &str) -> io::Result<Option<Record>>;
+
+    /// Stores `record` as session `id`, replacing any existing record.
+    async fn save(&self, id: &str, record: Record) -> io::Result<()>;
+
+    /// Removes session `id`. Succeeds if there is no such session.
+    async fn remove(&self, id: &str) -> io::Result<()>;
+
+    /// Removes every record for which `expired` returns `true`.
+    async fn purge(&self, expired: Expired<'_>) -> io::Result<()>;
+}
+
+/// A [`SessionStore`] that keeps sessions in memory.
+///
+/// Sessions are lost when the application exits. Clones of a `MemoryStore`
+/// share the same sessions.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::session::{Sessions, MemoryStore};
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(Sessions::new(MemoryStore::new()))
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore(Arc<Mutex<HashMap<String, Record>>>);
+
+impl MemoryStore {
+    /// Returns an empty `MemoryStore`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::session::MemoryStore;
+    ///
+    /// let store = MemoryStore::new();
+    /// assert!(store.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        MemoryStore::default()
+    }
+
+    /// Returns the number of stored sessions, including expired sessions not
+    /// yet purged.
+    pub fn len(&self) -> usize {
+        self.0.lock().len()
+    }
+
+    /// Returns `true` if there are no stored sessions.
+    pub fn is_empty(&self) -> bool {
+        self.0.lock().is_empty()
+    }
+}
+
+#[crate::async_trait]
+impl SessionStore for MemoryStore {
+    async fn load(&self, id: &str) -> io::Result<Option<Record>> {
+        Ok(self.0.lock().get(id).cloned())
+    }
+
+    async fn save(&self, id: &str, record: Record) -> io::Result<()> {
+        self.0.lock().insert(id.to_string(), record);
+        Ok(())
+    }
+
+    async fn remove(&self, id: &str) -> io::Result<()> {
+        self.0.lock().remove(id);
+        Ok(())
+    }
+
+    async fn purge(&self, expired: Expired<'_>) -> io::Result<()> {
+        self.0.lock().retain(|_, record| !expired(record));
+        Ok(())
+    }
+}
+
+/// A [`SessionStore`] that keeps each session in a JSON file, named by its
+/// id, in a directory.
+///
+/// The directory is created when the first session is saved. Sessions survive
+/// restarts and can be shared by several instances of an application with
+/// access to the same directory.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::session::{Sessions, FileStore};
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(Sessions::new(FileStore::new("/var/lib/app/sessions")))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    /// Returns a `FileStore` keeping sessions in the directory `dir`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::session::FileStore;
+    ///
+    /// let store = FileStore::new("sessions");
+    /// assert_eq!(store.dir(), std::path::Path::new("sessions"));
+    /// ```
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        FileStore { dir: dir.as_ref().into() }
+    }
+
+    /// Returns the directory sessions are kept in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The path to the file for session `id`. Ids are generated by Rocket, but
+    /// are checked anyway since they become file names.
+    fn path(&self, id: &str) -> io::Result<PathBuf> {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            let msg = format!("invalid session id: {:?}", id);
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+        }
+
+        Ok(self.dir.join(id).with_extension("json"))
+    }
+
+    async fn read(path: &Path) -> io::Result<Option<Record>> {
+        let bytes = match tokio::fs::read(path).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+#[crate::async_trait]
+impl SessionStore for FileStore {
+    async fn load(&self, id: &str) -> io::Result<Option<Record>> {
+        FileStore::read(&self.path(id)?).await
+    }
+
+    async fn save(&self, id: &str, record: Record) -> io::Result<()> {
+        let path = self.path(id)?;
+        let bytes = serde_json::to_vec(&record)?;
+
+        // Write to a temporary file and rename so readers never see a partial
+        // record. Each save uses its own temporary file so that concurrent
+        // saves of the same session don't write to the same file.
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let tmp = path.with_extension(format!("{}.tmp", crate::ext::random_token(16)));
+        let result = match tokio::fs::write(&tmp, bytes).await {
+            Ok(()) => tokio::fs::rename(&tmp, &path).await,
+            Err(e) => Err(e),
+        };
+
+        if result.is_err() {
+            let _ = tokio::fs::remove_file(&tmp).await;
+        }
+
+        result
+    }
+
+    async fn remove(&self, id: &str) -> io::Result<()> {
+        match tokio::fs::remove_file(self.path(id)?).await {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    async fn purge(&self, expired: Expired<'_>) -> io::Result<()> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+
+            // Unreadable records will never be loaded successfully either.
+            let remove = match FileStore::read(&path).await {
+                Ok(Some(record)) => expired(&record),
+                Ok(None) => false,
+                Err(_) => true,
+            };
+
+            if remove {
+                let _ = tokio::fs::remove_file(&path).await;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(data: &str) -> Record {
+        Record { data: data.into(), created: SystemTime::now(), accessed: SystemTime::now() }
+    }
+
+    async fn exercise<S: SessionStore>(store: S) {
+        assert_eq!(store.load("a").await.unwrap(), None);
+
+        store.save("a", record("1")).await.unwrap();
+        store.save("b", record("2")).await.unwrap();
+        store.save("a", record("3")).await.unwrap();
+        assert_eq!(store.load("a").await.unwrap().unwrap().data, "3");
+        assert_eq!(store.load("b").await.unwrap().unwrap().data, "2");
+
+        store.purge(&|record| record.data == "2").await.unwrap();
+        assert_eq!(store.load("b").await.unwrap(), None);
+
+        store.remove("a").await.unwrap();
+        store.remove("a").await.unwrap();
+        assert_eq!(store.load("a").await.unwrap(), None);
+    }
+
+    #[test]
+    fn test_memory_store() {
+        let store = MemoryStore::new();
+        crate::async_test(exercise(store.clone()));
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_file_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileStore::new(dir.path().join("sessions"));
+        crate::async_test(async {
+            store.purge(&|_| true).await.unwrap();
+            exercise(store.clone()).await;
+
+            assert!(store.load("../etc/passwd").await.is_err());
+            assert!(store.save("", record("1")).await.is_err());
+
+            tokio::fs::write(dir.path().join("sessions/bad.json"), "{").await.unwrap();
+            let error = store.load("bad").await.unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+            store.purge(&|_| false).await.unwrap();
+            assert!(store.load("bad").await.unwrap().is_none());
+        })
+    }
+
+    #[test]
+    fn test_file_store_concurrent_saves() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileStore::new(dir.path());
+        crate::async_test(async {
+            let saves = (0..16).map(|i| store.save("a", record(&i.to_string())));
+            for result in futures::future::join_all(saves).await {
+                result.unwrap();
+            }
+
+            let data = store.load("a").await.unwrap().unwrap().data;
+            assert!(data.parse::<usize>().unwrap() < 16);
+
+            // Only the record remains; no temporary files are left behind.
+            let names: Vec<_> = std::fs::read_dir(dir.path()).unwrap()
+                .map(|entry| entry.unwrap().file_name())
+                .collect();
+
+            assert_eq!(names, ["a.json"]);
+        })
+    }
+}
@@ -0,0 +1,636 @@
+use std::fmt;
+use std::borrow::Cow;
+
+use indexmap::IndexMap;
+use rocket_http::{ext::IntoCollection, private::SmallVec};
+use serde::{Serialize, Serializer};
+
+use crate::Request;
+use crate::request::{self, FromRequest};
+use crate::outcome::Outcome;
+use crate::http::{Header, uri::Absolute};
+use crate::shield::Policy;
+use crate::shield::policy::SubPolicy;
+
+/// The [Content-Security-Policy] header: controls the resources the browser
+/// may load for, and the scripts it may run in, a page.
+///
+/// A policy is a set of [`Directive`]s, each with a list of allowed
+/// [`Source`]s. Directives are set via the chainable
+/// [`directive()`](Self::directive()) builder method, which replaces any list
+/// previously set for the directive, and removed via
+/// [`remove()`](Self::remove()):
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::shield::{Shield, Csp, Directive, Source};
+///
+/// // Allow scripts from this origin and `https://cdn.rocket.rs`, and styles
+/// // from this origin and inline `<style>` tags with this request's nonce.
+/// let csp = Csp::default()
+///     .directive(Directive::ScriptSrc, [Source::This, Source::host("https://cdn.rocket.rs")])
+///     .directive(Directive::StyleSrc, [Source::This, Source::Nonce])
+///     .report_uri(uri!("https://rocket.rs/csp-reports"));
+///
+/// rocket::build().attach(Shield::default().enable(csp));
+/// ```
+///
+/// # Nonces
+///
+/// When any directive allows [`Source::Nonce`], [`Shield`] generates a random
+/// nonce for every request and renders it into that response's header. The
+/// nonce is exposed to handlers via the [`Nonce`] request guard so that it can
+/// be embedded in inline `<script>` and `<style>` tags, for instance by
+/// passing it to a template. [`Policy::header()`], which has no request to
+/// render for, omits nonce sources.
+///
+/// # Report-Only
+///
+/// To report on a policy without enforcing it, enable it as a
+/// [`CspReportOnly`] instead. The two are independent policies, so one policy
+/// can be enforced while another is trialled.
+///
+/// # Default
+///
+/// The default returned via [`Csp::default()`] allows resources only from the
+/// serving origin via `default-src 'self'`, disallows plugins via `object-src
+/// 'none'`, and restricts `<base>` URIs via `base-uri 'self'`.
+///
+/// [Content-Security-Policy]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy
+/// [`Shield`]: crate::shield::Shield
+#[derive(Debug, PartialEq, Clone)]
+pub struct Csp {
+    directives: IndexMap<Directive, SmallVec<[Source; 2]>>,
+    report_uri: Option<Absolute<'static>>,
+    report_to: Option<Cow<'static, str>>,
+    upgrade_insecure_requests: bool,
+}
+
+impl Default for Csp {
+    /// The default `Csp` policy: `default-src 'self'; object-src 'none';
+    /// base-uri 'self'`.
+    fn default() -> Self {
+        Csp::new()
+            .directive(Directive::DefaultSrc, Source::This)
+            .directive(Directive::ObjectSrc, Source::None)
+            .directive(Directive::BaseUri, Source::This)
+    }
+}
+
+impl Csp {
+    /// Constructs a new `Csp` policy with no directives, which allows
+    /// everything.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::{Csp, Directive, Source};
+    ///
+    /// let csp = Csp::new().directive(Directive::FrameAncestors, Source::None);
+    /// ```
+    pub fn new() -> Self {
+        Csp {
+            directives: IndexMap::new(),
+            report_uri: None,
+            report_to: None,
+            upgrade_insecure_requests: false,
+        }
+    }
+
+    /// Sets the sources allowed by `directive` to those in `sources`, which
+    /// may be a single [`Source`], a slice (`[Source]` or `&[Source]`), or a
+    /// vector (`Vec<Source>`).
+    ///
+    /// This list supercedes any previous list set for `directive`. An empty
+    /// list, like [`Source::None`], allows no sources.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::{Csp, Directive, Source};
+    ///
+    /// let csp = Csp::default()
+    ///     .directive(Directive::ImgSrc, [Source::This, Source::scheme("data")])
+    ///     .directive(Directive::ScriptSrc, Source::Nonce);
+    /// ```
+    pub fn directive<L>(mut self, directive: Directive, sources: L) -> Self
+        where L: IntoCollection<Source>
+    {
+        self.directives.insert(directive, sources.into_collection());
+        self
+    }
+
+    /// Removes `directive` from the policy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::{Csp, Directive};
+    ///
+    /// let csp = Csp::default().remove(Directive::BaseUri);
+    /// assert!(csp.get(Directive::BaseUri).is_none());
+    /// ```
+    pub fn remove(mut self, directive: Directive) -> Self {
+        self.directives.shift_remove(&directive);
+        self
+    }
+
+    /// Returns the sources allowed by `directive`, if it is set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::{Csp, Directive, Source};
+    ///
+    /// let csp = Csp::default();
+    /// assert_eq!(csp.get(Directive::DefaultSrc).unwrap(), &[Source::This]);
+    /// assert!(csp.get(Directive::ScriptSrc).is_none());
+    /// ```
+    pub fn get(&self, directive: Directive) -> Option<&[Source]> {
+        self.directives.get(&directive).map(|sources| &sources[..])
+    }
+
+    /// Sets the `report-uri` directive: the URI browsers `POST` reports of
+    /// policy violations to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::shield::Csp;
+    ///
+    /// let csp = Csp::default().report_uri(uri!("https://rocket.rs/csp"));
+    /// ```
+    pub fn report_uri(mut self, uri: Absolute<'static>) -> Self {
+        self.report_uri = Some(uri);
+        self
+    }
+
+    /// Sets the `report-to` directive: the name of the [reporting endpoint]
+    /// group browsers send reports of policy violations to.
+    ///
+    /// [reporting endpoint]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/report-to
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::Csp;
+    ///
+    /// let csp = Csp::default().report_to("csp-endpoint");
+    /// ```
+    pub fn report_to<S: Into<Cow<'static, str>>>(mut self, group: S) -> Self {
+        self.report_to = Some(group.into());
+        self
+    }
+
+    /// Sets whether to emit the `upgrade-insecure-requests` directive, which
+    /// instructs browsers to fetch `http` resources via `https` instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::Csp;
+    ///
+    /// let csp = Csp::default().upgrade_insecure_requests(true);
+    /// ```
+    pub fn upgrade_insecure_requests(mut self, enable: bool) -> Self {
+        self.upgrade_insecure_requests = enable;
+        self
+    }
+
+    /// Returns `true` if any directive allows [`Source::Nonce`].
+    pub(crate) fn uses_nonce(&self) -> bool {
+        self.directives.values().flatten().any(|source| *source == Source::Nonce)
+    }
+
+    /// Renders the policy in a header named `name`, replacing nonce sources
+    /// with `nonce`, or omitting them if `nonce` is `None`.
+    pub(crate) fn render(&self, name: &'static str, nonce: Option<&Nonce>) -> Header<'static> {
+        let mut directives: Vec<String> = self.directives.iter()
+            .map(|(directive, sources)| {
+                let mut rendered = directive.as_str().to_string();
+                for source in sources {
+                    let value = match (source, nonce) {
+                        (Source::Nonce, Some(nonce)) => format!("'nonce-{}'", nonce),
+                        (Source::Nonce, None) => continue,
+                        (source, _) => source.to_string(),
+                    };
+
+                    rendered.push(' ');
+                    rendered.push_str(&value);
+                }
+
+                rendered
+            })
+            .collect();
+
+        if self.upgrade_insecure_requests {
+            directives.push("upgrade-insecure-requests".into());
+        }
+
+        if let Some(uri) = &self.report_uri {
+            directives.push(format!("report-uri {}", uri));
+        }
+
+        if let Some(group) = &self.report_to {
+            directives.push(format!("report-to {}", group));
+        }
+
+        Header::new(name, directives.join("; "))
+    }
+
+    /// Returns the policy in `policy`, enforced or report-only, and the name of
+    /// its header if it uses nonces.
+    pub(crate) fn nonced(policy: &dyn SubPolicy) -> Option<(&'static str, &Csp)> {
+        let policy = policy.as_any();
+        policy.downcast_ref::<Csp>().map(|csp| (Csp::NAME, csp))
+            .or_else(|| policy.downcast_ref::<CspReportOnly>()
+                .map(|csp| (CspReportOnly::NAME, &csp.0)))
+            .filter(|(_, csp)| csp.uses_nonce())
+    }
+}
+
+impl From<&Csp> for Header<'static> {
+    fn from(csp: &Csp) -> Self {
+        csp.render(Csp::NAME, None)
+    }
+}
+
+/// The [Content-Security-Policy-Report-Only] header: a [`Csp`] policy whose
+/// violations are reported but not blocked.
+///
+/// Violations are reported to the policy's [`report_uri()`](Csp::report_uri())
+/// and [`report_to()`](Csp::report_to()) endpoints. This is a good way to try
+/// out a policy before enforcing it. Because it's a separate header, a
+/// `CspReportOnly` policy can be enabled alongside an enforced `Csp`:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::shield::{Shield, Csp, CspReportOnly, Directive, Source};
+///
+/// // Enforce the default policy while trialling a stricter one.
+/// let trial = Csp::default()
+///     .directive(Directive::ScriptSrc, Source::Nonce)
+///     .report_uri(uri!("https://rocket.rs/csp-reports"));
+///
+/// let shield = Shield::default()
+///     .enable(Csp::default())
+///     .enable(CspReportOnly::from(trial));
+/// ```
+///
+/// Nonces are rendered into both headers as described in [`Csp`]; a request's
+/// [`Nonce`] is the same in each.
+///
+/// [Content-Security-Policy-Report-Only]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy-Report-Only
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct CspReportOnly(Csp);
+
+impl From<Csp> for CspReportOnly {
+    fn from(csp: Csp) -> Self {
+        CspReportOnly(csp)
+    }
+}
+
+impl From<&CspReportOnly> for Header<'static> {
+    fn from(csp: &CspReportOnly) -> Self {
+        csp.0.render(CspReportOnly::NAME, None)
+    }
+}
+
+/// A fetch, document, or navigation directive of a [`Csp`] policy that takes
+/// a list of [`Source`]s.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[non_exhaustive]
+pub enum Directive {
+    /// The "default-src" directive: the fallback for other fetch directives.
+    DefaultSrc,
+    /// The "script-src" directive.
+    ScriptSrc,
+    /// The "script-src-elem" directive.
+    ScriptSrcElem,
+    /// The "script-src-attr" directive.
+    ScriptSrcAttr,
+    /// The "style-src" directive.
+    StyleSrc,
+    /// The "style-src-elem" directive.
+    StyleSrcElem,
+    /// The "style-src-attr" directive.
+    StyleSrcAttr,
+    /// The "img-src" directive.
+    ImgSrc,
+    /// The "font-src" directive.
+    FontSrc,
+    /// The "connect-src" directive.
+    ConnectSrc,
+    /// The "media-src" directive.
+    MediaSrc,
+    /// The "object-src" directive.
+    ObjectSrc,
+    /// The "frame-src" directive.
+    FrameSrc,
+    /// The "child-src" directive.
+    ChildSrc,
+    /// The "worker-src" directive.
+    WorkerSrc,
+    /// The "manifest-src" directive.
+    ManifestSrc,
+    /// The "base-uri" directive.
+    BaseUri,
+    /// The "form-action" directive.
+    FormAction,
+    /// The "frame-ancestors" directive.
+    FrameAncestors,
+}
+
+impl Directive {
+    /// Returns the directive's name as it appears in the header.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::Directive;
+    ///
+    /// assert_eq!(Directive::ScriptSrc.as_str(), "script-src");
+    /// assert_eq!(Directive::FrameAncestors.as_str(), "frame-ancestors");
+    /// ```
+    pub const fn as_str(self) -> &'static str {
+        use Directive::*;
+
+        match self {
+            DefaultSrc => "default-src",
+            ScriptSrc => "script-src",
+            ScriptSrcElem => "script-src-elem",
+            ScriptSrcAttr => "script-src-attr",
+            StyleSrc => "style-src",
+            StyleSrcElem => "style-src-elem",
+            StyleSrcAttr => "style-src-attr",
+            ImgSrc => "img-src",
+            FontSrc => "font-src",
+            ConnectSrc => "connect-src",
+            MediaSrc => "media-src",
+            ObjectSrc => "object-src",
+            FrameSrc => "frame-src",
+            ChildSrc => "child-src",
+            WorkerSrc => "worker-src",
+            ManifestSrc => "manifest-src",
+            BaseUri => "base-uri",
+            FormAction => "form-action",
+            FrameAncestors => "frame-ancestors",
+        }
+    }
+}
+
+impl fmt::Display for Directive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+/// A source allowed by a [`Csp`] [`Directive`].
+#[derive(Debug, PartialEq, Clone)]
+pub enum Source {
+    /// No source at all: `'none'`. Ignored by browsers when other sources are
+    /// listed.
+    None,
+    /// The serving origin: `'self'`.
+    This,
+    /// Any URL except `data:`, `blob:`, and `filesystem:` ones: `*`.
+    Any,
+    /// Inline scripts or styles: `'unsafe-inline'`.
+    UnsafeInline,
+    /// `eval()` and similar: `'unsafe-eval'`.
+    UnsafeEval,
+    /// Scripts loaded by already trusted scripts: `'strict-dynamic'`.
+    StrictDynamic,
+    /// The current request's [`Nonce`]: `'nonce-<nonce>'`.
+    Nonce,
+    /// Inline scripts or styles with the given base64-encoded SHA-256 hash:
+    /// `'sha256-<hash>'`.
+    Sha256(Cow<'static, str>),
+    /// Inline scripts or styles with the given base64-encoded SHA-384 hash:
+    /// `'sha384-<hash>'`.
+    Sha384(Cow<'static, str>),
+    /// Inline scripts or styles with the given base64-encoded SHA-512 hash:
+    /// `'sha512-<hash>'`.
+    Sha512(Cow<'static, str>),
+    /// Any URL with the given scheme, rendered with a trailing `:`. Construct
+    /// with [`Source::scheme()`].
+    Scheme(Cow<'static, str>),
+    /// URLs matching the given host source, such as `https://*.rocket.rs` or
+    /// `cdn.rocket.rs:443/assets/`, rendered verbatim. Construct with
+    /// [`Source::host()`].
+    Host(Cow<'static, str>),
+}
+
+impl Source {
+    /// Returns a [`Source::Scheme`] for `scheme`, with or without its trailing
+    /// `:`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scheme` contains characters not allowed in a URI scheme.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::Source;
+    ///
+    /// assert_eq!(Source::scheme("data").to_string(), "data:");
+    /// assert_eq!(Source::scheme("https:").to_string(), "https:");
+    /// ```
+    pub fn scheme<S: Into<Cow<'static, str>>>(scheme: S) -> Source {
+        let mut scheme = scheme.into();
+        if scheme.ends_with(':') {
+            scheme.to_mut().pop();
+        }
+
+        let valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+
+        if !valid {
+            panic!("invalid CSP scheme source: {:?}", scheme);
+        }
+
+        Source::Scheme(scheme)
+    }
+
+    /// Returns a [`Source::Host`] for `host`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `host` is empty or contains whitespace, `;`, `,`, or `'`,
+    /// which would corrupt the header.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::Source;
+    ///
+    /// let source = Source::host("https://*.rocket.rs");
+    /// assert_eq!(source.to_string(), "https://*.rocket.rs");
+    /// ```
+    pub fn host<S: Into<Cow<'static, str>>>(host: S) -> Source {
+        let host = host.into();
+        if host.is_empty() || host.contains(|c: char| c.is_whitespace() || ";,'".contains(c)) {
+            panic!("invalid CSP host source: {:?}", host);
+        }
+
+        Source::Host(host)
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::None => "'none'".fmt(f),
+            Source::This => "'self'".fmt(f),
+            Source::Any => "*".fmt(f),
+            Source::UnsafeInline => "'unsafe-inline'".fmt(f),
+            Source::UnsafeEval => "'unsafe-eval'".fmt(f),
+            Source::StrictDynamic => "'strict-dynamic'".fmt(f),
+            Source::Nonce => "'nonce'".fmt(f),
+            Source::Sha256(hash) => write!(f, "'sha256-{}'", hash),
+            Source::Sha384(hash) => write!(f, "'sha384-{}'", hash),
+            Source::Sha512(hash) => write!(f, "'sha512-{}'", hash),
+            Source::Scheme(scheme) => write!(f, "{}:", scheme),
+            Source::Host(host) => host.fmt(f),
+        }
+    }
+}
+
+/// A request guard for the random, per-request nonce rendered into the [`Csp`]
+/// header in place of [`Source::Nonce`].
+///
+/// The same nonce is returned for every use of the guard in a request, and is
+/// available whether or not a `Csp` policy using it is enabled. A `Nonce`
+/// renders (via `Display`) and serializes as a plain string, so it can be
+/// passed directly to templates.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::response::content::Html;
+/// use rocket::shield::Nonce;
+///
+/// #[get("/")]
+/// fn index(nonce: Nonce) -> Html<String> {
+///     Html(format!(r#"<script nonce="{}">console.log("hi!")</script>"#, nonce))
+/// }
+/// ```
+///
+/// With `rocket_dyn_templates`, a template can embed it as `<script
+/// nonce="{{ nonce }}">` when rendered with:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # use rocket::serde::Serialize;
+/// # use rocket::shield::Nonce;
+/// # #[derive(Serialize)] #[serde(crate = "rocket::serde")]
+/// # struct Context { nonce: Nonce }
+/// #[get("/")]
+/// fn index(nonce: Nonce) -> String {
+///     // Template::render("index", Context { nonce })
+/// #   let _ = Context { nonce }; String::new()
+/// }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Nonce(String);
+
+impl Nonce {
+    /// The nonce for `req`, generated on first use.
+    pub(crate) fn of<'r>(req: &'r Request<'_>) -> &'r Nonce {
+        req.local_cache(|| Nonce(crate::ext::random_token(22)))
+    }
+
+    /// Returns the nonce as a string slice.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::shield::Nonce;
+    ///
+    /// #[get("/")]
+    /// fn index(nonce: Nonce) -> String {
+    ///     format!("<style nonce=\"{}\"></style>", nonce.as_str())
+    /// }
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Nonce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for Nonce {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Nonce {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        Outcome::Success(Nonce::of(req).clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let header = Header::from(&Csp::default());
+        assert_eq!(header.name(), "Content-Security-Policy");
+        assert_eq!(header.value(), "default-src 'self'; object-src 'none'; base-uri 'self'");
+
+        let csp = Csp::new()
+            .directive(Directive::ScriptSrc, [Source::This, Source::Nonce, Source::StrictDynamic])
+            .directive(Directive::ImgSrc, vec![Source::scheme("data:"), Source::host("*.rs")])
+            .directive(Directive::StyleSrc, Source::Sha256("abc=".into()))
+            .directive(Directive::FrameAncestors, [])
+            .upgrade_insecure_requests(true)
+            .report_to("csp");
+
+        let nonce = Nonce("n0nce".into());
+        let header = csp.render(CspReportOnly::NAME, Some(&nonce));
+        assert_eq!(header.name(), "Content-Security-Policy-Report-Only");
+        assert_eq!(header.value(), "script-src 'self' 'nonce-n0nce' 'strict-dynamic'; \
+            img-src data: *.rs; style-src 'sha256-abc='; frame-ancestors; \
+            upgrade-insecure-requests; report-to csp");
+
+        let header = Header::from(&CspReportOnly::from(csp));
+        assert_eq!(header.name(), "Content-Security-Policy-Report-Only");
+        assert!(header.value().starts_with("script-src 'self' 'strict-dynamic'; img-src"));
+    }
+
+    #[test]
+    fn test_uses_nonce() {
+        assert!(!Csp::default().uses_nonce());
+        assert!(Csp::default().directive(Directive::StyleSrc, Source::Nonce).uses_nonce());
+        assert!(!Csp::default()
+            .directive(Directive::StyleSrc, Source::Nonce)
+            .directive(Directive::StyleSrc, Source::This)
+            .uses_nonce());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bad_host() {
+        Source::host("rocket.rs; script-src *");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bad_scheme() {
+        Source::scheme("1http");
+    }
+}
@@ -10,16 +10,18 @@
 //!
 //! # Supported Headers
 //!
-//! | HTTP Header                 | Description                            | Policy         | Default? |
-//! | --------------------------- | -------------------------------------- | -------------- | -------- |
-//! | [X-XSS-Protection]          | Prevents some reflected XSS attacks.   | [`XssFilter`]  | ✗        |
-//! | [X-Content-Type-Options]    | Prevents client sniffing of MIME type. | [`NoSniff`]    | ✔        |
-//! | [X-Frame-Options]           | Prevents [clickjacking].               | [`Frame`]      | ✔        |
-//! | [Strict-Transport-Security] | Enforces strict use of HTTPS.          | [`Hsts`]       | ?        |
-//! | [Expect-CT]                 | Enables certificate transparency.      | [`ExpectCt`]   | ✗        |
-//! | [Referrer-Policy]           | Enables referrer policy.               | [`Referrer`]   | ✗        |
-//! | [X-DNS-Prefetch-Control]    | Controls browser DNS prefetching.      | [`Prefetch`]   | ✗        |
-//! | [Permissions-Policy]        | Allows or block browser features.      | [`Permission`] | ✔        |
+//! | HTTP Header                           | Description                            | Policy            | Default? |
+//! | ------------------------------------- | -------------------------------------- | ----------------- | -------- |
+//! | [X-XSS-Protection]                    | Prevents some reflected XSS attacks.   | [`XssFilter`]     | ✗        |
+//! | [X-Content-Type-Options]              | Prevents client sniffing of MIME type. | [`NoSniff`]       | ✔        |
+//! | [X-Frame-Options]                     | Prevents [clickjacking].               | [`Frame`]         | ✔        |
+//! | [Strict-Transport-Security]           | Enforces strict use of HTTPS.          | [`Hsts`]          | ?        |
+//! | [Expect-CT]                           | Enables certificate transparency.      | [`ExpectCt`]      | ✗        |
+//! | [Referrer-Policy]                     | Enables referrer policy.               | [`Referrer`]      | ✗        |
+//! | [X-DNS-Prefetch-Control]              | Controls browser DNS prefetching.      | [`Prefetch`]      | ✗        |
+//! | [Permissions-Policy]                  | Allows or block browser features.      | [`Permission`]    | ✔        |
+//! | [Content-Security-Policy]             | Restricts the resources a page loads.  | [`Csp`]           | ✗        |
+//! | [Content-Security-Policy-Report-Only] | Reports on a resource policy.          | [`CspReportOnly`] | ✗        |
 //!
 //! <small>? If TLS is enabled in a non-debug profile, HSTS is automatically
 //! enabled with its default policy and a warning is logged at liftoff.</small>
@@ -33,6 +35,8 @@
 //! [X-DNS-Prefetch-Control]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/X-DNS-Prefetch-Control
 //! [clickjacking]: https://en.wikipedia.org/wiki/Clickjacking
 //! [Permissions-Policy]: https://github.com/w3c/webappsec-permissions-policy/blob/a45df7b237e2a85e1909d7f226ca4eb4ce5095ba/permissions-policy-explainer.md
+//! [Content-Security-Policy]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy
+//! [Content-Security-Policy-Report-Only]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy-Report-Only
 //!
 //! [`XssFilter`]: self::XssFilter
 //! [`NoSniff`]: self::NoSniff
@@ -41,6 +45,8 @@
 //! [`ExpectCt`]: self::ExpectCt
 //! [`Referrer`]: self::Referrer
 //! [`Prefetch`]: self::Prefetch
+//! [`Csp`]: self::Csp
+//! [`CspReportOnly`]: self::CspReportOnly
 //!
 //! # Usage
 //!
@@ -81,6 +87,20 @@
 //!     .disable::<NoSniff>();
 //! ```
 //!
+//! To change the policies applied to the responses of particular routes,
+//! wrap their responders in an [`Override`]:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::shield::{Override, Frame};
+//!
+//! // Allow this page, and only this page, to be embedded in iframes.
+//! #[get("/embed")]
+//! fn embed() -> Override<&'static str> {
+//!     Override::new("Embed me!").disable::<Frame>()
+//! }
+//! ```
+//!
 //! # FAQ
 //!
 //! * **Which policies should I choose?**
@@ -99,6 +119,10 @@
 
 mod shield;
 mod policy;
+mod csp;
+mod overrides;
 
 pub use self::shield::Shield;
 pub use self::policy::*;
+pub use self::csp::{Csp, CspReportOnly, Directive, Source, Nonce};
+pub use self::overrides::Override;
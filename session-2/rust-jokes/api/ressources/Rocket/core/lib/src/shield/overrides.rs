@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+
+use crate::Request;
+use crate::response::{self, Responder};
+use crate::http::uncased::UncasedStr;
+use crate::shield::{Policy, SubPolicy};
+
+/// Policies enabled, when `Some`, or disabled, when `None`, keyed by name.
+pub(crate) type Policies = HashMap<&'static UncasedStr, Option<Box<dyn SubPolicy>>>;
+
+/// The overrides set by an [`Override`] responder for a request, if any.
+#[derive(Default)]
+struct Overrides(Mutex<Option<Policies>>);
+
+/// Takes the overrides set for `req`, if any.
+pub(crate) fn take(req: &Request<'_>) -> Option<Policies> {
+    req.local_cache(Overrides::default).0.lock().take()
+}
+
+/// A responder that overrides the [`Shield`] policies applied to its response.
+///
+/// `Shield` applies the same set of policies to every response. Wrapping a
+/// responder in an `Override` adds or replaces policies via
+/// [`enable()`](Override::enable()) and removes them via
+/// [`disable()`](Override::disable()) for the wrapped response only. Policies
+/// not mentioned are applied as usual. An `Override` has no effect if `Shield`
+/// isn't attached.
+///
+/// # Example
+///
+/// Allow a page to be embedded in iframes by any origin, and grant it access
+/// to the camera:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::response::content::Html;
+/// use rocket::shield::{Override, Frame, Permission, Feature, Allow};
+///
+/// #[get("/widget")]
+/// fn widget() -> Override<Html<&'static str>> {
+///     Override::new(Html("<video id='camera'></video>"))
+///         .disable::<Frame>()
+///         .enable(Permission::allowed(Feature::Camera, Allow::This))
+/// }
+/// ```
+///
+/// [`Shield`]: crate::shield::Shield
+pub struct Override<R> {
+    responder: R,
+    policies: Policies,
+}
+
+impl<R> Override<R> {
+    /// Wraps `responder` without overriding any policies.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::Override;
+    ///
+    /// let response = Override::new("Hello, world!");
+    /// ```
+    pub fn new(responder: R) -> Self {
+        Override { responder, policies: HashMap::new() }
+    }
+
+    /// Enables `policy` for the response, replacing the policy of the same
+    /// type `Shield` would otherwise apply, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::{Override, Frame};
+    ///
+    /// let response = Override::new("Hello, world!").enable(Frame::Deny);
+    /// ```
+    pub fn enable<P: Policy>(mut self, policy: P) -> Self {
+        self.policies.insert(P::NAME.into(), Some(Box::new(policy)));
+        self
+    }
+
+    /// Disables the policy `P` for the response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::{Override, Frame};
+    ///
+    /// let response = Override::new("Hello, world!").disable::<Frame>();
+    /// ```
+    pub fn disable<P: Policy>(mut self) -> Self {
+        self.policies.insert(P::NAME.into(), None);
+        self
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Override<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let response = self.responder.respond_to(req)?;
+        *req.local_cache(Overrides::default).0.lock() = Some(self.policies);
+        Ok(response)
+    }
+}
@@ -1,6 +1,7 @@
 //! Module containing the [`Policy`] trait and types that implement it.
 
 use std::fmt;
+use std::any::Any;
 use std::borrow::Cow;
 
 use indexmap::IndexMap;
@@ -8,6 +9,7 @@ use rocket_http::{ext::IntoCollection, private::SmallVec};
 use time::Duration;
 
 use crate::http::{Header, uri::Absolute, uncased::{UncasedStr, Uncased}};
+use crate::shield::{Csp, CspReportOnly};
 
 /// Trait implemented by security and privacy policy headers.
 ///
@@ -68,6 +70,7 @@ pub trait Policy: Default + Send + Sync + 'static {
 pub(crate) trait SubPolicy: Send + Sync {
     fn name(&self) -> &'static UncasedStr;
     fn header(&self) -> Header<'static>;
+    fn as_any(&self) -> &dyn Any;
 }
 
 impl<P: Policy> SubPolicy for P {
@@ -78,6 +81,10 @@ impl<P: Policy> SubPolicy for P {
     fn header(&self) -> Header<'static> {
         Policy::header(self)
     }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 macro_rules! impl_policy {
@@ -101,6 +108,8 @@ impl_policy!(ExpectCt, "Expect-CT");
 impl_policy!(Referrer, "Referrer-Policy");
 impl_policy!(Prefetch, "X-DNS-Prefetch-Control");
 impl_policy!(Permission, "Permissions-Policy");
+impl_policy!(Csp, "Content-Security-Policy");
+impl_policy!(CspReportOnly, "Content-Security-Policy-Report-Only");
 
 /// The [Referrer-Policy] header: controls the value set by the browser for the
 /// [Referer] header.
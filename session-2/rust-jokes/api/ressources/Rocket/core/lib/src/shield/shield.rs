@@ -9,6 +9,7 @@ use crate::fairing::{Fairing, Info, Kind};
 use crate::http::{Header, uncased::UncasedStr};
 use crate::log::PaintExt;
 use crate::shield::*;
+use crate::shield::overrides;
 
 /// A [`Fairing`] that injects browser security and privacy headers into all
 /// outgoing responses.
@@ -173,9 +174,17 @@ impl Shield {
         self.policies.contains_key(UncasedStr::new(P::NAME))
     }
 
+    /// The enabled `Csp` and `CspReportOnly` policies that use nonces, with
+    /// their header names. Their headers are rendered per request rather than
+    /// with the rest in [`Shield::headers()`].
+    fn nonced_csps(&self) -> impl Iterator<Item = (&'static str, &Csp)> {
+        self.policies.values().filter_map(|policy| Csp::nonced(&**policy))
+    }
+
     fn headers(&self) -> &[Header<'static>] {
         self.rendered.get_or_set(|| {
             let mut headers: Vec<_> = self.policies.values()
+                .filter(|p| Csp::nonced(&***p).is_none())
                 .map(|p| p.header())
                 .collect();
 
@@ -188,6 +197,15 @@ impl Shield {
     }
 }
 
+/// Renders `policy`'s header for `req`, with the request's nonce if `policy`
+/// is a `Csp` or `CspReportOnly` that uses one.
+fn render(policy: &dyn SubPolicy, req: &Request<'_>) -> Header<'static> {
+    match Csp::nonced(policy) {
+        Some((name, csp)) => csp.render(name, Some(Nonce::of(req))),
+        None => policy.header(),
+    }
+}
+
 #[crate::async_trait]
 impl Fairing for Shield {
     fn info(&self) -> Info {
@@ -206,13 +224,19 @@ impl Fairing for Shield {
             self.force_hsts.store(true, Ordering::Release);
         }
 
-        if !self.headers().is_empty() {
+        if !self.headers().is_empty() || self.nonced_csps().next().is_some() {
             info!("{}{}:", Paint::emoji("🛡️ "), Paint::magenta("Shield"));
 
             for header in self.headers() {
                 info_!("{}: {}", header.name(), Paint::default(header.value()));
             }
 
+            for (name, csp) in self.nonced_csps() {
+                let header = csp.render(name, None);
+                info_!("{}: {} {}", header.name(), Paint::default(header.value()),
+                    Paint::default("(with nonces)").italic());
+            }
+
             if force_hsts {
                 warn_!("Detected TLS-enabled liftoff without enabling HSTS.");
                 warn_!("Shield has enabled a default HSTS policy.");
@@ -221,17 +245,37 @@ impl Fairing for Shield {
         }
     }
 
-    async fn on_response<'r>(&self, _: &'r Request<'_>, response: &mut Response<'r>) {
-        // Set all of the headers in `self.policies` in `response` as long as
-        // the header is not already in the response.
-        for header in self.headers() {
+    async fn on_response<'r>(&self, req: &'r Request<'_>, response: &mut Response<'r>) {
+        // Policies overridden for this response replace those in `self`.
+        let headers: Vec<Header<'static>> = match overrides::take(req) {
+            Some(overrides) => {
+                let hsts = self.force_hsts.load(Ordering::Acquire).then(Hsts::default);
+                self.policies.values()
+                    .map(|policy| &**policy)
+                    .chain(hsts.as_ref().map(|hsts| hsts as &dyn SubPolicy))
+                    .filter(|policy| !overrides.contains_key(policy.name()))
+                    .chain(overrides.values().flatten().map(|policy| &**policy))
+                    .map(|policy| render(policy, req))
+                    .collect()
+            }
+            None => {
+                let nonced = self.nonced_csps()
+                    .map(|(name, csp)| csp.render(name, Some(Nonce::of(req))));
+
+                self.headers().iter().cloned().chain(nonced).collect()
+            }
+        };
+
+        // Set all of the headers in `headers` in `response` as long as the
+        // header is not already in the response.
+        for header in headers {
             if response.headers().contains(header.name()) {
                 warn!("Shield: response contains a '{}' header.", header.name());
                 warn_!("Refusing to overwrite existing header.");
                 continue
             }
 
-            response.set_header(header.clone());
+            response.set_header(header);
         }
     }
 }
@@ -1,6 +1,7 @@
 use std::future::Future;
 use std::task::{Context, Poll};
 use std::pin::Pin;
+use std::time::Duration;
 
 use futures::FutureExt;
 
@@ -89,6 +90,25 @@ impl Shutdown {
     pub fn notify(self) {
         self.0.trip();
     }
+
+    /// Spawns a task that awaits `task()` every `period`, starting right away,
+    /// until shutdown is requested. Used to purge expired sessions and uploads.
+    #[cfg_attr(not(any(feature = "sessions", feature = "upload")), allow(dead_code))]
+    pub(crate) fn spawn_periodic<F, Fut>(self, period: Duration, mut task: F)
+        where F: FnMut() -> Fut + Send + 'static,
+              Fut: Future<Output = ()> + Send + 'static
+    {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            let mut shutdown = self;
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => task().await,
+                    _ = &mut shutdown => break,
+                }
+            }
+        });
+    }
 }
 
 #[crate::async_trait]
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// The upload configuration: where uploads are accepted and kept, and when
+/// abandoned uploads expire.
+///
+/// Upload data is kept in `dir`, so an upload can resume after a restart if
+/// the [`UploadStore`](crate::upload::UploadStore) keeps its records too. The
+/// [`Uploads`](crate::upload::Uploads) fairing periodically purges uploads
+/// idle for longer than `expiration`.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::upload::Config;
+///
+/// let figment = rocket::Config::figment()
+///     .merge(("upload.path", "/files"))
+///     .merge(("upload.expiration", 3600));
+///
+/// let config: Config = figment.extract_inner("upload").unwrap();
+/// assert_eq!(config.path, "/files");
+/// assert_eq!(config.expiration, 3600);
+/// assert!(config.dir.is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// The path uploads are created at and served under. **(default:
+    /// `"/uploads"`)**
+    pub path: String,
+    /// The directory the data of uploads in progress is kept in. **(default:
+    /// `uploads` in [`temp_dir`](crate::Config::temp_dir))**
+    pub dir: Option<PathBuf>,
+    /// Seconds after the last chunk was received that an unfinished upload
+    /// expires, `0` to disable. **(default: `86400`)**
+    pub expiration: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            path: "/uploads".into(),
+            dir: None,
+            expiration: 86400,
+        }
+    }
+}
@@ -0,0 +1,457 @@
+use std::io;
+use std::sync::Arc;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use parking_lot::Mutex;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+use crate::{Rocket, Build, Orbit, Request, Response, Data, Route};
+use crate::data::{ByteUnit, Limits};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::http::{Header, Method, Status};
+use crate::response::{self, Responder};
+use crate::route::{self, Handler, Outcome};
+use crate::upload::{Config, Record, UploadStore};
+
+/// How often expired uploads are purged.
+const PURGE_INTERVAL: Duration = Duration::from_secs(300);
+
+/// The version of the tus protocol spoken.
+const TUS_VERSION: &str = "1.0.0";
+
+/// The supported `Upload-Checksum` algorithms.
+const CHECKSUM_ALGORITHMS: &str = "sha1,sha256";
+
+/// The status of a chunk whose checksum doesn't match, as defined by tus.
+const CHECKSUM_MISMATCH: Status = Status::new(460);
+
+/// Tracks uploads and writes their data. Managed by the [`Uploads`] fairing.
+pub(crate) struct Manager {
+    pub store: Arc<dyn UploadStore>,
+    pub config: Config,
+    /// Ids of uploads being written to or finalized.
+    busy: Mutex<HashSet<String>>,
+}
+
+/// Marks an upload busy until dropped.
+pub(crate) struct Busy<'a> {
+    manager: &'a Manager,
+    id: &'a str,
+}
+
+impl Drop for Busy<'_> {
+    fn drop(&mut self) {
+        self.manager.busy.lock().remove(self.id);
+    }
+}
+
+impl Manager {
+    /// The directory upload data is kept in.
+    pub fn dir(&self, config: &crate::Config) -> PathBuf {
+        self.config.dir.clone().unwrap_or_else(|| config.temp_dir.join("uploads"))
+    }
+
+    /// The path to the data of upload `id`, or `None` if `id` can't be an
+    /// upload id. Ids are generated by Rocket but are checked anyway since
+    /// they become file names.
+    pub fn path(&self, config: &crate::Config, id: &str) -> Option<PathBuf> {
+        match !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            true => Some(self.dir(config).join(id)),
+            false => None,
+        }
+    }
+
+    /// Marks upload `id` busy, or returns `None` if it already is.
+    pub fn lock<'a>(&'a self, id: &'a str) -> Option<Busy<'a>> {
+        match self.busy.lock().insert(id.to_string()) {
+            true => Some(Busy { manager: self, id }),
+            false => None,
+        }
+    }
+
+    /// Handles a creation request: `POST` with an `Upload-Length`.
+    async fn create(&self, req: &Request<'_>) -> io::Result<Reply> {
+        let length = match header::<u64>(req, "Upload-Length") {
+            Some(length) => length,
+            None => return Ok(Reply::new(Status::BadRequest)),
+        };
+
+        let limit = req.limits().get("upload").unwrap_or(Limits::UPLOAD);
+        if length > limit.as_u64() {
+            return Ok(Reply::new(Status::PayloadTooLarge));
+        }
+
+        let id = crate::ext::random_token(32);
+
+        let config = req.rocket().config();
+        tokio::fs::create_dir_all(self.dir(config)).await?;
+        File::create(self.dir(config).join(&id)).await?;
+
+        let metadata = req.headers().get_one("Upload-Metadata").map(|m| m.to_string());
+        let record = Record { length, offset: 0, metadata, updated: SystemTime::now() };
+        self.store.save(&id, record).await?;
+
+        let location = format!("{}/{}", self.config.path.trim_end_matches('/'), id);
+        Ok(Reply::new(Status::Created).header("Location", location))
+    }
+
+    /// Handles an offset request: `HEAD` of an upload.
+    async fn status(&self, id: &str) -> io::Result<Reply> {
+        let record = match self.store.load(id).await? {
+            Some(record) => record,
+            None => return Ok(Reply::new(Status::NotFound)),
+        };
+
+        let mut reply = Reply::new(Status::Ok)
+            .header("Upload-Offset", record.offset.to_string())
+            .header("Upload-Length", record.length.to_string())
+            .header("Cache-Control", "no-store");
+
+        if let Some(metadata) = record.metadata {
+            reply = reply.header("Upload-Metadata", metadata);
+        }
+
+        Ok(reply)
+    }
+
+    /// Handles a chunk: `PATCH` of an upload with an `Upload-Offset` and,
+    /// optionally, an `Upload-Checksum`.
+    async fn append(&self, req: &Request<'_>, id: &str, data: Data<'_>) -> io::Result<Reply> {
+        match req.content_type() {
+            Some(ct) if ct.top() == "application" && ct.sub() == "offset+octet-stream" => {},
+            _ => return Ok(Reply::new(Status::UnsupportedMediaType)),
+        }
+
+        let (offset, mut checksum) = match header::<u64>(req, "Upload-Offset") {
+            Some(offset) => match req.headers().get_one("Upload-Checksum") {
+                Some(header) => match Checksum::parse(header) {
+                    Some(checksum) => (offset, Some(checksum)),
+                    None => return Ok(Reply::new(Status::BadRequest)),
+                },
+                None => (offset, None),
+            },
+            None => return Ok(Reply::new(Status::BadRequest)),
+        };
+
+        let config = req.rocket().config();
+        let (path, _busy) = match (self.path(config, id), self.lock(id)) {
+            (Some(path), Some(busy)) => (path, busy),
+            (None, _) => return Ok(Reply::new(Status::NotFound)),
+            (_, None) => return Ok(Reply::new(Status::Conflict)),
+        };
+
+        let mut record = match self.store.load(id).await? {
+            Some(record) if record.offset == offset => record,
+            Some(_) => return Ok(Reply::new(Status::Conflict)),
+            None => return Ok(Reply::new(Status::NotFound)),
+        };
+
+        // Read one byte more than may be written to detect oversized chunks.
+        let remaining = record.length - record.offset;
+        let mut stream = data.open(ByteUnit::from(remaining + 1));
+        let mut file = OpenOptions::new().write(true).open(&path).await?;
+        file.seek(io::SeekFrom::Start(offset)).await?;
+
+        let (mut written, mut buffer, mut failed) = (0, vec![0; 16 * 1024], None);
+        loop {
+            let n = match stream.read(&mut buffer).await {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) => { failed = Some(e); break; }
+            };
+
+            if written + n as u64 > remaining {
+                file.set_len(offset).await?;
+                return Ok(Reply::new(Status::PayloadTooLarge));
+            }
+
+            file.write_all(&buffer[..n]).await?;
+            if let Some(checksum) = checksum.as_mut() {
+                checksum.update(&buffer[..n]);
+            }
+
+            written += n as u64;
+        }
+
+        file.flush().await?;
+        match (checksum.map(Checksum::verify), &failed) {
+            // A partial chunk can't be checked, so none of it is kept.
+            (Some(_), Some(_)) => {
+                file.set_len(offset).await?;
+                return Ok(Reply::new(Status::BadRequest));
+            }
+            (Some(false), None) => {
+                file.set_len(offset).await?;
+                return Ok(Reply::new(CHECKSUM_MISMATCH));
+            }
+            _ => {}
+        }
+
+        // Without a checksum, what was received of an interrupted chunk is
+        // kept so that the client can resume from there.
+        record.offset += written;
+        record.updated = SystemTime::now();
+        let new_offset = record.offset;
+        self.store.save(id, record).await?;
+        if let Some(e) = failed {
+            warn_!("Upload interrupted after {} bytes: {}", written, e);
+            return Ok(Reply::new(Status::BadRequest));
+        }
+
+        Ok(Reply::new(Status::NoContent).header("Upload-Offset", new_offset.to_string()))
+    }
+
+    /// Handles a termination request: `DELETE` of an upload.
+    async fn terminate(&self, req: &Request<'_>, id: &str) -> io::Result<Reply> {
+        let (path, _busy) = match (self.path(req.rocket().config(), id), self.lock(id)) {
+            (Some(path), Some(busy)) => (path, busy),
+            (None, _) => return Ok(Reply::new(Status::NotFound)),
+            (_, None) => return Ok(Reply::new(Status::Conflict)),
+        };
+
+        if self.store.load(id).await?.is_none() {
+            return Ok(Reply::new(Status::NotFound));
+        }
+
+        self.store.remove(id).await?;
+        remove_file(path).await?;
+        Ok(Reply::new(Status::NoContent))
+    }
+}
+
+/// Removes the file at `path`. Succeeds if there is no such file.
+pub(crate) async fn remove_file(path: PathBuf) -> io::Result<()> {
+    match tokio::fs::remove_file(path).await {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+/// Returns the value of the header `name` in `req` parsed as a `T`.
+fn header<T: std::str::FromStr>(req: &Request<'_>, name: &str) -> Option<T> {
+    req.headers().get_one(name).and_then(|value| value.trim().parse().ok())
+}
+
+/// An `Upload-Checksum` being verified.
+enum Checksum {
+    Sha1(Sha1, Vec<u8>),
+    Sha256(Sha256, Vec<u8>),
+}
+
+impl Checksum {
+    /// Parses an `Upload-Checksum` header: an algorithm and a base64 digest.
+    fn parse(header: &str) -> Option<Checksum> {
+        let mut parts = header.trim().splitn(2, ' ');
+        let (algorithm, digest) = (parts.next()?, base64::decode(parts.next()?).ok()?);
+        match algorithm {
+            "sha1" => Some(Checksum::Sha1(Sha1::new(), digest)),
+            "sha256" => Some(Checksum::Sha256(Sha256::new(), digest)),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Checksum::Sha1(hasher, _) => hasher.update(bytes),
+            Checksum::Sha256(hasher, _) => hasher.update(bytes),
+        }
+    }
+
+    fn verify(self) -> bool {
+        match self {
+            Checksum::Sha1(hasher, digest) => hasher.finalize()[..] == digest[..],
+            Checksum::Sha256(hasher, digest) => hasher.finalize()[..] == digest[..],
+        }
+    }
+}
+
+/// A tus response: a status and headers, always with `Tus-Resumable`.
+struct Reply {
+    status: Status,
+    headers: Vec<Header<'static>>,
+}
+
+impl Reply {
+    fn new(status: Status) -> Self {
+        Reply { status, headers: vec![Header::new("Tus-Resumable", TUS_VERSION)] }
+    }
+
+    fn header<V>(mut self, name: &'static str, value: V) -> Self
+        where V: Into<std::borrow::Cow<'static, str>>
+    {
+        self.headers.push(Header::new(name, value));
+        self
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Reply {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = Response::build();
+        response.status(self.status);
+        for header in self.headers {
+            response.header(header);
+        }
+
+        response.ok()
+    }
+}
+
+/// The tus endpoints.
+#[derive(Clone, Copy)]
+enum Endpoint {
+    Options,
+    Create,
+    Status,
+    Append,
+    Terminate,
+}
+
+#[crate::async_trait]
+impl Handler for Endpoint {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> route::Outcome<'r> {
+        let manager = match req.rocket().state::<Manager>() {
+            Some(manager) => manager,
+            None => return Outcome::Failure(Status::InternalServerError),
+        };
+
+        let version = req.headers().get_one("Tus-Resumable");
+        if !matches!(self, Endpoint::Options) && version != Some(TUS_VERSION) {
+            let reply = Reply::new(Status::PreconditionFailed).header("Tus-Version", TUS_VERSION);
+            return Outcome::from(req, reply);
+        }
+
+        let id = req.uri().path().segments().last().unwrap_or("");
+        let reply = match self {
+            Endpoint::Options => {
+                let limit = req.limits().get("upload").unwrap_or(Limits::UPLOAD);
+                Ok(Reply::new(Status::NoContent)
+                    .header("Tus-Version", TUS_VERSION)
+                    .header("Tus-Extension", "creation,termination,checksum")
+                    .header("Tus-Max-Size", limit.as_u64().to_string())
+                    .header("Tus-Checksum-Algorithm", CHECKSUM_ALGORITHMS))
+            }
+            Endpoint::Create => manager.create(req).await,
+            Endpoint::Status => manager.status(id).await,
+            Endpoint::Append => manager.append(req, id, data).await,
+            Endpoint::Terminate => manager.terminate(req, id).await,
+        };
+
+        match reply {
+            Ok(reply) => Outcome::from(req, reply),
+            Err(e) => {
+                error_!("Upload request failed: {}", e);
+                Outcome::Failure(Status::InternalServerError)
+            }
+        }
+    }
+}
+
+/// A [`Fairing`] that accepts resumable uploads, tracking them in an
+/// [`UploadStore`].
+///
+/// See the [module docs](crate::upload) for usage, configuration, and protocol
+/// details.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::upload::{Uploads, MemoryStore};
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(Uploads::new(MemoryStore::new()))
+/// }
+/// ```
+pub struct Uploads {
+    store: Arc<dyn UploadStore>,
+}
+
+impl Uploads {
+    /// Returns an `Uploads` fairing tracking uploads in `store`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::upload::{Uploads, MemoryStore};
+    ///
+    /// let uploads = Uploads::new(MemoryStore::new());
+    /// ```
+    pub fn new<S: UploadStore>(store: S) -> Self {
+        Uploads { store: Arc::new(store) }
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Uploads {
+    fn info(&self) -> Info {
+        Info {
+            name: "Uploads",
+            kind: Kind::Ignite | Kind::Liftoff | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let config = match crate::config::extract_or_default::<Config>(&rocket, "upload") {
+            Some(config) => config,
+            None => return Err(rocket),
+        };
+
+        let base = config.path.trim_end_matches('/');
+        let upload = format!("{}/<id>", base);
+        let base = if base.is_empty() { "/" } else { base };
+        let routes = vec![
+            (Method::Options, base, Endpoint::Options),
+            (Method::Post, base, Endpoint::Create),
+            (Method::Head, &upload, Endpoint::Status),
+            (Method::Patch, &upload, Endpoint::Append),
+            (Method::Delete, &upload, Endpoint::Terminate),
+        ];
+
+        let routes: Vec<_> = routes.into_iter()
+            .map(|(method, path, endpoint)| {
+                let mut route = Route::new(method, path, endpoint);
+                route.name = Some("Uploads".into());
+                route
+            })
+            .collect();
+
+        let busy = Mutex::new(HashSet::new());
+        let manager = Manager { store: self.store.clone(), config, busy };
+        Ok(rocket.manage(manager).mount("/", routes))
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let manager = match rocket.state::<Manager>() {
+            Some(manager) if manager.config.expiration != 0 => manager,
+            _ => return,
+        };
+
+        let store = manager.store.clone();
+        let dir = manager.dir(rocket.config());
+        let expiration = Duration::from_secs(manager.config.expiration);
+        rocket.shutdown().spawn_periodic(PURGE_INTERVAL, move || {
+            let (store, dir) = (store.clone(), dir.clone());
+            async move {
+                let now = SystemTime::now();
+                let expired = |record: &Record| {
+                    now.duration_since(record.updated).unwrap_or_default() > expiration
+                };
+
+                match store.purge(&expired).await {
+                    Ok(ids) => for id in ids {
+                        if let Err(e) = remove_file(dir.join(id)).await {
+                            error!("failed to remove expired upload: {}", e);
+                        }
+                    },
+                    Err(e) => error!("failed to purge expired uploads: {}", e),
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,117 @@
+//! Resumable uploads over the [tus] protocol, with pluggable state stores.
+//!
+//! Attaching the [`Uploads`] fairing mounts endpoints that accept large files
+//! in chunks, so that a client whose connection fails halfway through an
+//! upload can resume it rather than start over. Uploads are tracked in an
+//! [`UploadStore`] and their data is written to disk as it arrives. Once an
+//! upload is complete, a handler takes it as a [`TempFile`] with the
+//! [`Uploaded`] request guard.
+//!
+//! Rocket provides a [`MemoryStore`]. Other backends can be used by
+//! implementing [`UploadStore`].
+//!
+//! [tus]: https://tus.io/protocols/resumable-upload.html
+//! [`TempFile`]: crate::fs::TempFile
+//!
+//! # Enabling
+//!
+//! This module is only available when the `upload` feature is enabled:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket = { version = "0.5.0-rc.1", features = ["upload"] }
+//! ```
+//!
+//! # Usage
+//!
+//! Attach the fairing and take finished uploads in handlers:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::http::Status;
+//! use rocket::upload::{Uploads, Uploaded, MemoryStore};
+//!
+//! #[post("/videos/<id>")]
+//! async fn publish(id: &str, uploads: Uploaded<'_>) -> Status {
+//!     match uploads.take(id).await {
+//!         Ok(Some(mut file)) => match file.persist_to(format!("/srv/videos/{}", id)).await {
+//!             Ok(()) => Status::Created,
+//!             Err(_) => Status::InternalServerError,
+//!         },
+//!         Ok(None) => Status::NotFound,
+//!         Err(_) => Status::InternalServerError,
+//!     }
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![publish])
+//!         .attach(Uploads::new(MemoryStore::new()))
+//! }
+//! ```
+//!
+//! A client creates an upload with `POST /uploads`, sends its data with
+//! `PATCH /uploads/<id>` requests, then asks the application to publish it
+//! with `POST /videos/<id>`.
+//!
+//! # Configuration
+//!
+//! The path uploads are served under, the directory their data is kept in, and
+//! the number of seconds after which abandoned uploads expire are read from the
+//! `upload` configuration parameter into a [`Config`]. The defaults are:
+//!
+//! ```toml
+//! [default.upload]
+//! path = "/uploads"
+//! # dir = "/var/lib/app/uploads" (default: `uploads` in `temp_dir`)
+//! expiration = 86400
+//! ```
+//!
+//! The maximum length of an upload is the `upload` [limit](crate::data::Limits),
+//! 1GiB by default:
+//!
+//! ```toml
+//! [default.limits]
+//! upload = "4GiB"
+//! ```
+//!
+//! # Protocol
+//!
+//! The fairing implements version 1.0.0 of the tus core protocol with the
+//! `creation`, `termination`, and `checksum` extensions. With the default
+//! path, it handles:
+//!
+//!   * `OPTIONS /uploads`: advertises the supported version, extensions,
+//!     maximum size, and checksum algorithms.
+//!   * `POST /uploads`: creates an upload of `Upload-Length` bytes with
+//!     optional `Upload-Metadata`, responding `201 Created` with its
+//!     `Location`. Fails with `413 Payload Too Large` if the length exceeds
+//!     the `upload` limit.
+//!   * `HEAD /uploads/<id>`: responds with the upload's `Upload-Offset`.
+//!   * `PATCH /uploads/<id>`: appends an `application/offset+octet-stream`
+//!     body at `Upload-Offset`, responding `204 No Content` with the new
+//!     offset. Fails with `409 Conflict` if the offset isn't the upload's
+//!     current one or if the upload is being written to by another request.
+//!   * `DELETE /uploads/<id>`: removes the upload.
+//!
+//! All requests but `OPTIONS` must carry a `Tus-Resumable: 1.0.0` header.
+//!
+//! A chunk with an `Upload-Checksum` using the `sha1` or `sha256` algorithm is
+//! discarded, and the request fails with a status of `460`, unless its digest
+//! matches. Chunks without a checksum are kept even if they're interrupted, so
+//! that the client can resume from the last byte received. Chunks exceeding
+//! the length of the upload are discarded.
+//!
+//! An incomplete upload that hasn't received data for `expiration` seconds,
+//! or a complete one that hasn't been taken, is removed along with its data.
+
+mod config;
+mod store;
+mod uploaded;
+mod fairing;
+
+pub use self::config::Config;
+pub use self::store::{UploadStore, Record, MemoryStore};
+pub use self::uploaded::Uploaded;
+pub use self::fairing::Uploads;
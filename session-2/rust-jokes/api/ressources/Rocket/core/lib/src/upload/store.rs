@@ -0,0 +1,215 @@
+use std::io;
+use std::sync::Arc;
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+/// The state of an upload: how long it is and how much has been received.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Record {
+    /// The length of the upload in bytes, as declared when it was created.
+    pub length: u64,
+    /// The number of bytes received so far.
+    pub offset: u64,
+    /// The `Upload-Metadata` the upload was created with, if any.
+    pub metadata: Option<String>,
+    /// When the upload was created or last received data.
+    pub updated: SystemTime,
+}
+
+impl Record {
+    /// Returns `true` if every byte of the upload has been received.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::SystemTime;
+    /// use rocket::upload::Record;
+    ///
+    /// let record = Record { length: 4, offset: 4, metadata: None, updated: SystemTime::now() };
+    /// assert!(record.is_complete());
+    /// ```
+    pub fn is_complete(&self) -> bool {
+        self.offset == self.length
+    }
+
+    /// Returns the decoded value of `key` in the upload's metadata, if it is
+    /// present and valid UTF-8. Keys without a value have an empty one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::SystemTime;
+    /// use rocket::upload::Record;
+    ///
+    /// let metadata = Some("filename cGhvdG8ucG5n,private".into());
+    /// let record = Record { length: 4, offset: 0, metadata, updated: SystemTime::now() };
+    /// assert_eq!(record.metadata("filename").unwrap(), "photo.png");
+    /// assert_eq!(record.metadata("private").unwrap(), "");
+    /// assert!(record.metadata("filetype").is_none());
+    /// ```
+    pub fn metadata(&self, key: &str) -> Option<String> {
+        self.metadata.as_deref()?
+            .split(',')
+            .map(|pair| pair.trim().splitn(2, ' '))
+            .find_map(|mut pair| match pair.next() {
+                Some(k) if k == key => Some(pair.next().unwrap_or("")),
+                _ => None,
+            })
+            .and_then(|value| base64::decode(value).ok())
+            .and_then(|value| String::from_utf8(value).ok())
+    }
+}
+
+/// A backend that persists upload [`Record`]s by upload id.
+///
+/// Upload ids are random alphanumeric strings generated by Rocket. The data of
+/// uploads isn't kept in the store but in the [configured
+/// directory](crate::upload::Config::dir). A store need not check for expiry:
+/// the [`Uploads`](crate::upload::Uploads) fairing periodically calls
+/// [`purge()`](UploadStore::purge()) to remove expired records and deletes
+/// their data.
+///
+/// Rocket provides a [`MemoryStore`]. Other backends, such as a database that
+/// lets uploads be resumed across restarts, can be used by implementing this
+/// trait.
+#[crate::async_trait]
+pub trait UploadStore: Send + Sync + 'static {
+    /// Returns the record for upload `id`, or `None` if there is none.
+    async fn load(&self, id: &str) -> io::Result<Option<Record>>;
+
+    /// Stores `record` as upload `id`, replacing any existing record.
+    async fn save(&self, id: &str, record: Record) -> io::Result<()>;
+
+    /// Removes upload `id`. Succeeds if there is no such upload.
+    async fn remove(&self, id: &str) -> io::Result<()>;
+
+    /// Removes every record for which `expired` returns `true`, returning the
+    /// ids of the removed records.
+    async fn purge(
+        &self,
+        expired: &(dyn for<'a> Fn(&'a Record) -> bool + Send + Sync)
+    ) -> io::Result<Vec<String>>;
+}
+
+/// An [`UploadStore`] that keeps upload records in memory.
+///
+/// Records are lost when the application exits, so uploads can't be resumed
+/// across restarts. Clones of a `MemoryStore` share the same records.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::upload::{Uploads, MemoryStore};
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(Uploads::new(MemoryStore::new()))
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore(Arc<Mutex<HashMap<String, Record>>>);
+
+impl MemoryStore {
+    /// Returns an empty `MemoryStore`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::upload::MemoryStore;
+    ///
+    /// let store = MemoryStore::new();
+    /// assert!(store.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        MemoryStore::default()
+    }
+
+    /// Returns the number of stored uploads.
+    pub fn len(&self) -> usize {
+        self.0.lock().len()
+    }
+
+    /// Returns `true` if there are no stored uploads.
+    pub fn is_empty(&self) -> bool {
+        self.0.lock().is_empty()
+    }
+}
+
+#[crate::async_trait]
+impl UploadStore for MemoryStore {
+    async fn load(&self, id: &str) -> io::Result<Option<Record>> {
+        Ok(self.0.lock().get(id).cloned())
+    }
+
+    async fn save(&self, id: &str, record: Record) -> io::Result<()> {
+        self.0.lock().insert(id.to_string(), record);
+        Ok(())
+    }
+
+    async fn remove(&self, id: &str) -> io::Result<()> {
+        self.0.lock().remove(id);
+        Ok(())
+    }
+
+    async fn purge(
+        &self,
+        expired: &(dyn for<'a> Fn(&'a Record) -> bool + Send + Sync)
+    ) -> io::Result<Vec<String>> {
+        let mut removed = vec![];
+        self.0.lock().retain(|id, record| match expired(record) {
+            true => { removed.push(id.clone()); false }
+            false => true,
+        });
+
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(offset: u64) -> Record {
+        Record { length: 8, offset, metadata: None, updated: SystemTime::now() }
+    }
+
+    #[test]
+    fn test_memory_store() {
+        let store = MemoryStore::new();
+        crate::async_test(async {
+            assert_eq!(store.load("a").await.unwrap(), None);
+
+            store.save("a", record(0)).await.unwrap();
+            store.save("b", record(8)).await.unwrap();
+            store.save("a", record(4)).await.unwrap();
+            assert_eq!(store.load("a").await.unwrap().unwrap().offset, 4);
+
+            let removed = store.purge(&|record| record.is_complete()).await.unwrap();
+            assert_eq!(removed, vec!["b".to_string()]);
+            assert_eq!(store.load("b").await.unwrap(), None);
+
+            store.remove("a").await.unwrap();
+            store.remove("a").await.unwrap();
+            assert_eq!(store.load("a").await.unwrap(), None);
+        });
+
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_metadata() {
+        let mut record = record(0);
+        assert!(record.metadata("filename").is_none());
+
+        record.metadata = Some("filename aGk=, filetype dGV4dC9wbGFpbg==,bad !,empty".into());
+        assert_eq!(record.metadata("filename").unwrap(), "hi");
+        assert_eq!(record.metadata("filetype").unwrap(), "text/plain");
+        assert_eq!(record.metadata("empty").unwrap(), "");
+        assert!(record.metadata("bad").is_none());
+        assert!(record.metadata("file").is_none());
+    }
+}
@@ -0,0 +1,116 @@
+use std::io;
+
+use either::Either;
+use tempfile::NamedTempFile;
+
+use crate::{Request, Rocket, Ignite, Sentinel};
+use crate::fs::TempFile;
+use crate::http::{ContentType, Status};
+use crate::outcome::Outcome;
+use crate::request::{self, FromRequest};
+use crate::upload::Record;
+use crate::upload::fairing::{Manager, remove_file};
+
+/// A request guard giving access to the uploads accepted by the
+/// [`Uploads`](crate::upload::Uploads) fairing.
+///
+/// Once a client has finished an upload, a handler [takes](Uploaded::take())
+/// it as a [`TempFile`], typically to [persist](TempFile::persist_to()) it.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::upload::Uploaded;
+///
+/// #[post("/photos/<id>")]
+/// async fn save(id: &str, uploads: Uploaded<'_>) -> std::io::Result<Option<()>> {
+///     match uploads.take(id).await? {
+///         Some(mut file) => file.persist_to(format!("/srv/photos/{}", id)).await.map(Some),
+///         None => Ok(None),
+///     }
+/// }
+/// ```
+pub struct Uploaded<'r> {
+    manager: &'r Manager,
+    config: &'r crate::Config,
+}
+
+impl Uploaded<'_> {
+    /// Returns the record of upload `id`, or `None` if there is no such
+    /// upload.
+    pub async fn get(&self, id: &str) -> io::Result<Option<Record>> {
+        self.manager.store.load(id).await
+    }
+
+    /// Removes upload `id` and returns its data as a [`TempFile`] if the
+    /// upload is complete. Returns `None` if there is no such upload, if it's
+    /// incomplete, or if it's being written to.
+    ///
+    /// The file's content type is read from the `filetype` key of the upload's
+    /// metadata, if there is one.
+    pub async fn take(&self, id: &str) -> io::Result<Option<TempFile<'static>>> {
+        let (path, _busy) = match (self.manager.path(self.config, id), self.manager.lock(id)) {
+            (Some(path), Some(busy)) => (path, busy),
+            _ => return Ok(None),
+        };
+
+        let record = match self.manager.store.load(id).await? {
+            Some(record) if record.is_complete() => record,
+            _ => return Ok(None),
+        };
+
+        // Move the data to a temporary file, copying if it's on another device.
+        let temp_dir = self.config.temp_dir.clone();
+        let file = tokio::task::spawn_blocking(move || {
+            NamedTempFile::new_in(temp_dir)
+        }).await.map_err(|_| {
+            io::Error::new(io::ErrorKind::BrokenPipe, "spawn_block panic")
+        })??;
+
+        let temp_path = file.into_temp_path();
+        if tokio::fs::rename(&path, &temp_path).await.is_err() {
+            tokio::fs::copy(&path, &temp_path).await?;
+            remove_file(path).await?;
+        }
+
+        self.manager.store.remove(id).await?;
+        let content_type = record.metadata("filetype")
+            .and_then(|filetype| ContentType::parse_flexible(&filetype));
+
+        Ok(Some(TempFile::File {
+            file_name: None,
+            content_type,
+            path: Either::Left(temp_path),
+            len: record.length,
+        }))
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Uploaded<'r> {
+    type Error = io::Error;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match req.rocket().state::<Manager>() {
+            Some(manager) => Outcome::Success(Uploaded { manager, config: req.rocket().config() }),
+            None => {
+                error_!("`Uploaded` guard used without attaching the `Uploads` fairing.");
+                let error = io::Error::new(io::ErrorKind::Other, "missing `Uploads` fairing");
+                Outcome::Failure((Status::InternalServerError, error))
+            }
+        }
+    }
+}
+
+impl Sentinel for Uploaded<'_> {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        if rocket.state::<Manager>().is_none() {
+            error!("launching with an `Uploaded` guard without the `Uploads` fairing.");
+            info_!("Using `Uploaded` requires attaching `Uploads`.");
+            return true;
+        }
+
+        false
+    }
+}
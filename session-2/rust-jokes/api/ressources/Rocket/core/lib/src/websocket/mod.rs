@@ -0,0 +1,71 @@
+//! Two-way WebSocket connections.
+//!
+//! The [`WebSocket`] request guard succeeds for requests asking to upgrade
+//! their connection to the WebSocket protocol. Its [`channel()`] method returns
+//! a [`Channel`] responder that completes the handshake and, once the
+//! connection has been upgraded, runs a handler with a [`DuplexStream`] of
+//! [`Message`]s: a `Stream` of incoming messages and a `Sink` for outgoing
+//! ones.
+//!
+//! # Enabling
+//!
+//! This module is only available when the `websocket` feature is enabled:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket = { version = "0.5.0-rc.1", features = ["websocket"] }
+//! ```
+//!
+//! # Usage
+//!
+//! Use the [`WebSocket`] guard in a `GET` route and return a [`Channel`]. The
+//! handler below echoes every message it receives:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::futures::{SinkExt, StreamExt};
+//! use rocket::websocket::{WebSocket, Channel};
+//!
+//! #[get("/echo")]
+//! fn echo(ws: WebSocket) -> Channel {
+//!     ws.channel(|mut stream| async move {
+//!         while let Some(message) = stream.next().await {
+//!             stream.send(message?).await?;
+//!         }
+//!
+//!         Ok(())
+//!     })
+//! }
+//! ```
+//!
+//! Requests that don't ask for an upgrade are forwarded by the guard, so a
+//! route with a higher rank can serve them at the same URI.
+//!
+//! # Shutdown
+//!
+//! Upgraded connections outlive the response but not the server: when Rocket
+//! is [shut down](crate::Shutdown), a `DuplexStream` sends a "going away"
+//! close frame to the client and its stream of incoming messages ends. A
+//! handler that only sends messages should watch for shutdown itself, for
+//! instance by `select!`ing on a [`Shutdown`](crate::Shutdown) guard.
+//!
+//! # Testing
+//!
+//! [`LocalResponse::into_websocket()`] returns the client's end of a connection
+//! upgraded by a local, asynchronous client.
+//!
+//! [`channel()`]: WebSocket::channel()
+//! [`LocalResponse::into_websocket()`]: crate::local::asynchronous::LocalResponse::into_websocket()
+
+mod websocket;
+mod stream;
+
+pub use tokio_tungstenite::tungstenite::{Message, Error};
+pub use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+pub use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+
+pub use self::websocket::{WebSocket, Channel, HandshakeError};
+pub use self::stream::DuplexStream;
+
+/// Type alias for the `Result` of WebSocket operations.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -0,0 +1,102 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::{ready, FutureExt, Sink, SinkExt, Stream, StreamExt};
+use tokio_tungstenite::WebSocketStream;
+use tokio_tungstenite::tungstenite::protocol::Role;
+
+use crate::Shutdown;
+use crate::response::Io;
+use crate::websocket::{Message, CloseFrame, CloseCode, Error, Result};
+
+/// The two halves of a WebSocket connection.
+///
+/// A `DuplexStream` is a [`Stream`] of the [`Message`]s received from the
+/// other end of the connection and a [`Sink`] for messages to send to it. Use
+/// [`StreamExt`] and [`SinkExt`] to work with it, or [`split()`] it to receive
+/// and send messages concurrently.
+///
+/// On the server, the stream of incoming messages ends once Rocket has been
+/// [shut down](crate::Shutdown), after a [`CloseCode::Away`] close frame is
+/// sent to the client.
+///
+/// [`split()`]: StreamExt::split()
+pub struct DuplexStream {
+    inner: WebSocketStream<Box<dyn Io>>,
+    shutdown: Option<Shutdown>,
+    state: State,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Open,
+    Closing,
+    Flushing,
+    Closed,
+}
+
+impl DuplexStream {
+    pub(crate) async fn new(io: Box<dyn Io>, role: Role, shutdown: Option<Shutdown>) -> Self {
+        let inner = WebSocketStream::from_raw_socket(io, role, None).await;
+        DuplexStream { inner, shutdown, state: State::Open }
+    }
+}
+
+impl Stream for DuplexStream {
+    type Item = Result<Message>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if let (State::Open, Some(shutdown)) = (this.state, &mut this.shutdown) {
+            if shutdown.poll_unpin(cx).is_ready() {
+                this.state = State::Closing;
+            }
+        }
+
+        // Errors while closing are ignored: the stream ends either way.
+        loop {
+            match this.state {
+                State::Open => return this.inner.poll_next_unpin(cx),
+                State::Closing => {
+                    let frame = CloseFrame {
+                        code: CloseCode::Away,
+                        reason: "server is shutting down".into(),
+                    };
+
+                    this.state = match ready!(this.inner.poll_ready_unpin(cx)) {
+                        Ok(()) => match this.inner.start_send_unpin(Message::Close(Some(frame))) {
+                            Ok(()) => State::Flushing,
+                            Err(_) => State::Closed,
+                        },
+                        Err(_) => State::Closed,
+                    };
+                }
+                State::Flushing => {
+                    let _ = ready!(this.inner.poll_flush_unpin(cx));
+                    this.state = State::Closed;
+                }
+                State::Closed => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl Sink<Message> for DuplexStream {
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.get_mut().inner.poll_ready_unpin(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Message) -> Result<()> {
+        self.get_mut().inner.start_send_unpin(item)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.get_mut().inner.poll_flush_unpin(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.get_mut().inner.poll_close_unpin(cx)
+    }
+}
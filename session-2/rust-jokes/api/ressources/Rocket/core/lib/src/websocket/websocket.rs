@@ -0,0 +1,167 @@
+use std::io;
+use std::future::Future;
+
+use futures::future::{BoxFuture, FutureExt};
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+
+use crate::{Request, Response, Shutdown};
+use crate::request::{self, FromRequest};
+use crate::response::{self, Responder};
+use crate::outcome::Outcome;
+use crate::http::{HeaderMap, Status};
+use crate::websocket::{DuplexStream, Error, Result};
+
+/// A request guard for requests asking to upgrade to a WebSocket connection.
+///
+/// The guard forwards requests without `Connection: upgrade` and `Upgrade:
+/// websocket` headers. It fails with `400 Bad Request` and a
+/// [`HandshakeError`] if an upgrade request is for a WebSocket version other
+/// than `13` or is missing a `Sec-WebSocket-Key` header.
+///
+/// A `WebSocket` is consumed by [`WebSocket::channel()`], which returns the
+/// responder that completes the upgrade.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::futures::SinkExt;
+/// use rocket::websocket::{WebSocket, Channel, Message};
+///
+/// #[get("/hello/<name>")]
+/// fn hello(ws: WebSocket, name: &str) -> Channel {
+///     let greeting = format!("Hello, {}!", name);
+///     ws.channel(move |mut stream| async move {
+///         stream.send(Message::Text(greeting)).await?;
+///         stream.close().await
+///     })
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WebSocket {
+    accept_key: String,
+    shutdown: Shutdown,
+}
+
+/// An error in a request's WebSocket handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandshakeError {
+    /// The `Sec-WebSocket-Version` is missing or isn't `13`.
+    Version,
+    /// The `Sec-WebSocket-Key` header is missing.
+    Key,
+}
+
+/// A [`Responder`] that upgrades a connection to a WebSocket connection.
+///
+/// A `Channel` is returned by [`WebSocket::channel()`]. It responds with `101
+/// Switching Protocols` and, once the connection has been upgraded, runs its
+/// handler with the connection's [`DuplexStream`]. The handler may run for as
+/// long as the connection is open; it's not subject to the route's timeout.
+///
+/// Handler errors caused by the connection closing aren't errors. Others are
+/// logged, and the connection is dropped.
+pub struct Channel {
+    websocket: WebSocket,
+    handler: Box<dyn FnOnce(DuplexStream) -> BoxFuture<'static, Result<()>> + Send>,
+}
+
+/// Returns `true` if the comma-separated values of the header `name` in
+/// `headers` contain `token`, without regard for case.
+fn has_token(headers: &HeaderMap<'_>, name: &str, token: &str) -> bool {
+    headers.get(name)
+        .flat_map(|value| value.split(','))
+        .any(|value| value.trim().eq_ignore_ascii_case(token))
+}
+
+impl WebSocket {
+    /// Returns a responder that upgrades the connection and then runs
+    /// `handler` with the connection's [`DuplexStream`]. The connection is
+    /// dropped when the future returned by `handler` resolves; [`close()`] the
+    /// stream first to close it cleanly.
+    ///
+    /// [`close()`]: futures::SinkExt::close()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::futures::{SinkExt, StreamExt};
+    /// use rocket::websocket::{WebSocket, Channel, Message};
+    ///
+    /// #[get("/count")]
+    /// fn count(ws: WebSocket) -> Channel {
+    ///     ws.channel(|mut stream| async move {
+    ///         let mut count = 0;
+    ///         while let Some(message) = stream.next().await {
+    ///             if let Message::Text(_) = message? {
+    ///                 count += 1;
+    ///                 stream.send(Message::Text(count.to_string())).await?;
+    ///             }
+    ///         }
+    ///
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub fn channel<F, Fut>(self, handler: F) -> Channel
+        where F: FnOnce(DuplexStream) -> Fut + Send + 'static,
+              Fut: Future<Output = Result<()>> + Send + 'static
+    {
+        Channel {
+            websocket: self,
+            handler: Box::new(move |stream| handler(stream).boxed()),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for WebSocket {
+    type Error = HandshakeError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let headers = req.headers();
+        if !has_token(headers, "Connection", "upgrade")
+            || !has_token(headers, "Upgrade", "websocket")
+        {
+            return Outcome::Forward(());
+        }
+
+        if headers.get_one("Sec-WebSocket-Version").map(|v| v.trim()) != Some("13") {
+            return Outcome::Failure((Status::BadRequest, HandshakeError::Version));
+        }
+
+        match headers.get_one("Sec-WebSocket-Key") {
+            Some(key) => Outcome::Success(WebSocket {
+                accept_key: derive_accept_key(key.trim().as_bytes()),
+                shutdown: req.rocket().shutdown(),
+            }),
+            None => Outcome::Failure((Status::BadRequest, HandshakeError::Key)),
+        }
+    }
+}
+
+impl<'r, 'o: 'r> Responder<'r, 'o> for Channel {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'o> {
+        let Channel { websocket, handler } = self;
+        let mut response = Response::build()
+            .status(Status::SwitchingProtocols)
+            .raw_header("Connection", "Upgrade")
+            .raw_header("Upgrade", "websocket")
+            .raw_header("Sec-WebSocket-Accept", websocket.accept_key)
+            .finalize();
+
+        let shutdown = websocket.shutdown;
+        response.set_upgrade(Box::new(move |io| Box::pin(async move {
+            let stream = DuplexStream::new(io, Role::Server, Some(shutdown)).await;
+            match handler(stream).await {
+                Ok(()) | Err(Error::ConnectionClosed) | Err(Error::AlreadyClosed) => Ok(()),
+                Err(Error::Io(e)) => Err(e),
+                Err(e) => Err(io::Error::new(io::ErrorKind::Other, e)),
+            }
+        })));
+
+        Ok(response)
+    }
+}
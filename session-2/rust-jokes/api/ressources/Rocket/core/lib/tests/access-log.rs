@@ -0,0 +1,93 @@
+#![cfg(feature = "access-log")]
+
+#[macro_use] extern crate rocket;
+
+use std::path::Path;
+
+use rocket::{Rocket, Build, Config};
+use rocket::access_log::AccessLog;
+use rocket::figment::Figment;
+use rocket::http::Header;
+use rocket::local::blocking::Client;
+
+#[get("/hello/<name>")]
+fn hello(name: &str) -> String {
+    format!("Hello, {}!", name)
+}
+
+fn figment(path: &Path) -> Figment {
+    Config::figment().merge(("access_log.path", path))
+}
+
+fn rocket(figment: Figment) -> Rocket<Build> {
+    rocket::custom(figment)
+        .mount("/", routes![hello])
+        .attach(AccessLog::new())
+}
+
+fn read(path: &Path) -> Vec<String> {
+    let log = std::fs::read_to_string(path).unwrap();
+    log.lines().map(|line| line.to_string()).collect()
+}
+
+#[test]
+fn requests_are_logged_in_combined_format() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("access.log");
+    let client = Client::debug(rocket(figment(&path))).unwrap();
+    client.get("/hello/Bob")
+        .remote("10.0.0.1:8000".parse().unwrap())
+        .header(Header::new("User-Agent", "curl/7.79"))
+        .dispatch();
+
+    client.head("/hello/Alice").header(Header::new("Referer", "http://a.b/\"c\"")).dispatch();
+    client.get("/nope").dispatch();
+
+    // Dropping the client waits for the writer to catch up.
+    drop(client);
+    let log = read(&path);
+    assert_eq!(log.len(), 3);
+    assert!(log[0].starts_with("10.0.0.1 - - ["));
+    assert!(log[0].ends_with(r#"] "GET /hello/Bob" 200 11 "-" "curl/7.79""#));
+    assert!(log[1].starts_with("- - - ["));
+    assert!(log[1].ends_with(r#"] "HEAD /hello/Alice" 200 13 "http://a.b/\"c\"" "-""#));
+    assert!(log[2].contains(r#""GET /nope" 404 "#));
+}
+
+#[test]
+fn custom_formats_are_rendered() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("access.log");
+    let format = "{request_id} {method} {uri} {status}";
+    let client = Client::debug(rocket(figment(&path).merge(("access_log.format", format))));
+    let client = client.unwrap();
+
+    client.get("/hello/Bob?x=1").header(Header::new("X-Request-Id", "abc")).dispatch();
+    drop(client);
+    assert_eq!(read(&path), vec!["abc GET /hello/Bob?x=1 200"]);
+
+    let figment = figment(&path).merge(("access_log.format", "{method} {host}"));
+    let error = Client::debug(rocket(figment)).unwrap_err();
+    assert!(matches!(error.kind(), rocket::error::ErrorKind::FailedFairings(..)));
+}
+
+#[test]
+fn access_log_is_rotated() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("access.log");
+    let figment = figment(&path)
+        .merge(("access_log.format", "{status}"))
+        .merge(("access_log.max_size", 8))
+        .merge(("access_log.max_files", 1));
+
+    let client = Client::debug(rocket(figment)).unwrap();
+    for _ in 0..3 {
+        client.get("/hello/Bob").dispatch();
+    }
+
+    client.get("/").dispatch();
+    drop(client);
+    assert_eq!(read(&path), vec!["200", "404"]);
+    assert_eq!(read(&dir.path().join("access.log.1")), vec!["200", "200"]);
+    assert!(!dir.path().join("access.log.2").exists());
+}
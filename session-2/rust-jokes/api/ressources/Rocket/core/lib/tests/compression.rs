@@ -0,0 +1,179 @@
+#![cfg(feature = "compression")]
+
+#[macro_use] extern crate rocket;
+
+use std::io::Cursor;
+
+use rocket::compression::Compression;
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use rocket::response::stream::ReaderStream;
+use rocket::tokio::io::AsyncReadExt;
+
+use async_compression::tokio::bufread::{BrotliDecoder, DeflateDecoder, GzipDecoder};
+
+const TEXT: &str = "Rocket makes it simple to write fast, secure web applications. ";
+
+fn long_text() -> String {
+    TEXT.repeat(64)
+}
+
+#[get("/long")]
+fn long() -> String {
+    long_text()
+}
+
+#[get("/short")]
+fn short() -> &'static str {
+    TEXT
+}
+
+#[get("/png")]
+fn png() -> (ContentType, String) {
+    (ContentType::PNG, long_text())
+}
+
+#[get("/stream")]
+fn stream() -> ReaderStream![Cursor<String>] {
+    ReaderStream! {
+        for _ in 0..4 {
+            yield Cursor::new(TEXT.repeat(16));
+        }
+    }
+}
+
+#[derive(Responder)]
+struct Tagged(String, Header<'static>);
+
+#[get("/tagged")]
+fn tagged() -> Tagged {
+    Tagged(long_text(), Header::new("ETag", "\"abc\""))
+}
+
+async fn client_with(compression: Compression) -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![long, short, png, stream, tagged])
+        .attach(compression);
+
+    Client::debug(rocket).await.unwrap()
+}
+
+async fn decode(encoding: &str, bytes: &[u8]) -> String {
+    let mut string = String::new();
+    match encoding {
+        "br" => BrotliDecoder::new(bytes).read_to_string(&mut string).await,
+        "gzip" => GzipDecoder::new(bytes).read_to_string(&mut string).await,
+        "deflate" => DeflateDecoder::new(bytes).read_to_string(&mut string).await,
+        _ => panic!("unexpected encoding: {}", encoding),
+    }.unwrap();
+
+    string
+}
+
+#[rocket::async_test]
+async fn compresses_with_negotiated_encoding() {
+    let client = client_with(Compression::default()).await;
+    let cases = [
+        ("gzip", "gzip"),
+        ("deflate", "deflate"),
+        ("br", "br"),
+        ("gzip, deflate, br", "br"),
+        ("br;q=0.5, deflate;q=0.9, gzip;q=0.2", "deflate"),
+        ("*", "br"),
+    ];
+
+    for (accept, expected) in cases.iter() {
+        let response = client.get("/long")
+            .header(Header::new("Accept-Encoding", *accept))
+            .dispatch().await;
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("Content-Encoding"), Some(*expected));
+        assert_eq!(response.headers().get_one("Vary"), Some("Accept-Encoding"));
+        assert!(response.headers().get_one("Content-Length").is_none());
+
+        let bytes = response.into_bytes().await.unwrap();
+        assert!(bytes.len() < long_text().len());
+        assert_eq!(decode(expected, &bytes).await, long_text());
+    }
+}
+
+#[rocket::async_test]
+async fn compresses_streamed_bodies() {
+    let client = client_with(Compression::default()).await;
+    let response = client.get("/stream")
+        .header(Header::new("Accept-Encoding", "gzip"))
+        .dispatch().await;
+
+    assert_eq!(response.headers().get_one("Content-Encoding"), Some("gzip"));
+    let bytes = response.into_bytes().await.unwrap();
+    assert_eq!(decode("gzip", &bytes).await, TEXT.repeat(64));
+}
+
+#[rocket::async_test]
+async fn skips_unacceptable_and_ineligible_responses() {
+    let client = client_with(Compression::default()).await;
+
+    // No acceptable encoding: uncompressed, but still varies.
+    for accept in &[None, Some("identity"), Some("gzip;q=0, *;q=0")] {
+        let mut request = client.get("/long");
+        if let Some(accept) = accept {
+            request.add_header(Header::new("Accept-Encoding", *accept));
+        }
+
+        let response = request.dispatch().await;
+        assert!(response.headers().get_one("Content-Encoding").is_none());
+        assert_eq!(response.headers().get_one("Vary"), Some("Accept-Encoding"));
+        assert_eq!(response.into_string().await.unwrap(), long_text());
+    }
+
+    // Too small or an excluded media type: untouched.
+    for uri in &["/short", "/png"] {
+        let response = client.get(*uri)
+            .header(Header::new("Accept-Encoding", "gzip"))
+            .dispatch().await;
+
+        assert!(response.headers().get_one("Content-Encoding").is_none());
+        assert!(response.headers().get_one("Vary").is_none());
+    }
+}
+
+#[rocket::async_test]
+async fn respects_configuration() {
+    use rocket::compression::Level;
+    use rocket::http::MediaType;
+
+    let compression = Compression::default()
+        .level(Level::Best)
+        .min_size(0)
+        .exclude(MediaType::new("text", "*"));
+
+    let client = client_with(compression).await;
+    let response = client.get("/short")
+        .header(Header::new("Accept-Encoding", "gzip"))
+        .dispatch().await;
+
+    assert!(response.headers().get_one("Content-Encoding").is_none());
+
+    let client = client_with(Compression::default().min_size(0)).await;
+    let response = client.get("/short")
+        .header(Header::new("Accept-Encoding", "gzip"))
+        .dispatch().await;
+
+    assert_eq!(response.headers().get_one("Content-Encoding"), Some("gzip"));
+    let bytes = response.into_bytes().await.unwrap();
+    assert_eq!(decode("gzip", &bytes).await, TEXT);
+}
+
+#[rocket::async_test]
+async fn weakens_strong_etags() {
+    let client = client_with(Compression::default()).await;
+    let response = client.get("/tagged")
+        .header(Header::new("Accept-Encoding", "gzip"))
+        .dispatch().await;
+
+    assert_eq!(response.headers().get_one("ETag"), Some("W/\"abc\""));
+
+    let response = client.get("/tagged").dispatch().await;
+    assert_eq!(response.headers().get_one("ETag"), Some("\"abc\""));
+}
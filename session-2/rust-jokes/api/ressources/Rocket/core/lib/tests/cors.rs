@@ -0,0 +1,182 @@
+#![cfg(feature = "cors")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, Config};
+use rocket::figment::Figment;
+use rocket::http::{Header, Method, Status};
+use rocket::local::blocking::{Client, LocalResponse};
+use rocket::cors::Cors;
+
+#[get("/items")]
+fn items() -> &'static str {
+    "items"
+}
+
+#[post("/items")]
+fn new_item() -> &'static str {
+    "created"
+}
+
+#[delete("/items/<id>")]
+fn delete_item(id: usize) -> String {
+    id.to_string()
+}
+
+#[options("/items")]
+fn options() -> &'static str {
+    "options"
+}
+
+fn rocket(figment: Figment) -> Rocket<Build> {
+    rocket::custom(figment)
+        .mount("/", routes![items, new_item, delete_item, options])
+        .attach(Cors::new())
+}
+
+fn header<'c>(response: &'c LocalResponse<'_>, name: &str) -> Option<&'c str> {
+    response.headers().get_one(name)
+}
+
+fn preflight<'c>(
+    client: &'c Client,
+    uri: &'static str,
+    origin: &'static str,
+    method: &'static str
+) -> LocalResponse<'c> {
+    client.options(uri)
+        .header(Header::new("Origin", origin))
+        .header(Header::new("Access-Control-Request-Method", method))
+        .dispatch()
+}
+
+#[test]
+fn any_origin() {
+    let client = Client::debug(rocket(Config::figment())).unwrap();
+
+    let response = preflight(&client, "/items", "https://rocket.rs", "POST");
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(header(&response, "Access-Control-Allow-Origin"), Some("*"));
+    assert_eq!(header(&response, "Access-Control-Allow-Methods"), Some("GET, HEAD, OPTIONS, POST"));
+    assert!(header(&response, "Access-Control-Allow-Headers").is_none());
+    assert!(header(&response, "Access-Control-Max-Age").is_none());
+    assert!(header(&response, "Vary").is_none());
+
+    let response = preflight(&client, "/items/10", "https://rocket.rs", "DELETE");
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(header(&response, "Access-Control-Allow-Methods"), Some("DELETE"));
+
+    let response = client.get("/items").header(Header::new("Origin", "null")).dispatch();
+    assert_eq!(header(&response, "Access-Control-Allow-Origin"), Some("*"));
+    assert_eq!(response.into_string().unwrap(), "items");
+}
+
+#[test]
+fn unpreflighted_requests() {
+    let client = Client::debug(rocket(Config::figment())).unwrap();
+
+    // Not preflights: routed as usual.
+    let response = client.options("/items").dispatch();
+    assert_eq!(response.into_string().unwrap(), "options");
+
+    let response = client.get("/items").dispatch();
+    assert!(header(&response, "Access-Control-Allow-Origin").is_none());
+
+    // A method no route supports and a URI no route matches.
+    let response = preflight(&client, "/items", "https://rocket.rs", "PUT");
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = preflight(&client, "/other", "https://rocket.rs", "GET");
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[test]
+fn configured_policy() {
+    let figment = Config::figment()
+        .merge(("cors.allowed_origins", ["https://rocket.rs"]))
+        .merge(("cors.allowed_origin_regexes", [r"https://[a-z]+\.rocket\.rs"]))
+        .merge(("cors.allowed_methods", ["GET", "DELETE"]))
+        .merge(("cors.allowed_headers", ["X-Token"]))
+        .merge(("cors.exposed_headers", ["X-Total", "X-Page"]))
+        .merge(("cors.allow_credentials", true))
+        .merge(("cors.max_age", 600));
+
+    let client = Client::debug(rocket(figment)).unwrap();
+
+    let response = client.options("/items")
+        .header(Header::new("Origin", "https://api.rocket.rs"))
+        .header(Header::new("Access-Control-Request-Method", "GET"))
+        .header(Header::new("Access-Control-Request-Headers", "x-token"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(header(&response, "Access-Control-Allow-Origin"), Some("https://api.rocket.rs"));
+    assert_eq!(header(&response, "Access-Control-Allow-Methods"), Some("GET"));
+    assert_eq!(header(&response, "Access-Control-Allow-Headers"), Some("x-token"));
+    assert_eq!(header(&response, "Access-Control-Allow-Credentials"), Some("true"));
+    assert_eq!(header(&response, "Access-Control-Max-Age"), Some("600"));
+    assert_eq!(header(&response, "Vary"), Some("Origin"));
+
+    // Disallowed method, header, and origins.
+    let response = preflight(&client, "/items", "https://rocket.rs", "POST");
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = client.options("/items")
+        .header(Header::new("Origin", "https://rocket.rs"))
+        .header(Header::new("Access-Control-Request-Method", "GET"))
+        .header(Header::new("Access-Control-Request-Headers", "X-Token, Authorization"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Forbidden);
+
+    for origin in &["https://evil.com", "https://rocket.rs.evil.com", "null"] {
+        let response = preflight(&client, "/items", origin, "GET");
+        assert_eq!(response.status(), Status::Forbidden);
+        assert!(header(&response, "Access-Control-Allow-Origin").is_none());
+    }
+
+    // Actual requests.
+    let origin = Header::new("Origin", "https://rocket.rs");
+    let response = client.get("/items").header(origin).dispatch();
+    assert_eq!(header(&response, "Access-Control-Allow-Origin"), Some("https://rocket.rs"));
+    assert_eq!(header(&response, "Access-Control-Allow-Credentials"), Some("true"));
+    assert_eq!(header(&response, "Access-Control-Expose-Headers"), Some("X-Total, X-Page"));
+    assert_eq!(header(&response, "Vary"), Some("Origin"));
+
+    let origin = Header::new("Origin", "https://evil.com");
+    let response = client.get("/items").header(origin).dispatch();
+    assert!(header(&response, "Access-Control-Allow-Origin").is_none());
+    assert!(header(&response, "Access-Control-Expose-Headers").is_none());
+    assert_eq!(header(&response, "Vary"), Some("Origin"));
+    assert_eq!(response.into_string().unwrap(), "items");
+}
+
+#[test]
+#[cfg(feature = "ratelimit")]
+fn with_rate_limit() {
+    use rocket::ratelimit::{RateLimit, Limit};
+
+    let figment = Config::figment().merge(("ratelimit.global", Limit::new(100, 60)));
+    let client = Client::debug(rocket(figment).attach(RateLimit::new())).unwrap();
+
+    let response = preflight(&client, "/items/1", "https://rocket.rs", "DELETE");
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(header(&response, "Access-Control-Allow-Methods"), Some("DELETE"));
+
+    let response = client.req(Method::Delete, "/items/1").dispatch();
+    assert_eq!(response.into_string().unwrap(), "1");
+}
+
+#[test]
+fn invalid_regex_fails_ignite() {
+    let figment = Config::figment().merge(("cors.allowed_origin_regexes", ["("]));
+    let error = Client::debug(rocket(figment)).unwrap_err();
+    assert!(matches!(error.kind(), rocket::error::ErrorKind::FailedFairings(..)));
+}
+
+#[test]
+fn credentials_from_any_origin_fail_ignite() {
+    let figment = Config::figment().merge(("cors.allow_credentials", true));
+    let error = Client::debug(rocket(figment)).unwrap_err();
+    assert!(matches!(error.kind(), rocket::error::ErrorKind::FailedFairings(..)));
+}
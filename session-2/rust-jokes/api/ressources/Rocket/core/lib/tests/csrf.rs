@@ -0,0 +1,156 @@
+#![cfg(feature = "csrf")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build};
+use rocket::form::{Form, Contextual};
+use rocket::csrf::{Csrf, CsrfToken};
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+
+#[derive(FromForm)]
+struct Message<'r> {
+    text: &'r str,
+}
+
+#[get("/")]
+fn token(token: CsrfToken<'_>) -> String {
+    format!("{}={}", token.field_name(), token)
+}
+
+#[post("/", data = "<form>")]
+fn submit(form: Form<Message<'_>>) -> String {
+    form.text.to_string()
+}
+
+#[post("/contextual", data = "<form>")]
+fn contextual<'r>(form: Form<Contextual<'r, Message<'r>>>) -> String {
+    match form.value {
+        Some(ref message) => message.text.to_string(),
+        None => form.context.field_errors("_csrf").map(|e| e.to_string()).collect(),
+    }
+}
+
+#[get("/search?<q>")]
+fn search(q: &str) -> &str {
+    q
+}
+
+fn rocket() -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![token, submit, contextual, search])
+        .attach(Csrf::new())
+}
+
+async fn token_field(client: &Client) -> String {
+    client.get("/").dispatch().await.into_string().await.unwrap()
+}
+
+#[async_test]
+async fn forms_require_token() {
+    let client = Client::tracked(rocket()).await.unwrap();
+    let field = token_field(&client).await;
+    assert!(client.cookies().get_private("rocket_csrf").is_some());
+    assert_eq!(token_field(&client).await, field);
+
+    let response = client.post("/")
+        .header(ContentType::Form)
+        .body(format!("text=hi&{}", field))
+        .dispatch().await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().await.unwrap(), "hi");
+
+    let response = client.post("/")
+        .header(ContentType::Form)
+        .body("text=hi")
+        .dispatch().await;
+
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = client.post("/")
+        .header(ContentType::Form)
+        .body("text=hi&_csrf=forged")
+        .dispatch().await;
+
+    assert_eq!(response.status(), Status::Forbidden);
+}
+
+#[async_test]
+async fn header_carries_token() {
+    let client = Client::tracked(rocket()).await.unwrap();
+    let field = token_field(&client).await;
+    let token = field.trim_start_matches("_csrf=").to_string();
+
+    let response = client.post("/")
+        .header(ContentType::Form)
+        .header(Header::new("X-CSRF-Token", token))
+        .body("text=hi")
+        .dispatch().await;
+
+    assert_eq!(response.into_string().await.unwrap(), "hi");
+
+    let response = client.post("/")
+        .header(ContentType::Form)
+        .header(Header::new("X-CSRF-Token", "forged"))
+        .body("text=hi")
+        .dispatch().await;
+
+    assert_eq!(response.status(), Status::Forbidden);
+}
+
+#[async_test]
+async fn first_request_is_rejected() {
+    let client = Client::untracked(rocket()).await.unwrap();
+    let field = token_field(&client).await;
+
+    let response = client.post("/")
+        .header(ContentType::Form)
+        .body(format!("text=hi&{}", field))
+        .dispatch().await;
+
+    assert_eq!(response.status(), Status::Forbidden);
+}
+
+#[async_test]
+async fn errors_render_in_context() {
+    let client = Client::tracked(rocket()).await.unwrap();
+    let field = token_field(&client).await;
+
+    let response = client.post("/contextual")
+        .header(ContentType::Form)
+        .body("text=hi&_csrf=forged")
+        .dispatch().await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().await.unwrap(), "invalid CSRF token");
+
+    let response = client.post("/contextual")
+        .header(ContentType::Form)
+        .body("text=hi")
+        .dispatch().await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().await.unwrap(), "missing");
+
+    let response = client.post("/contextual")
+        .header(ContentType::Form)
+        .body(format!("text=hi&{}", field))
+        .dispatch().await;
+
+    assert_eq!(response.into_string().await.unwrap(), "hi");
+}
+
+#[async_test]
+async fn safe_methods_are_not_checked() {
+    let client = Client::untracked(rocket()).await.unwrap();
+    let response = client.get("/search?q=rocket").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "rocket");
+}
+
+#[async_test]
+async fn token_requires_fairing() {
+    let rocket = rocket::build().mount("/", routes![token]);
+    let error = Client::untracked(rocket).await.unwrap_err();
+    assert!(matches!(error.kind(), rocket::error::ErrorKind::SentinelAborts(..)));
+}
@@ -2,7 +2,7 @@ use std::{io::Read, fs::File};
 use std::path::Path;
 
 use rocket::{Rocket, Route, Build};
-use rocket::http::Status;
+use rocket::http::{Accept, ContentType, Header, Status};
 use rocket::local::blocking::Client;
 use rocket::fs::{FileServer, Options, relative};
 
@@ -20,6 +20,11 @@ fn rocket() -> Rocket<Build> {
         .mount("/both", FileServer::new(&root, Options::DotFiles | Options::Index))
         .mount("/redir", FileServer::new(&root, Options::NormalizeDirs))
         .mount("/redir_index", FileServer::new(&root, Options::NormalizeDirs | Options::Index))
+        .mount("/ranges", FileServer::new(&root, Options::Ranges | Options::Conditional))
+        .mount("/precompressed", FileServer::new(&root, Options::Precompressed))
+        .mount("/listing", FileServer::new(&root, Options::Listing))
+        .mount("/listing_index", FileServer::new(&root, Options::Listing | Options::Index))
+        .mount("/listing_dots", FileServer::new(&root, Options::Listing | Options::DotFiles))
 }
 
 static REGULAR_FILES: &[&str] = &[
@@ -186,3 +191,236 @@ fn test_redirection() {
     assert_eq!(response.status(), Status::PermanentRedirect);
     assert_eq!(response.headers().get("Location").next(), Some("/redir_index/"));
 }
+
+#[test]
+fn test_ranges() {
+    let client = Client::debug(rocket()).expect("valid rocket");
+    let range = |value: &str| {
+        client.get("/ranges/index.html").header(Header::new("Range", value.to_string())).dispatch()
+    };
+
+    let response = client.get("/ranges/index.html").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("Accept-Ranges"), Some("bytes"));
+
+    let response = range("bytes=0-3");
+    assert_eq!(response.status(), Status::PartialContent);
+    assert_eq!(response.headers().get_one("Content-Range"), Some("bytes 0-3/30"));
+    assert_eq!(response.headers().get_one("Content-Type"), Some("text/html; charset=utf-8"));
+    assert_eq!(response.into_string().unwrap(), "Just");
+
+    let response = range("bytes=-6");
+    assert_eq!(response.status(), Status::PartialContent);
+    assert_eq!(response.headers().get_one("Content-Range"), Some("bytes 24-29/30"));
+    assert_eq!(response.into_string().unwrap(), "html.\n");
+
+    let response = range("bytes=0-3, 5-5");
+    assert_eq!(response.status(), Status::PartialContent);
+    let content_type = response.content_type().expect("content type");
+    assert_eq!(content_type.top(), "multipart");
+    assert_eq!(content_type.sub(), "byteranges");
+    let boundary = content_type.params().find(|(k, _)| k == "boundary").expect("boundary").1;
+    let body = response.into_string().unwrap();
+    assert!(body.contains("Content-Range: bytes 0-3/30\r\n\r\nJust\r\n"));
+    assert!(body.contains("Content-Range: bytes 5-5/30\r\n\r\na\r\n"));
+    assert!(body.ends_with(&format!("\r\n--{}--\r\n", boundary)));
+
+    let response = range("bytes=30-");
+    assert_eq!(response.status(), Status::RangeNotSatisfiable);
+    assert_eq!(response.headers().get_one("Content-Range"), Some("bytes */30"));
+
+    for invalid in &["bytes=4-1", "lines=0-1", "bytes=abc"] {
+        let response = range(invalid);
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap().len(), 30);
+    }
+
+    // Ranges are only honored when enabled.
+    let response = client.get("/default/index.html")
+        .header(Header::new("Range", "bytes=0-3"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.headers().get_one("Accept-Ranges").is_none());
+    assert!(response.headers().get_one("ETag").is_none());
+    assert!(response.headers().get_one("Last-Modified").is_none());
+}
+
+#[test]
+fn test_conditional() {
+    let client = Client::debug(rocket()).expect("valid rocket");
+    let response = client.get("/ranges/index.html").dispatch();
+    let etag = response.headers().get_one("ETag").expect("etag").to_string();
+    let modified = response.headers().get_one("Last-Modified").expect("date").to_string();
+
+    let response = client.get("/ranges/index.html")
+        .header(Header::new("If-None-Match", etag.clone()))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::NotModified);
+    assert_eq!(response.headers().get_one("ETag"), Some(etag.as_str()));
+    assert!(response.into_string().is_none());
+
+    let response = client.get("/ranges/index.html")
+        .header(Header::new("If-None-Match", format!("\"other\", W/{}", etag)))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::NotModified);
+
+    let response = client.get("/ranges/index.html")
+        .header(Header::new("If-None-Match", "\"other\""))
+        .header(Header::new("If-Modified-Since", modified.clone()))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.get("/ranges/index.html")
+        .header(Header::new("If-Modified-Since", modified.clone()))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::NotModified);
+
+    let response = client.get("/ranges/index.html")
+        .header(Header::new("If-Modified-Since", "Thu, 01 Jan 1970 00:00:00 GMT"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+
+    // A matching `If-Range` honors the range; any other sends the full file.
+    for (if_range, status) in &[
+        (etag.as_str(), Status::PartialContent),
+        (modified.as_str(), Status::PartialContent),
+        ("\"other\"", Status::Ok),
+        ("Thu, 01 Jan 1970 00:00:00 GMT", Status::Ok),
+    ] {
+        let response = client.get("/ranges/index.html")
+            .header(Header::new("Range", "bytes=0-3"))
+            .header(Header::new("If-Range", if_range.to_string()))
+            .dispatch();
+
+        assert_eq!(response.status(), *status, "If-Range: {}", if_range);
+    }
+}
+
+#[test]
+fn test_precompressed() {
+    let client = Client::debug(rocket()).expect("valid rocket");
+    let read = |path: &str| std::fs::read(static_root().join(path)).expect("read file");
+
+    for (accept, expected) in &[
+        (Some("gzip"), Some("gzip")),
+        (Some("br"), Some("br")),
+        (Some("gzip, deflate, br"), Some("br")),
+        (Some("br;q=0.5, gzip;q=0.8"), Some("gzip")),
+        (Some("*, br;q=0"), Some("gzip")),
+        (Some("deflate"), None),
+        (None, None),
+    ] {
+        let mut request = client.get("/precompressed/other/hello.txt");
+        if let Some(accept) = accept {
+            request.add_header(Header::new("Accept-Encoding", *accept));
+        }
+
+        let response = request.dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("Content-Encoding"), *expected);
+        assert_eq!(response.headers().get_one("Vary"), Some("Accept-Encoding"));
+        assert_eq!(response.headers().get_one("Content-Type"), Some("text/plain; charset=utf-8"));
+
+        let file = match expected {
+            Some("br") => "other/hello.txt.br",
+            Some(_) => "other/hello.txt.gz",
+            None => "other/hello.txt",
+        };
+
+        assert_eq!(response.into_bytes(), Some(read(file)));
+    }
+
+    // Files without a precompressed variant are served as-is.
+    let response = client.get("/precompressed/index.html")
+        .header(Header::new("Accept-Encoding", "gzip, br"))
+        .dispatch();
+
+    assert!(response.headers().get_one("Content-Encoding").is_none());
+    assert_eq!(response.headers().get_one("Vary"), Some("Accept-Encoding"));
+    assert_eq!(response.into_bytes(), Some(read("index.html")));
+
+    // Variants are only served when enabled.
+    let response = client.get("/default/other/hello.txt")
+        .header(Header::new("Accept-Encoding", "gzip, br"))
+        .dispatch();
+
+    assert!(response.headers().get_one("Content-Encoding").is_none());
+    assert!(response.headers().get_one("Vary").is_none());
+    assert_eq!(response.into_bytes(), Some(read("other/hello.txt")));
+}
+
+/// Listings escape `/` in HTML; unescape it for readable assertions.
+fn unescape(html: String) -> String {
+    html.replace("&#x2F;", "/")
+}
+
+#[test]
+fn test_listing() {
+    let client = Client::debug(rocket()).expect("valid rocket");
+
+    let response = client.get("/listing").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+    let html = unescape(response.into_string().unwrap());
+    assert!(html.contains("<title>Index of /listing</title>"));
+    assert!(html.contains("<a href=\"/listing/inner/\">inner/</a>"));
+    assert!(html.contains("<a href=\"/listing/other/\">other/</a>"));
+    assert!(html.contains("<a href=\"/listing/index.html\">index.html</a></td><td>30</td>"));
+    assert!(!html.contains("../"));
+    assert!(!html.contains(".hidden"));
+
+    // Directories are listed first.
+    assert!(html.find("other/").unwrap() < html.find("index.html").unwrap());
+
+    let html = unescape(client.get("/listing/inner/").dispatch().into_string().unwrap());
+    assert!(html.contains("<a href=\"../\">../</a>"));
+    assert!(html.contains("<a href=\"/listing/inner/goodbye\">goodbye</a>"));
+    assert!(html.contains("<a href=\"/listing/inner/index.html\">index.html</a>"));
+    assert!(!html.contains(".hideme"));
+
+    let html = unescape(client.get("/listing_dots/").dispatch().into_string().unwrap());
+    assert!(html.contains("<a href=\"/listing_dots/.hidden\">.hidden</a>"));
+
+    // Index pages take precedence when enabled.
+    assert_file(&client, "listing_index", "", true);
+    assert_file(&client, "listing_index", "inner/", true);
+    let html = unescape(client.get("/listing_index/other/").dispatch().into_string().unwrap());
+    assert!(html.contains("<a href=\"/listing_index/other/hello.txt\">hello.txt</a>"));
+
+    // Files are served as usual; missing directories still forward.
+    assert_all(&client, "listing", REGULAR_FILES, true);
+    assert_all(&client, "listing", HIDDEN_FILES, false);
+    assert_file(&client, "listing", "inner/nonexistent/", false);
+}
+
+#[test]
+fn test_listing_json() {
+    let client = Client::debug(rocket()).expect("valid rocket");
+    let response = client.get("/listing/other/").header(Accept::JSON).dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    assert_eq!(response.headers().get_one("Vary"), Some("Accept"));
+
+    let json = response.into_string().unwrap();
+    assert!(json.starts_with("{\"path\":\"/listing/other/\",\"entries\":["));
+    assert!(json.contains("\"entries\":[{\"name\":\"hello.txt\","));
+    assert!(json.contains("{\"name\":\"hello.txt\",\"type\":\"file\",\"size\":4,\"modified\":"));
+    assert!(json.ends_with("}]}"));
+
+    let json = client.get("/listing").header(Accept::JSON).dispatch().into_string().unwrap();
+    assert!(json.contains("{\"name\":\"inner\",\"type\":\"directory\",\"size\":0,"));
+
+    // HTML remains preferred unless JSON is.
+    let response = client.get("/listing/other/")
+        .header(Header::new("Accept", "text/html, application/json;q=0.9"))
+        .dispatch();
+
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+}
@@ -0,0 +1,87 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::{Rocket, Route, Build, Config};
+use rocket::http::{Method, Status};
+use rocket::local::asynchronous::Client;
+use rocket::tokio::time::sleep;
+
+#[get("/slow", timeout = 1)]
+async fn slow() -> &'static str {
+    sleep(Duration::from_secs(30)).await;
+    "slow"
+}
+
+#[get("/fast", timeout = 1)]
+fn fast() -> &'static str {
+    "fast"
+}
+
+#[get("/sleep/<ms>")]
+async fn sleepy(ms: u64) -> &'static str {
+    sleep(Duration::from_millis(ms)).await;
+    "awake"
+}
+
+#[get("/untimed/<ms>", timeout = 0)]
+async fn untimed(ms: u64) -> &'static str {
+    sleep(Duration::from_millis(ms)).await;
+    "untimed"
+}
+
+#[catch(503)]
+fn unavailable() -> &'static str {
+    "timed out"
+}
+
+fn rocket(timeout: u32) -> Rocket<Build> {
+    let figment = Config::figment().merge((Config::TIMEOUT, timeout));
+    rocket::custom(figment)
+        .mount("/", routes![slow, fast, sleepy, untimed])
+        .register("/", catchers![unavailable])
+}
+
+#[test]
+fn route_timeout_is_exposed() {
+    let routes: Vec<Route> = routes![slow, sleepy, untimed];
+    assert_eq!(routes[0].timeout, Some(1));
+    assert_eq!(routes[1].timeout, None);
+    assert_eq!(routes[2].timeout, Some(0));
+    assert_eq!(Route::new(Method::Get, "/", rocket::route::dummy_handler).timeout, None);
+}
+
+#[rocket::async_test]
+async fn route_timeout_cancels_handler() {
+    let client = Client::debug(rocket(0)).await.unwrap();
+
+    let response = client.get("/slow").dispatch().await;
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+    assert_eq!(response.into_string().await.unwrap(), "timed out");
+
+    let response = client.get("/fast").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().await.unwrap(), "fast");
+
+    // Without a configured timeout, other routes run to completion.
+    let response = client.get("/sleep/1500").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[rocket::async_test]
+async fn configured_timeout_applies_to_all_routes() {
+    let client = Client::debug(rocket(1)).await.unwrap();
+    assert_eq!(client.rocket().config().timeout, 1);
+
+    let response = client.get("/sleep/30000").dispatch().await;
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+
+    let response = client.get("/sleep/0").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().await.unwrap(), "awake");
+
+    // A route's timeout of `0` disables the configured timeout.
+    let response = client.get("/untimed/1500").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().await.unwrap(), "untimed");
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "health")]
+
+use std::time::Duration;
+
+use rocket::{Rocket, Build, Config};
+use rocket::fairing::AdHoc;
+use rocket::figment::Figment;
+use rocket::health::Health;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+
+struct Flaky(&'static str);
+
+fn rocket(figment: Figment) -> Rocket<Build> {
+    let health = Health::new()
+        .check("always", |_| Box::pin(async move { Ok(()) }))
+        .check("flaky", |rocket| Box::pin(async move {
+            match rocket.state::<Flaky>() {
+                Some(Flaky(e)) => Err(e.to_string()),
+                None => Ok(()),
+            }
+        }));
+
+    rocket::custom(figment).attach(health)
+}
+
+fn get(client: &Client, path: &str) -> (Status, String) {
+    let response = client.get(path.to_string()).dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    (response.status(), response.into_string().unwrap())
+}
+
+#[test]
+fn readiness_reflects_checks() {
+    let client = Client::debug(rocket(Config::figment())).unwrap();
+    assert_eq!(get(&client, "/health/live"), (Status::Ok, r#"{"status":"up"}"#.into()));
+
+    let ready = r#"{"status":"up","checks":{"always":{"status":"up"},"flaky":{"status":"up"}}}"#;
+    assert_eq!(get(&client, "/health/ready"), (Status::Ok, ready.into()));
+
+    let rocket = rocket(Config::figment()).manage(Flaky("it's \"down\""));
+    let client = Client::debug(rocket).unwrap();
+    let (status, body) = get(&client, "/health/ready");
+    assert_eq!(status, Status::ServiceUnavailable);
+    assert!(body.starts_with(r#"{"status":"down","checks":{"always":{"status":"up"},"#));
+    assert!(body.ends_with(r#""flaky":{"status":"down","error":"it's \"down\""}}}"#));
+}
+
+#[test]
+fn registered_checks_run_with_a_timeout() {
+    let registrar = AdHoc::on_ignite("Registrar", |rocket| async {
+        Health::register(rocket, "slow", |_| Box::pin(async move {
+            rocket::tokio::time::sleep(Duration::from_secs(3)).await;
+            Ok(())
+        }))
+    });
+
+    let figment = Config::figment()
+        .merge(("health.path", "/internal/health"))
+        .merge(("health.timeout", 1));
+
+    let client = Client::debug(rocket(figment).attach(registrar)).unwrap();
+    assert_eq!(get(&client, "/internal/health/live").0, Status::Ok);
+
+    let (status, body) = get(&client, "/internal/health/ready");
+    assert_eq!(status, Status::ServiceUnavailable);
+    assert!(body.contains(r#""slow":{"status":"down","error":"timed out after 1s"}"#));
+
+    let figment = Config::figment().merge(("health.path", "/<path>"));
+    let error = Client::debug(rocket(figment)).unwrap_err();
+    assert!(matches!(error.kind(), rocket::error::ErrorKind::FailedFairings(..)));
+}
+
+#[test]
+fn readiness_fails_once_shutdown_is_triggered() {
+    let client = Client::debug(rocket(Config::figment())).unwrap();
+    assert_eq!(get(&client, "/health/ready").0, Status::Ok);
+
+    client.rocket().shutdown().notify();
+    let ready = r#"{"status":"down","shutdown":true,"checks":{}}"#;
+    assert_eq!(get(&client, "/health/ready"), (Status::ServiceUnavailable, ready.into()));
+    assert_eq!(get(&client, "/health/live").0, Status::Ok);
+}
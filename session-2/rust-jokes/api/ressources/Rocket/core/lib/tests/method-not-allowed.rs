@@ -0,0 +1,96 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::Status;
+use rocket::local::blocking::{Client, LocalResponse};
+
+#[get("/items")]
+fn list() -> &'static str { "list" }
+
+#[post("/items")]
+fn create() -> &'static str { "create" }
+
+#[delete("/items/<id>")]
+fn remove(id: usize) -> String { id.to_string() }
+
+#[options("/custom")]
+fn custom_options() -> &'static str { "options" }
+
+#[put("/custom")]
+fn custom_put() { }
+
+#[get("/forward/<n>")]
+fn forward(n: usize) -> String { n.to_string() }
+
+#[catch(405)]
+fn not_allowed() -> &'static str { "not allowed" }
+
+fn client() -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![list, create, remove, custom_options, custom_put, forward]);
+
+    Client::debug(rocket).unwrap()
+}
+
+fn allow(response: &LocalResponse<'_>) -> Option<String> {
+    response.headers().get_one("Allow").map(|s| s.to_string())
+}
+
+#[test]
+fn unsupported_method_is_not_allowed() {
+    let client = client();
+    let response = client.put("/items").dispatch();
+    assert_eq!(response.status(), Status::MethodNotAllowed);
+    assert_eq!(allow(&response).unwrap(), "GET, HEAD, OPTIONS, POST");
+
+    let response = client.get("/items/10").dispatch();
+    assert_eq!(response.status(), Status::MethodNotAllowed);
+    assert_eq!(allow(&response).unwrap(), "DELETE, OPTIONS");
+
+    // `HEAD` is retried as `GET` before giving up.
+    let response = client.head("/custom").dispatch();
+    assert_eq!(response.status(), Status::MethodNotAllowed);
+    assert_eq!(allow(&response).unwrap(), "OPTIONS, PUT");
+}
+
+#[test]
+fn unmatched_uri_is_not_found() {
+    let client = client();
+    let response = client.put("/nothing").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(allow(&response).is_none());
+
+    let response = client.options("/nothing").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    // A forward from a route for the request's method is still a 404.
+    let response = client.get("/forward/ten").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(allow(&response).is_none());
+}
+
+#[test]
+fn options_are_answered() {
+    let client = client();
+    let response = client.options("/items").dispatch();
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(allow(&response).unwrap(), "GET, HEAD, OPTIONS, POST");
+    assert!(response.into_bytes().is_none());
+
+    let response = client.options("/custom").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(allow(&response).is_none());
+    assert_eq!(response.into_string().unwrap(), "options");
+}
+
+#[test]
+fn not_allowed_uses_catcher() {
+    let rocket = rocket::build()
+        .mount("/", routes![list])
+        .register("/", catchers![not_allowed]);
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.post("/items").dispatch();
+    assert_eq!(response.status(), Status::MethodNotAllowed);
+    assert_eq!(allow(&response).unwrap(), "GET, HEAD, OPTIONS");
+    assert_eq!(response.into_string().unwrap(), "not allowed");
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "metrics")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, Config};
+use rocket::figment::Figment;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket::metrics::Metrics;
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+#[get("/hello/<name>")]
+fn hello(name: &str) -> String {
+    format!("Hello, {}!", name)
+}
+
+#[get("/number/<n>")]
+fn number(n: usize) -> String {
+    n.to_string()
+}
+
+#[get("/teapot")]
+fn teapot() -> Status {
+    Status::ImATeapot
+}
+
+fn rocket(figment: Figment) -> Rocket<Build> {
+    rocket::custom(figment)
+        .mount("/", routes![index, hello, number, teapot])
+        .attach(Metrics::new())
+}
+
+fn metrics(client: &Client, path: &str) -> String {
+    let response = client.get(path.to_string()).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type().unwrap().to_string(), "text/plain; version=0.0.4");
+    response.into_string().unwrap()
+}
+
+#[test]
+fn requests_are_counted_by_route_and_status() {
+    let client = Client::debug(rocket(Config::figment())).unwrap();
+    client.get("/").dispatch();
+    client.get("/").dispatch();
+    client.head("/").dispatch();
+    client.get("/hello/Bob").dispatch();
+    client.get("/hello/Alice").dispatch();
+    client.get("/nope").dispatch();
+
+    // Neither a route that forwards nor one that fails labels the response.
+    client.get("/number/ten").dispatch();
+    client.get("/teapot").dispatch();
+
+    let metrics = metrics(&client, "/metrics");
+    let lines: Vec<_> = metrics.lines().collect();
+    assert!(lines.contains(&r#"http_requests_total{method="GET",route="index",status="200"} 2"#));
+    assert!(lines.contains(&r#"http_requests_total{method="HEAD",route="index",status="200"} 1"#));
+    assert!(lines.contains(&r#"http_requests_total{method="GET",route="hello",status="200"} 2"#));
+    assert!(lines.contains(&r#"http_requests_total{method="GET",route="",status="404"} 2"#));
+    assert!(lines.contains(&r#"http_requests_total{method="GET",route="",status="418"} 1"#));
+    assert!(!metrics.contains("route=\"number\"") && !metrics.contains("route=\"teapot\""));
+
+    // The request for metrics is in flight but hasn't been counted yet.
+    assert!(lines.contains(&r#"http_requests_in_flight{method="GET"} 1"#));
+    assert!(!metrics.contains("route=\"Metrics\""));
+
+    let size = r#"http_response_size_bytes_sum{method="GET",route="hello",status="200"} 24"#;
+    assert!(lines.contains(&size));
+
+    let latency = r#"_count{method="GET",route="index",status="200"} 2"#;
+    assert!(metrics.contains(&format!("\nhttp_request_duration_seconds{}\n", latency)));
+
+    let total = r#"http_requests_total{method="GET",route="Metrics",status="200"} 1"#;
+    assert!(self::metrics(&client, "/metrics").lines().any(|line| line == total));
+}
+
+#[test]
+fn metrics_are_configurable() {
+    let figment = Config::figment()
+        .merge(("metrics.path", "/internal/metrics"))
+        .merge(("metrics.latency_buckets", [1.0]));
+
+    let client = Client::debug(rocket(figment)).unwrap();
+    assert_eq!(client.get("/metrics").dispatch().status(), Status::NotFound);
+
+    let metrics = metrics(&client, "/internal/metrics");
+    let bucket = r#"_bucket{method="GET",route="",status="404",le="1"} 1"#;
+    assert!(metrics.lines().any(|line| line == format!("http_request_duration_seconds{}", bucket)));
+    assert!(!metrics.contains("le=\"0.5\""));
+}
+
+#[test]
+fn invalid_config_fails_ignition() {
+    let figment = Config::figment().merge(("metrics.size_buckets", [2.0, 1.0]));
+    let error = Client::debug(rocket(figment)).unwrap_err();
+    assert!(matches!(error.kind(), rocket::error::ErrorKind::FailedFairings(..)));
+
+    let figment = Config::figment().merge(("metrics.path", "/<metrics>"));
+    let error = Client::debug(rocket(figment)).unwrap_err();
+    assert!(matches!(error.kind(), rocket::error::ErrorKind::FailedFairings(..)));
+}
@@ -0,0 +1,142 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, Config};
+use rocket::data::{Limits, ToByteUnit};
+use rocket::figment::Figment;
+use rocket::form::Multipart;
+use rocket::futures::TryStreamExt;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+
+#[post("/", data = "<form>")]
+async fn parts(mut form: Multipart<'_>) -> Result<String, Status> {
+    let mut parts = vec![];
+    while let Some(part) = form.next().await.map_err(|e| e.status())? {
+        let name = part.name().unwrap_or("?").to_string();
+        let kind = match part.file_name() {
+            Some(file_name) => format!("file {:?}", file_name.as_str().unwrap_or("?")),
+            None if part.is_file() => "data".to_string(),
+            None => "value".to_string(),
+        };
+
+        let limit = part.limit();
+        let data = part.try_fold(0, |n, chunk| async move { Ok(n + chunk.len()) }).await
+            .map_err(|_| Status::PayloadTooLarge)?;
+
+        parts.push(format!("{}: {} ({} of {})", name, kind, data, limit));
+    }
+
+    Ok(parts.join("\n"))
+}
+
+#[post("/", rank = 2)]
+fn other() -> &'static str {
+    "other"
+}
+
+fn rocket(limits: Limits) -> Rocket<Build> {
+    let figment = Figment::from(Config::debug_default()).merge(("limits", limits));
+    rocket::custom(figment).mount("/", routes![parts, other])
+}
+
+const BODY: &str = "--X\r\n\
+    Content-Disposition: form-data; name=\"title\"\r\n\r\n\
+    holiday\r\n\
+    --X\r\n\
+    Content-Disposition: form-data; name=\"photo\"; filename=\"beach.png\"\r\n\
+    Content-Type: image/png\r\n\r\n\
+    0123456789\r\n\
+    --X\r\n\
+    Content-Disposition: form-data; name=\"notes\"\r\n\
+    Content-Type: text/plain\r\n\r\n\
+    sunny\r\n\
+    --X--\r\n";
+
+fn dispatch(client: &Client, body: &str) -> (Status, String) {
+    let form = ContentType::with_params("multipart", "form-data", ("boundary", "X"));
+    let response = client.post("/").header(form).body(body).dispatch();
+    (response.status(), response.into_string().unwrap())
+}
+
+#[test]
+fn parts_are_streamed_in_order() {
+    let limits = Limits::default().limit("string", 1.kibibytes()).limit("file", 2.kibibytes());
+    let client = Client::debug(rocket(limits)).unwrap();
+    let (status, body) = dispatch(&client, BODY);
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body, "title: value (7 of 1KiB)\n\
+        photo: file \"beach\" (10 of 2KiB)\n\
+        notes: data (5 of 2KiB)");
+}
+
+#[test]
+fn parts_are_limited_by_name() {
+    let limits = Limits::default()
+        .limit("file/png", 4.bytes())
+        .limit("multipart/notes", 3.bytes())
+        .limit("multipart/photo", 16.bytes());
+
+    let client = Client::debug(rocket(limits)).unwrap();
+    let (status, _) = dispatch(&client, BODY);
+    assert_eq!(status, Status::PayloadTooLarge);
+
+    let limits = Limits::default().limit("file/png", 4.bytes()).limit("multipart/notes", 8.bytes());
+    let client = Client::debug(rocket(limits)).unwrap();
+    let (status, _) = dispatch(&client, BODY);
+    assert_eq!(status, Status::PayloadTooLarge);
+
+    let limits = Limits::default()
+        .limit("file/png", 16.bytes())
+        .limit("multipart/notes", 8.bytes());
+
+    let client = Client::debug(rocket(limits)).unwrap();
+    let (status, body) = dispatch(&client, BODY);
+    assert_eq!(status, Status::Ok);
+    assert!(body.contains("photo: file \"beach\" (10 of 16B)"));
+    assert!(body.contains("notes: data (5 of 8B)"));
+}
+
+#[test]
+fn non_multipart_requests_are_forwarded() {
+    let client = Client::debug(rocket(Limits::default())).unwrap();
+    let response = client.post("/").header(ContentType::Form).body("a=b").dispatch();
+    assert_eq!(response.into_string().unwrap(), "other");
+
+    let response = client.post("/")
+        .header(ContentType::new("multipart", "form-data"))
+        .body(BODY)
+        .dispatch();
+
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[cfg(feature = "csrf")]
+#[get("/")]
+fn token(token: rocket::csrf::CsrfToken<'_>) -> String {
+    token.to_string()
+}
+
+#[test]
+#[cfg(feature = "csrf")]
+fn token_part_is_verified_and_skipped() {
+    let with_token = |token: &str| format!("--X\r\n\
+        Content-Disposition: form-data; name=\"_csrf\"\r\n\r\n\
+        {}\r\n{}", token, BODY);
+
+    let rocket = rocket(Limits::default())
+        .mount("/", routes![token])
+        .attach(rocket::csrf::Csrf::new());
+
+    let client = Client::tracked(rocket).unwrap();
+    let token = client.get("/").dispatch().into_string().unwrap();
+
+    let (status, body) = dispatch(&client, &with_token(&token));
+    assert_eq!(status, Status::Ok);
+    assert!(body.starts_with("title: value"));
+
+    let (status, _) = dispatch(&client, &with_token("forged"));
+    assert_eq!(status, Status::Forbidden);
+
+    let (status, _) = dispatch(&client, BODY);
+    assert_eq!(status, Status::Forbidden);
+}
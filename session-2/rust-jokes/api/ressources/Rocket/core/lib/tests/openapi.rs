@@ -0,0 +1,200 @@
+#![cfg(feature = "openapi")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::form::Form;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::openapi::{OpenApi, Responses, Schema};
+use rocket::response::status;
+use rocket::serde::{Deserialize, Serialize, json::{Json, Value}};
+
+/// A joke.
+#[derive(Serialize, Deserialize, Schema)]
+#[serde(crate = "rocket::serde", rename_all = "camelCase")]
+struct Joke {
+    id: u32,
+    /// The setup.
+    setup_line: String,
+    punchline: Option<String>,
+    kind: Kind,
+    #[serde(skip)]
+    _votes: usize,
+}
+
+#[derive(Serialize, Deserialize, Schema)]
+#[serde(crate = "rocket::serde")]
+enum Kind {
+    Pun,
+    #[serde(rename = "one-liner")]
+    OneLiner,
+}
+
+#[derive(FromForm, Schema)]
+struct Filter<'r> {
+    #[field(name = "q")]
+    query: &'r str,
+    #[field(default = 10)]
+    limit: usize,
+}
+
+#[derive(Responder, Responses)]
+enum Lookup {
+    Found(Json<Joke>),
+    /// There's no such joke.
+    #[response(status = 404)]
+    Missing(&'static str),
+    #[response(status = 500, content_type = "json")]
+    Failed(String),
+}
+
+/// Fetches a joke.
+///
+/// Jokes are fetched by their ID.
+#[get("/jokes/<id>")]
+#[openapi(tag = "jokes")]
+fn joke(id: u32) -> Lookup {
+    Lookup::Missing(if id == 0 { "zero" } else { "none" })
+}
+
+/// Searches jokes.
+#[get("/jokes?<filter..>")]
+#[openapi(tag = "jokes", deprecated)]
+fn search(filter: Filter<'_>) -> Json<Vec<Joke>> {
+    let _ = (filter.query, filter.limit);
+    Json(vec![])
+}
+
+#[post("/jokes?<dry_run>", data = "<joke>", format = "json")]
+#[openapi]
+fn add(joke: Json<Joke>, dry_run: Option<bool>) -> status::Created<Json<Joke>> {
+    let _ = dry_run;
+    status::Created::new("/jokes/1").body(joke)
+}
+
+#[post("/votes/<id>", data = "<vote>")]
+#[openapi]
+fn vote(id: u32, vote: Form<bool>) -> Option<()> {
+    let _ = (id, vote);
+    None
+}
+
+#[get("/hidden")]
+fn hidden() -> &'static str {
+    "hidden"
+}
+
+fn client(config: Option<(&str, &str)>) -> Client {
+    let mut figment = rocket::Config::figment();
+    if let Some(param) = config {
+        figment = figment.merge(param);
+    }
+
+    let rocket = rocket::custom(figment)
+        .mount("/", routes![joke, search, add, vote, hidden])
+        .attach(OpenApi::new());
+
+    Client::debug(rocket).unwrap()
+}
+
+fn document(client: &Client, path: &str) -> Value {
+    let response = client.get(path).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    response.into_json().unwrap()
+}
+
+#[test]
+fn document_describes_marked_routes() {
+    let client = client(None);
+    let doc = document(&client, "/openapi.json");
+    assert_eq!(doc["openapi"], "3.0.3");
+    assert_eq!(doc["info"]["title"], "API");
+
+    let paths = doc["paths"].as_object().unwrap();
+    let mut templates: Vec<_> = paths.keys().map(|k| k.as_str()).collect();
+    templates.sort();
+    assert_eq!(templates, &["/jokes", "/jokes/{id}", "/votes/{id}"]);
+
+    let get = &paths["/jokes/{id}"]["get"];
+    assert_eq!(get["operationId"], "joke");
+    assert_eq!(get["summary"], "Fetches a joke.");
+    assert_eq!(get["description"], "Jokes are fetched by their ID.");
+    assert_eq!(get["tags"][0], "jokes");
+    assert!(get.get("deprecated").is_none());
+    assert_eq!(get["parameters"][0]["in"], "path");
+    assert_eq!(get["parameters"][0]["name"], "id");
+    assert_eq!(get["parameters"][0]["schema"]["type"], "integer");
+
+    let search = &paths["/jokes"]["get"];
+    assert_eq!(search["deprecated"], true);
+    let params = search["parameters"].as_array().unwrap();
+    let required = |name: &str| {
+        params.iter().find(|p| p["name"] == name).unwrap()["required"].clone()
+    };
+
+    assert_eq!(params.len(), 2);
+    assert_eq!(required("q"), true);
+    assert_eq!(required("limit"), false);
+}
+
+#[test]
+fn document_describes_bodies_and_responses() {
+    let client = client(None);
+    let doc = document(&client, "/openapi.json");
+    let joke_ref = "#/components/schemas/Joke";
+
+    let get = &doc["paths"]["/jokes/{id}"]["get"];
+    let ok = &get["responses"]["200"];
+    assert_eq!(ok["content"]["application/json"]["schema"]["$ref"], joke_ref);
+    assert_eq!(get["responses"]["404"]["description"], "There's no such joke.");
+    assert!(get["responses"]["404"]["content"]["text/plain"].is_object());
+    assert!(get["responses"]["500"]["content"]["application/json"].is_object());
+
+    let add = &doc["paths"]["/jokes"]["post"];
+    assert_eq!(add["parameters"][0]["name"], "dry_run");
+    assert_eq!(add["parameters"][0]["required"], false);
+    let body = &add["requestBody"]["content"]["application/json"]["schema"];
+    assert_eq!(body["$ref"], joke_ref);
+    assert!(add["responses"]["201"].is_object());
+
+    let vote = &doc["paths"]["/votes/{id}"]["post"];
+    let content = vote["requestBody"]["content"].as_object().unwrap();
+    assert!(content.contains_key("application/x-www-form-urlencoded"));
+    assert!(content.contains_key("multipart/form-data"));
+    assert!(vote["responses"]["200"].is_object());
+    assert!(vote["responses"]["404"].is_object());
+}
+
+#[test]
+fn document_contains_derived_schemas() {
+    let client = client(None);
+    let doc = document(&client, "/openapi.json");
+    let schemas = &doc["components"]["schemas"];
+
+    let joke = &schemas["Joke"];
+    assert_eq!(joke["description"], "A joke.");
+    let properties = joke["properties"].as_object().unwrap();
+    let mut names: Vec<_> = properties.keys().map(|k| k.as_str()).collect();
+    names.sort();
+    assert_eq!(names, &["id", "kind", "punchline", "setupLine"]);
+    assert_eq!(properties["setupLine"]["description"], "The setup.");
+    assert_eq!(properties["kind"]["$ref"], "#/components/schemas/Kind");
+    assert_eq!(joke["required"], rocket::serde::json::json!(["id", "setupLine", "kind"]));
+
+    assert_eq!(schemas["Kind"]["enum"], rocket::serde::json::json!(["Pun", "one-liner"]));
+}
+
+#[test]
+fn document_path_is_configurable() {
+    let client = client(Some(("openapi.path", "/api/spec.json")));
+    let doc = document(&client, "/api/spec.json");
+    assert!(doc["paths"].is_object());
+    assert_eq!(client.get("/openapi.json").dispatch().status(), Status::NotFound);
+
+    let rocket = rocket::custom(rocket::Config::figment().merge(("openapi.path", "/<bad>")))
+        .attach(OpenApi::new());
+
+    let error = Client::debug(rocket).unwrap_err();
+    assert!(matches!(error.kind(), rocket::error::ErrorKind::FailedFairings(..)));
+}
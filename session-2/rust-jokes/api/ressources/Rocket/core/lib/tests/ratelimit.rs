@@ -0,0 +1,162 @@
+#![cfg(feature = "ratelimit")]
+
+#[macro_use] extern crate rocket;
+
+use std::net::SocketAddr;
+
+use rocket::{Rocket, Build, Config};
+use rocket::http::{Header, Status};
+use rocket::local::blocking::{Client, LocalResponse};
+use rocket::ratelimit::{RateLimit, Limit, Quota};
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+#[post("/login")]
+fn login(quota: Quota) -> String {
+    format!("{:?}", quota.remaining())
+}
+
+#[get("/search")]
+fn search(quota: Quota) -> String {
+    format!("{:?}", quota.remaining())
+}
+
+#[catch(429)]
+fn too_many() -> &'static str {
+    "slow down"
+}
+
+fn rocket(global: Option<Limit>) -> Rocket<Build> {
+    let mut figment = Config::figment().merge(("ratelimit.routes.login", Limit::new(2, 60)));
+    if let Some(global) = global {
+        figment = figment.merge(("ratelimit.global", global));
+    }
+
+    rocket::custom(figment)
+        .mount("/", routes![index, login, search])
+        .register("/", catchers![too_many])
+}
+
+fn addr(ip: &str) -> SocketAddr {
+    format!("{}:8000", ip).parse().unwrap()
+}
+
+fn header<'c>(response: &'c LocalResponse<'_>, name: &str) -> Option<&'c str> {
+    response.headers().get_one(name)
+}
+
+#[test]
+fn global_limit() {
+    let client = Client::debug(rocket(Some(Limit::new(3, 60))).attach(RateLimit::new())).unwrap();
+    assert!(client.rocket().routes().all(|route| route.name.as_deref() != Some("RateLimit")));
+
+    for (i, uri) in ["/", "/search", "/missing"].iter().enumerate() {
+        let response = client.get(*uri).remote(addr("10.0.0.1")).dispatch();
+        assert_ne!(response.status(), Status::TooManyRequests);
+        assert_eq!(header(&response, "RateLimit-Limit"), Some("3"));
+        assert_eq!(header(&response, "RateLimit-Remaining"), Some(&*(2 - i).to_string()));
+        assert!(header(&response, "Retry-After").is_none());
+    }
+
+    let response = client.get("/").remote(addr("10.0.0.1")).dispatch();
+    assert_eq!(response.status(), Status::TooManyRequests);
+    assert_eq!(header(&response, "RateLimit-Remaining"), Some("0"));
+    assert_eq!(header(&response, "Retry-After"), Some("20"));
+    assert_eq!(response.into_string().unwrap(), "slow down");
+
+    // Other clients, identified by IP, have their own buckets.
+    let response = client.get("/").remote(addr("10.0.0.2")).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.get("/")
+        .remote(addr("10.0.0.1"))
+        .header(Header::new("X-Real-IP", "10.0.0.3"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+
+    // Requests without a client IP aren't limited.
+    for _ in 0..5 {
+        let response = client.get("/").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert!(header(&response, "RateLimit-Limit").is_none());
+    }
+}
+
+#[test]
+fn route_limit() {
+    let client = Client::debug(rocket(None).attach(RateLimit::new())).unwrap();
+
+    for remaining in &["Some(1)", "Some(0)"] {
+        let response = client.post("/login").remote(addr("10.0.0.1")).dispatch();
+        assert_eq!(header(&response, "RateLimit-Limit"), Some("2"));
+        assert_eq!(response.into_string().unwrap(), *remaining);
+    }
+
+    let response = client.post("/login").remote(addr("10.0.0.1")).dispatch();
+    assert_eq!(response.status(), Status::TooManyRequests);
+    assert_eq!(header(&response, "Retry-After"), Some("30"));
+
+    // Routes without a configured limit, and other routes, are unaffected.
+    for _ in 0..5 {
+        let response = client.get("/search").remote(addr("10.0.0.1")).dispatch();
+        assert!(header(&response, "RateLimit-Limit").is_none());
+        assert_eq!(response.into_string().unwrap(), "None");
+
+        let response = client.get("/").remote(addr("10.0.0.1")).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+    }
+}
+
+#[test]
+fn most_restrictive_headers() {
+    let client = Client::debug(rocket(Some(Limit::new(10, 60))).attach(RateLimit::new())).unwrap();
+
+    let response = client.post("/login").remote(addr("10.0.0.1")).dispatch();
+    assert_eq!(header(&response, "RateLimit-Limit"), Some("2"));
+    assert_eq!(header(&response, "RateLimit-Remaining"), Some("1"));
+
+    let response = client.get("/").remote(addr("10.0.0.1")).dispatch();
+    assert_eq!(header(&response, "RateLimit-Limit"), Some("10"));
+    assert_eq!(header(&response, "RateLimit-Remaining"), Some("8"));
+}
+
+#[test]
+fn custom_key() {
+    let rate_limit = RateLimit::new()
+        .key(|req| req.headers().get_one("X-Api-Key").map(String::from));
+
+    let client = Client::debug(rocket(None).attach(rate_limit)).unwrap();
+    let login = |key: &str| client.post("/login")
+        .remote(addr("10.0.0.1"))
+        .header(Header::new("X-Api-Key", key.to_string()))
+        .dispatch()
+        .status();
+
+    assert_eq!(login("a"), Status::Ok);
+    assert_eq!(login("a"), Status::Ok);
+    assert_eq!(login("a"), Status::TooManyRequests);
+    assert_eq!(login("b"), Status::Ok);
+
+    // No key, no limit.
+    for _ in 0..5 {
+        let response = client.post("/login").remote(addr("10.0.0.1")).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+    }
+}
+
+#[test]
+fn quota_requires_fairing() {
+    let error = Client::debug(rocket(None)).unwrap_err();
+    assert!(matches!(error.kind(), rocket::error::ErrorKind::SentinelAborts(..)));
+}
+
+#[test]
+fn invalid_config_fails_ignite() {
+    let rocket = rocket(Some(Limit::new(0, 60))).attach(RateLimit::new());
+    let error = Client::debug(rocket).unwrap_err();
+    assert!(matches!(error.kind(), rocket::error::ErrorKind::FailedFairings(..)));
+}
@@ -0,0 +1,69 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, Config};
+use rocket::figment::Figment;
+use rocket::http::Header;
+use rocket::local::blocking::Client;
+use rocket::request::RequestId;
+
+#[get("/")]
+fn index(id: &RequestId) -> String {
+    assert_eq!(RequestId::current().as_deref(), Some(id.as_str()));
+    id.to_string()
+}
+
+#[get("/spawned")]
+async fn spawned() -> String {
+    let id = rocket::tokio::spawn(async { RequestId::current() }).await.unwrap();
+    format!("{:?}", id)
+}
+
+fn rocket(figment: Figment) -> Rocket<Build> {
+    rocket::custom(figment).mount("/", routes![index, spawned])
+}
+
+#[test]
+fn request_ids_are_echoed() {
+    let client = Client::debug(rocket(Config::figment())).unwrap();
+    let response = client.get("/").header(Header::new("X-Request-Id", "abc-123")).dispatch();
+    assert_eq!(response.headers().get_one("X-Request-Id"), Some("abc-123"));
+    assert_eq!(response.into_string().unwrap(), "abc-123");
+
+    // Catcher responses carry the id too.
+    let response = client.get("/404").header(Header::new("X-Request-Id", "def")).dispatch();
+    assert_eq!(response.headers().get_one("X-Request-Id"), Some("def"));
+
+    let response = client.get("/spawned").dispatch();
+    assert_eq!(response.into_string().unwrap(), "None");
+}
+
+#[test]
+fn missing_or_invalid_request_ids_are_generated() {
+    let client = Client::debug(rocket(Config::figment())).unwrap();
+    let response = client.get("/").dispatch();
+    let id = response.headers().get_one("X-Request-Id").unwrap().to_string();
+    assert_eq!(id.len(), 16);
+    assert_eq!(response.into_string().unwrap(), id);
+
+    let response = client.get("/").dispatch();
+    assert_ne!(response.headers().get_one("X-Request-Id").unwrap(), id);
+
+    for invalid in vec!["".into(), "a b".into(), "é".into(), "a".repeat(201)] {
+        let response = client.get("/").header(Header::new("X-Request-Id", invalid)).dispatch();
+        assert_eq!(response.headers().get_one("X-Request-Id").unwrap().len(), 16);
+    }
+}
+
+#[test]
+fn request_id_header_is_configurable() {
+    let figment = Config::figment().merge(("request_id_header", "X-Correlation-Id"));
+    let client = Client::debug(rocket(figment)).unwrap();
+    let response = client.get("/")
+        .header(Header::new("X-Request-Id", "abc"))
+        .header(Header::new("X-Correlation-Id", "xyz"))
+        .dispatch();
+
+    assert_eq!(response.headers().get_one("X-Correlation-Id"), Some("xyz"));
+    assert!(response.headers().get_one("X-Request-Id").is_none());
+    assert_eq!(response.into_string().unwrap(), "xyz");
+}
@@ -0,0 +1,151 @@
+#![cfg(feature = "sessions")]
+
+#[macro_use] extern crate rocket;
+
+use std::time::{Duration, SystemTime};
+
+use rocket::{Rocket, Build};
+use rocket::http::{Cookie, Status};
+use rocket::local::asynchronous::Client;
+use rocket::session::{Session, Sessions, SessionStore, MemoryStore, FileStore, Record};
+
+#[get("/")]
+fn index(session: Session<'_, Vec<String>>) -> String {
+    match session.is_new() {
+        true => format!("new: {}", session.get().join(",")),
+        false => session.get().join(","),
+    }
+}
+
+#[post("/<item>")]
+fn push(item: String, session: Session<'_, Vec<String>>) -> String {
+    session.update(|items| { items.push(item); items.len().to_string() })
+}
+
+#[post("/rotate")]
+fn rotate(session: Session<'_, Vec<String>>) {
+    session.rotate();
+}
+
+#[post("/destroy")]
+fn destroy(session: Session<'_, Vec<String>>) {
+    session.destroy();
+}
+
+#[get("/wrong")]
+fn wrong(_a: Session<'_, Vec<String>>, _b: Session<'_, usize>) { }
+
+fn rocket<S: SessionStore>(store: S) -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![index, push, rotate, destroy, wrong])
+        .attach(Sessions::new(store))
+}
+
+fn session_id(client: &Client) -> Option<String> {
+    client.cookies().get_private("rocket_session").map(|c| c.value().to_string())
+}
+
+#[async_test]
+async fn sessions_persist() {
+    let store = MemoryStore::new();
+    let client = Client::tracked(rocket(store.clone())).await.unwrap();
+
+    // Reading doesn't create a session.
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "new: ");
+    assert!(session_id(&client).is_none());
+    assert!(store.is_empty());
+
+    assert_eq!(client.post("/a").dispatch().await.into_string().await.unwrap(), "1");
+    assert_eq!(client.post("/b").dispatch().await.into_string().await.unwrap(), "2");
+    assert_eq!(client.get("/").dispatch().await.into_string().await.unwrap(), "a,b");
+
+    let id = session_id(&client).expect("session cookie");
+    assert_eq!(store.len(), 1);
+    assert_eq!(store.load(&id).await.unwrap().unwrap().data, r#"["a","b"]"#);
+}
+
+#[async_test]
+async fn sessions_rotate_and_destroy() {
+    let store = MemoryStore::new();
+    let client = Client::tracked(rocket(store.clone())).await.unwrap();
+
+    client.post("/a").dispatch().await;
+    let old = session_id(&client).unwrap();
+
+    client.post("/rotate").dispatch().await;
+    let new = session_id(&client).unwrap();
+    assert_ne!(old, new);
+    assert!(store.load(&old).await.unwrap().is_none());
+    assert!(store.load(&new).await.unwrap().is_some());
+    assert_eq!(client.get("/").dispatch().await.into_string().await.unwrap(), "a");
+
+    client.post("/destroy").dispatch().await;
+    assert!(session_id(&client).is_none());
+    assert!(store.is_empty());
+    assert_eq!(client.get("/").dispatch().await.into_string().await.unwrap(), "new: ");
+}
+
+#[async_test]
+async fn sessions_expire() {
+    let store = MemoryStore::new();
+    let client = Client::untracked(rocket(store.clone())).await.unwrap();
+
+    let now = SystemTime::now();
+    let hour_ago = now - Duration::from_secs(3600);
+    let live = Record { data: r#"["live"]"#.into(), created: hour_ago, accessed: now };
+    let idle = Record { data: r#"["idle"]"#.into(), created: hour_ago, accessed: hour_ago };
+    store.save("live", live).await.unwrap();
+    store.save("idle", idle).await.unwrap();
+
+    let response = client.get("/")
+        .private_cookie(Cookie::new("rocket_session", "live"))
+        .dispatch().await;
+
+    assert_eq!(response.into_string().await.unwrap(), "live");
+
+    let response = client.get("/")
+        .private_cookie(Cookie::new("rocket_session", "idle"))
+        .dispatch().await;
+
+    assert_eq!(response.into_string().await.unwrap(), "new: ");
+    assert!(store.load("idle").await.unwrap().is_none());
+
+    // An unsigned cookie is ignored.
+    let response = client.get("/")
+        .cookie(Cookie::new("rocket_session", "live"))
+        .dispatch().await;
+
+    assert_eq!(response.into_string().await.unwrap(), "new: ");
+}
+
+#[async_test]
+async fn unreadable_sessions_are_replaced() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = FileStore::new(dir.path());
+    let client = Client::untracked(rocket(store.clone())).await.unwrap();
+
+    let path = dir.path().join("corrupt.json");
+    std::fs::write(&path, "{").unwrap();
+    let response = client.get("/")
+        .private_cookie(Cookie::new("rocket_session", "corrupt"))
+        .dispatch().await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().await.unwrap(), "new: ");
+    assert!(!path.exists());
+}
+
+#[async_test]
+async fn mismatched_session_types_fail() {
+    let client = Client::untracked(rocket(MemoryStore::new())).await.unwrap();
+    let response = client.get("/wrong").dispatch().await;
+    assert_eq!(response.status(), Status::InternalServerError);
+}
+
+#[async_test]
+async fn sessions_require_fairing() {
+    let rocket = rocket::build().mount("/", routes![index]);
+    let error = Client::untracked(rocket).await.unwrap_err();
+    assert!(matches!(error.kind(), rocket::error::ErrorKind::SentinelAborts(..)));
+}
@@ -250,3 +250,118 @@ fn permission_test() {
             "accelerometer=(\"http://rocket.rs\" \"https://rocket.rs\"), usb=()");
     });
 }
+
+#[test]
+fn csp_test() {
+    let shield = Shield::default().enable(Csp::default());
+    dispatch!(shield, |response: LocalResponse<'_>| {
+        assert_header!(response, "Content-Security-Policy",
+            "default-src 'self'; object-src 'none'; base-uri 'self'");
+    });
+
+    let csp = Csp::new()
+        .directive(Directive::ScriptSrc, [Source::This, Source::host("https://cdn.rocket.rs")])
+        .report_uri(uri!("https://rocket.rs/csp"));
+
+    let shield = Shield::default().enable(CspReportOnly::from(csp));
+    dispatch!(shield, |response: LocalResponse<'_>| {
+        assert_no_header!(response, "Content-Security-Policy");
+        assert_header!(response, "Content-Security-Policy-Report-Only",
+            "script-src 'self' https://cdn.rocket.rs; report-uri https://rocket.rs/csp");
+    });
+
+    let trial = Csp::new().directive(Directive::ImgSrc, Source::None);
+    let shield = Shield::default().enable(Csp::default()).enable(CspReportOnly::from(trial));
+    dispatch!(shield, |response: LocalResponse<'_>| {
+        assert_header!(response, "Content-Security-Policy",
+            "default-src 'self'; object-src 'none'; base-uri 'self'");
+        assert_header!(response, "Content-Security-Policy-Report-Only", "img-src 'none'");
+    });
+}
+
+#[get("/nonce")]
+fn nonce(nonce: Nonce, again: Nonce) -> String {
+    assert_eq!(nonce, again);
+    nonce.to_string()
+}
+
+#[test]
+fn csp_nonce_test() {
+    let csp = Csp::default().directive(Directive::ScriptSrc, [Source::This, Source::Nonce]);
+    let rocket = rocket::build().mount("/", routes![nonce]).attach(Shield::default().enable(csp));
+    let client = Client::debug(rocket).unwrap();
+
+    let mut nonces = vec![];
+    for _ in 0..2 {
+        let response = client.get("/nonce").dispatch();
+        let header = response.headers().get_one("Content-Security-Policy").unwrap().to_string();
+        let nonce = response.into_string().unwrap();
+        assert_eq!(nonce.len(), 22);
+        assert!(header.ends_with(&format!("; script-src 'self' 'nonce-{}'", nonce)));
+        nonces.push(nonce);
+    }
+
+    assert_ne!(nonces[0], nonces[1]);
+
+    // A nonce is rendered even if the handler never asked for it.
+    let response = client.get("/missing").dispatch();
+    let header = response.headers().get_one("Content-Security-Policy").unwrap();
+    assert!(header.contains("'nonce-"));
+
+    // Enforced and report-only policies share the request's nonce.
+    let csp = Csp::new().directive(Directive::StyleSrc, Source::Nonce);
+    let trial = Csp::new().directive(Directive::ScriptSrc, Source::Nonce);
+    let shield = Shield::default().enable(csp).enable(CspReportOnly::from(trial));
+    let rocket = rocket::build().mount("/", routes![nonce]).attach(shield);
+    let client = Client::debug(rocket).unwrap();
+    let response = client.get("/nonce").dispatch();
+    let enforced = response.headers().get_one("Content-Security-Policy").unwrap().to_string();
+    let trial = response.headers().get_one("Content-Security-Policy-Report-Only").unwrap();
+    let trial = trial.to_string();
+    let nonce = response.into_string().unwrap();
+    assert_eq!(enforced, format!("style-src 'nonce-{}'", nonce));
+    assert_eq!(trial, format!("script-src 'nonce-{}'", nonce));
+}
+
+#[get("/embed")]
+fn embed() -> Override<&'static str> {
+    Override::new("embed")
+        .disable::<Frame>()
+        .enable(Permission::allowed(Feature::Camera, Allow::This))
+        .enable(Referrer::SameOrigin)
+}
+
+#[get("/csp")]
+fn csp_override(nonce: Nonce) -> Override<String> {
+    let csp = Csp::new().directive(Directive::ScriptSrc, Source::Nonce);
+    Override::new(nonce.to_string()).enable(csp).disable::<Hsts>()
+}
+
+#[test]
+fn override_test() {
+    let shield = Shield::default().enable(Referrer::NoReferrer).enable(Csp::default());
+    let rocket = rocket::build()
+        .mount("/", routes![hello, embed, csp_override])
+        .attach(shield);
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.get("/embed").dispatch();
+    assert_no_header!(response, "X-Frame-Options");
+    assert_header!(response, "Permissions-Policy", "camera=(self)");
+    assert_header!(response, "Referrer-Policy", "same-origin");
+    assert_header!(response, "X-Content-Type-Options", "nosniff");
+    assert_header!(response, "Content-Security-Policy",
+        "default-src 'self'; object-src 'none'; base-uri 'self'");
+
+    let response = client.get("/csp").dispatch();
+    let header = response.headers().get_one("Content-Security-Policy").unwrap().to_string();
+    assert_header!(response, "X-Frame-Options", "SAMEORIGIN");
+    assert_header!(response, "Referrer-Policy", "no-referrer");
+    assert_eq!(header, format!("script-src 'nonce-{}'", response.into_string().unwrap()));
+
+    // Other routes are unaffected.
+    let response = client.get("/").dispatch();
+    assert_header!(response, "X-Frame-Options", "SAMEORIGIN");
+    assert_header!(response, "Permissions-Policy", "interest-cohort=()");
+    assert_header!(response, "Referrer-Policy", "no-referrer");
+}
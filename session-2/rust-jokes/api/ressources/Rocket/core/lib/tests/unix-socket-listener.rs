@@ -0,0 +1,66 @@
+#![cfg(unix)]
+
+#[macro_use] extern crate rocket;
+
+use std::net::SocketAddr;
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+use rocket::Config;
+use rocket::config::Address;
+use rocket::fairing::AdHoc;
+use rocket::futures::channel::oneshot;
+use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+use rocket::tokio::net::UnixStream;
+
+#[get("/")]
+fn remote(remote: Option<SocketAddr>) -> String {
+    format!("remote: {:?}", remote)
+}
+
+#[rocket::async_test]
+async fn serves_on_unix_socket_and_cleans_up() {
+    let path = std::env::temp_dir().join(format!("rocket-uds-{}.sock", std::process::id()));
+
+    // Leave a stale socket file behind; it should be removed on bind.
+    drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+    assert!(path.exists());
+
+    let config = Config {
+        address: Address::Unix(path.clone()),
+        socket_mode: Some(0o660),
+        ..Config::debug_default()
+    };
+
+    let (tx, rx) = oneshot::channel();
+    let rocket = rocket::custom(config)
+        .mount("/", routes![remote])
+        .attach(AdHoc::on_liftoff("Send Shutdown -> Channel", move |rocket| {
+            Box::pin(async move {
+                tx.send(rocket.shutdown()).unwrap();
+            })
+        }));
+
+    let server = rocket::tokio::spawn(rocket.launch());
+    let shutdown = rx.await.unwrap();
+
+    let metadata = std::fs::metadata(&path).unwrap();
+    assert!(metadata.file_type().is_socket());
+    assert_eq!(metadata.permissions().mode() & 0o777, 0o660);
+
+    // The private directory the socket was bound in is gone.
+    let prefix = format!(".rocket-uds-{}.sock.", std::process::id());
+    assert!(std::fs::read_dir(std::env::temp_dir()).unwrap()
+        .all(|entry| !entry.unwrap().file_name().to_string_lossy().starts_with(&prefix)));
+
+    let mut stream = UnixStream::connect(&path).await.unwrap();
+    let request = b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+    stream.write_all(request).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.ends_with("remote: None"));
+
+    shutdown.notify();
+    server.await.unwrap().unwrap();
+    assert!(!path.exists());
+}
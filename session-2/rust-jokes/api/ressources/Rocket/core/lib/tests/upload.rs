@@ -0,0 +1,175 @@
+#![cfg(feature = "upload")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, Config};
+use rocket::data::{Limits, ToByteUnit};
+use rocket::figment::Figment;
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::blocking::{Client, LocalRequest};
+use rocket::upload::{Uploads, Uploaded, MemoryStore};
+
+#[post("/publish/<id>")]
+async fn publish(id: &str, uploads: Uploaded<'_>) -> Option<String> {
+    let file = uploads.take(id).await.unwrap()?;
+    let content = std::fs::read_to_string(file.path()?).unwrap();
+    Some(format!("{} {}", file.content_type()?, content))
+}
+
+fn rocket(dir: &std::path::Path) -> Rocket<Build> {
+    let figment = Figment::from(Config::debug_default())
+        .merge(("temp_dir", dir))
+        .merge(("limits", Limits::default().limit("upload", 16.bytes())));
+
+    rocket::custom(figment)
+        .mount("/", routes![publish])
+        .attach(Uploads::new(MemoryStore::new()))
+}
+
+fn tus(request: LocalRequest<'_>) -> LocalRequest<'_> {
+    request.header(Header::new("Tus-Resumable", "1.0.0"))
+}
+
+fn chunk<'c>(client: &'c Client, location: &str, offset: u64, body: &str) -> LocalRequest<'c> {
+    tus(client.patch(location.to_string()))
+        .header(ContentType::new("application", "offset+octet-stream"))
+        .header(Header::new("Upload-Offset", offset.to_string()))
+        .body(body)
+}
+
+fn create(client: &Client, length: u64) -> String {
+    let response = tus(client.post("/uploads"))
+        .header(Header::new("Upload-Length", length.to_string()))
+        .header(Header::new("Upload-Metadata", "filetype dGV4dC9wbGFpbg=="))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Created);
+    response.headers().get_one("Location").unwrap().to_string()
+}
+
+fn offset(client: &Client, location: &str) -> Option<String> {
+    let response = tus(client.head(location.to_string())).dispatch();
+    response.headers().get_one("Upload-Offset").map(|s| s.to_string())
+}
+
+#[test]
+fn uploads_are_resumed_and_taken() {
+    let dir = tempfile::tempdir().unwrap();
+    let client = Client::debug(rocket(dir.path())).unwrap();
+    let location = create(&client, 10);
+    let id = location.trim_start_matches("/uploads/");
+    assert_eq!(offset(&client, &location).unwrap(), "0");
+
+    let response = chunk(&client, &location, 0, "hello").dispatch();
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(response.headers().get_one("Upload-Offset"), Some("5"));
+    assert_eq!(response.headers().get_one("Tus-Resumable"), Some("1.0.0"));
+    assert_eq!(offset(&client, &location).unwrap(), "5");
+
+    // The upload isn't complete yet.
+    let response = client.post(format!("/publish/{}", id)).dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = chunk(&client, &location, 0, "hello").dispatch();
+    assert_eq!(response.status(), Status::Conflict);
+
+    let response = chunk(&client, &location, 5, " world").dispatch();
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+    assert_eq!(offset(&client, &location).unwrap(), "5");
+
+    let response = chunk(&client, &location, 5, "world").dispatch();
+    assert_eq!(response.headers().get_one("Upload-Offset"), Some("10"));
+
+    let response = client.post(format!("/publish/{}", id)).dispatch();
+    assert_eq!(response.into_string().unwrap(), "text/plain helloworld");
+
+    let response = client.post(format!("/publish/{}", id)).dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(offset(&client, &location).is_none());
+    assert_eq!(std::fs::read_dir(dir.path().join("uploads")).unwrap().count(), 0);
+}
+
+#[test]
+fn checksums_are_verified() {
+    let dir = tempfile::tempdir().unwrap();
+    let client = Client::debug(rocket(dir.path())).unwrap();
+    let location = create(&client, 10);
+    chunk(&client, &location, 0, "hello").dispatch();
+
+    let checksum = |value: &'static str| Header::new("Upload-Checksum", value);
+    let response = chunk(&client, &location, 5, "world")
+        .header(checksum("sha1 SG6kYiTRu0+2gPNPfJrZao8k7Ii+c+qOWmxlJg6cuKc="))
+        .dispatch();
+
+    assert_eq!(response.status().code, 460);
+    assert_eq!(offset(&client, &location).unwrap(), "5");
+
+    let response = chunk(&client, &location, 5, "world")
+        .header(checksum("md5 fCEUM/AgcVl3Qeb/Wo6jR4mrv0M="))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = chunk(&client, &location, 5, "world")
+        .header(checksum("sha256 SG6kYiTRu0+2gPNPfJrZao8k7Ii+c+qOWmxlJg6cuKc="))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(offset(&client, &location).unwrap(), "10");
+
+    let location = create(&client, 5);
+    let response = chunk(&client, &location, 0, "world")
+        .header(checksum("sha1 fCEUM/AgcVl3Qeb/Wo6jR4mrv0M="))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::NoContent);
+}
+
+#[test]
+fn protocol_is_enforced() {
+    let dir = tempfile::tempdir().unwrap();
+    let client = Client::debug(rocket(dir.path())).unwrap();
+
+    let response = client.options("/uploads").dispatch();
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(response.headers().get_one("Tus-Version"), Some("1.0.0"));
+    assert_eq!(response.headers().get_one("Tus-Max-Size"), Some("16"));
+    assert_eq!(response.headers().get_one("Tus-Checksum-Algorithm"), Some("sha1,sha256"));
+
+    let response = client.post("/uploads").header(Header::new("Upload-Length", "4")).dispatch();
+    assert_eq!(response.status(), Status::PreconditionFailed);
+
+    let response = tus(client.post("/uploads")).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = tus(client.post("/uploads"))
+        .header(Header::new("Upload-Length", "17"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+
+    let location = create(&client, 4);
+    let response = tus(client.patch(location.clone()))
+        .header(Header::new("Upload-Offset", "0"))
+        .body("abcd")
+        .dispatch();
+
+    assert_eq!(response.status(), Status::UnsupportedMediaType);
+
+    let response = tus(client.delete(location.clone())).dispatch();
+    assert_eq!(response.status(), Status::NoContent);
+    assert!(offset(&client, &location).is_none());
+
+    let response = chunk(&client, &location, 0, "abcd").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = chunk(&client, "/uploads/..", 0, "abcd").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[test]
+fn uploaded_requires_fairing() {
+    let rocket = rocket::build().mount("/", routes![publish]);
+    let error = Client::debug(rocket).unwrap_err();
+    assert!(matches!(error.kind(), rocket::error::ErrorKind::SentinelAborts(..)));
+}
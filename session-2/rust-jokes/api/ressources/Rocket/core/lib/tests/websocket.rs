@@ -0,0 +1,144 @@
+#![cfg(feature = "websocket")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::{Config, Shutdown};
+use rocket::fairing::AdHoc;
+use rocket::futures::{SinkExt, StreamExt};
+use rocket::futures::channel::oneshot;
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::{Client, LocalRequest};
+use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+use rocket::tokio::net::TcpStream;
+use rocket::websocket::{WebSocket, Channel, Message, CloseCode};
+
+#[get("/echo")]
+fn echo(ws: WebSocket) -> Channel {
+    ws.channel(|mut stream| async move {
+        while let Some(message) = stream.next().await {
+            match message? {
+                Message::Close(_) => break,
+                message => stream.send(message).await?,
+            }
+        }
+
+        Ok(())
+    })
+}
+
+#[get("/echo", rank = 2)]
+fn plain() -> &'static str {
+    "not a websocket"
+}
+
+fn upgrade<'c>(request: LocalRequest<'c>, version: &'static str) -> LocalRequest<'c> {
+    request
+        .header(Header::new("Connection", "keep-alive, Upgrade"))
+        .header(Header::new("Upgrade", "websocket"))
+        .header(Header::new("Sec-WebSocket-Version", version))
+        .header(Header::new("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ=="))
+}
+
+#[rocket::async_test]
+async fn channel_echoes_messages() {
+    let client = Client::debug_with(routes![echo, plain]).await.unwrap();
+    let response = upgrade(client.get("/echo"), "13").dispatch().await;
+    assert_eq!(response.status(), Status::SwitchingProtocols);
+    assert_eq!(response.headers().get_one("Upgrade"), Some("websocket"));
+    assert_eq!(response.headers().get_one("Sec-WebSocket-Accept"),
+        Some("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+
+    let mut stream = response.into_websocket().await.unwrap();
+    for message in vec![Message::Text("hi".into()), Message::Binary(vec![1, 2, 3])] {
+        stream.send(message.clone()).await.unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), message);
+    }
+
+    stream.close().await.unwrap();
+}
+
+#[rocket::async_test]
+async fn non_upgrade_requests_are_forwarded() {
+    let client = Client::debug_with(routes![echo, plain]).await.unwrap();
+    let response = client.get("/echo").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.into_websocket().await.is_none());
+
+    let response = upgrade(client.get("/echo"), "8").dispatch().await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client.get("/echo")
+        .header(Header::new("Connection", "upgrade"))
+        .header(Header::new("Upgrade", "WebSocket"))
+        .header(Header::new("Sec-WebSocket-Version", "13"))
+        .dispatch().await;
+
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[rocket::async_test]
+async fn shutdown_closes_connections() {
+    let client = Client::debug_with(routes![echo]).await.unwrap();
+    let response = upgrade(client.get("/echo"), "13").dispatch().await;
+    let mut stream = response.into_websocket().await.unwrap();
+
+    client.rocket().shutdown().notify();
+    match stream.next().await {
+        Some(Ok(Message::Close(Some(frame)))) => assert_eq!(frame.code, CloseCode::Away),
+        message => panic!("expected a close frame, got {:?}", message),
+    }
+}
+
+/// Reads an unmasked frame with a short payload, as sent by the server.
+async fn read_frame(socket: &mut TcpStream) -> (u8, Vec<u8>) {
+    let mut head = [0; 2];
+    socket.read_exact(&mut head).await.unwrap();
+    let mut payload = vec![0; (head[1] & 0x7F) as usize];
+    socket.read_exact(&mut payload).await.unwrap();
+    (head[0], payload)
+}
+
+#[rocket::async_test]
+async fn server_upgrades_connections() {
+    let (tx, rx) = oneshot::channel::<(u16, Shutdown)>();
+    let rocket = rocket::custom(Config { port: 0, ..Config::debug_default() })
+        .mount("/", routes![echo])
+        .attach(AdHoc::on_liftoff("Send Port -> Channel", move |rocket| {
+            Box::pin(async move {
+                tx.send((rocket.config().port, rocket.shutdown())).unwrap();
+            })
+        }));
+
+    let server = rocket::tokio::spawn(rocket.launch());
+    let (port, shutdown) = rx.await.unwrap();
+
+    let mut socket = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    socket.write_all(b"GET /echo HTTP/1.1\r\nHost: localhost\r\n\
+        Connection: Upgrade\r\nUpgrade: websocket\r\nSec-WebSocket-Version: 13\r\n\
+        Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n").await.unwrap();
+
+    let mut head = vec![];
+    while !head.ends_with(b"\r\n\r\n") {
+        head.push(socket.read_u8().await.unwrap());
+    }
+
+    let head = String::from_utf8(head).unwrap();
+    assert!(head.starts_with("HTTP/1.1 101 Switching Protocols\r\n"));
+    assert!(head.contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+
+    // A masked, final text frame with the payload "hi".
+    let mask = [1, 2, 3, 4];
+    let mut frame = vec![0x81, 0x80 | 2];
+    frame.extend_from_slice(&mask);
+    frame.extend(b"hi".iter().zip(mask.iter()).map(|(b, m)| b ^ m));
+    socket.write_all(&frame).await.unwrap();
+    assert_eq!(read_frame(&mut socket).await, (0x81, b"hi".to_vec()));
+
+    shutdown.notify();
+    let (opcode, payload) = read_frame(&mut socket).await;
+    assert_eq!(opcode, 0x88);
+    assert_eq!(payload[..2], 1001u16.to_be_bytes());
+
+    drop(socket);
+    server.await.unwrap().unwrap();
+}
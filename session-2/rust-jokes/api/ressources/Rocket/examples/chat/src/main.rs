@@ -5,10 +5,11 @@
 use rocket::{State, Shutdown};
 use rocket::fs::{relative, FileServer};
 use rocket::form::Form;
-use rocket::response::stream::{EventStream, Event};
-use rocket::serde::{Serialize, Deserialize};
-use rocket::tokio::sync::broadcast::{channel, Sender, error::RecvError};
+use rocket::futures::{SinkExt, StreamExt};
+use rocket::response::stream::{EventStream, Event, LastEventId, Replay};
+use rocket::serde::{Serialize, Deserialize, json};
 use rocket::tokio::select;
+use rocket::websocket::{self, WebSocket, Channel};
 
 #[derive(Debug, Clone, FromForm, Serialize, Deserialize)]
 #[cfg_attr(test, derive(PartialEq, UriDisplayQuery))]
@@ -22,37 +23,75 @@ struct Message {
 }
 
 /// Returns an infinite stream of server-sent events. Each event is a message
-/// pulled from a broadcast queue sent by the `post` handler.
+/// pulled from a replay queue sent by the `post` and `chat` handlers. Clients
+/// reconnecting with a `Last-Event-ID` first receive the messages they missed.
 #[get("/events")]
-async fn events(queue: &State<Sender<Message>>, mut end: Shutdown) -> EventStream![] {
-    let mut rx = queue.subscribe();
+async fn events(
+    queue: &State<Replay<Message>>,
+    last: Option<LastEventId<'_>>,
+    mut end: Shutdown
+) -> EventStream![] {
+    let mut rx = queue.subscribe(last);
     EventStream! {
         loop {
-            let msg = select! {
-                msg = rx.recv() => match msg {
-                    Ok(msg) => msg,
-                    Err(RecvError::Closed) => break,
-                    Err(RecvError::Lagged(_)) => continue,
+            let (id, msg) = select! {
+                msg = rx.next() => match msg {
+                    Some(msg) => msg,
+                    None => break,
                 },
                 _ = &mut end => break,
             };
 
-            yield Event::json(&msg);
+            yield Event::json(&msg).id(id.to_string());
         }
     }
 }
 
 /// Receive a message from a form submission and broadcast it to any receivers.
 #[post("/message", data = "<form>")]
-fn post(form: Form<Message>, queue: &State<Sender<Message>>) {
-    // A send 'fails' if there are no active subscribers. That's okay.
-    let _res = queue.send(form.into_inner());
+fn post(form: Form<Message>, queue: &State<Replay<Message>>) {
+    queue.send(form.into_inner());
+}
+
+/// Sends every message in the broadcast queue to the client, as JSON, over a
+/// WebSocket, and broadcasts every message the client sends, as a form, over
+/// the same connection.
+#[get("/chat")]
+fn chat(ws: WebSocket, queue: &State<Replay<Message>>) -> Channel {
+    let queue = queue.inner().clone();
+    ws.channel(move |stream| async move {
+        let mut rx = queue.subscribe(None);
+        let (mut sink, mut stream) = stream.split();
+        loop {
+            select! {
+                msg = rx.next() => match msg {
+                    Some((_, msg)) => {
+                        let json = json::serde_json::to_string(&msg).expect("valid JSON");
+                        sink.send(websocket::Message::Text(json)).await?;
+                    }
+                    None => break,
+                },
+                msg = stream.next() => match msg {
+                    Some(Ok(websocket::Message::Text(form))) => {
+                        if let Ok(msg) = Form::<Message>::parse(&form) {
+                            queue.send(msg);
+                        }
+                    }
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => return Err(e),
+                    None => break,
+                },
+            }
+        }
+
+        Ok(())
+    })
 }
 
 #[launch]
 fn rocket() -> _ {
     rocket::build()
-        .manage(channel::<Message>(1024).0)
-        .mount("/", routes![post, events])
+        .manage(Replay::<Message>::new(1024))
+        .mount("/", routes![post, events, chat])
         .mount("/", FileServer::from(relative!("static")))
 }
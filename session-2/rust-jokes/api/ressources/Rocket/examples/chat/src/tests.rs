@@ -3,7 +3,7 @@ use std::ops::Range;
 use rand::{thread_rng, Rng};
 use rand::distributions::Alphanumeric;
 
-use rocket::http::{ContentType, Status};
+use rocket::http::{ContentType, Header, Status};
 use rocket::http::uri::fmt::{UriDisplay, Query};
 use rocket::local::asynchronous::{Client, LocalResponse};
 
@@ -113,3 +113,79 @@ async fn bad_messages() {
         assert_eq!(response.status(), Status::PayloadTooLarge);
     }
 }
+
+#[async_test]
+async fn websocket_messages() {
+    let client = Client::tracked(rocket()).await.unwrap();
+    let response = client.get(uri!(chat))
+        .header(Header::new("Connection", "Upgrade"))
+        .header(Header::new("Upgrade", "websocket"))
+        .header(Header::new("Sec-WebSocket-Version", "13"))
+        .header(Header::new("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ=="))
+        .dispatch()
+        .await;
+
+    let mut stream = response.into_websocket().await.expect("websocket upgrade");
+    let bad_message = Message {
+        room: gen_string(30..40),
+        username: gen_string(10..20),
+        message: gen_string(10..100),
+    };
+
+    let message = Message {
+        room: gen_string(10..30),
+        username: gen_string(10..20),
+        message: gen_string(10..100),
+    };
+
+    // Invalid messages are dropped; valid ones are broadcast to every client.
+    for message in &[&bad_message, &message] {
+        let form = (*message as &dyn UriDisplay<Query>).to_string();
+        stream.send(websocket::Message::Text(form)).await.unwrap();
+    }
+
+    let received = match stream.next().await {
+        Some(Ok(websocket::Message::Text(json))) => json::from_str::<Message>(&json).unwrap(),
+        other => panic!("expected a text message, got {:?}", other),
+    };
+
+    assert_eq!(received, message);
+}
+
+#[async_test]
+async fn missed_messages_are_replayed() {
+    let client = Client::tracked(rocket()).await.unwrap();
+    let mut messages = vec![];
+    for _ in 0..5 {
+        let message = Message {
+            room: gen_string(10..30),
+            username: gen_string(10..20),
+            message: gen_string(10..100),
+        };
+
+        send_message(&client, &message).await;
+        messages.push(message);
+    }
+
+    // Reconnect having last seen the message with id `1`.
+    let response = client.get(uri!(events))
+        .header(Header::new("Last-Event-ID", "1"))
+        .dispatch()
+        .await;
+
+    let mut replayed = vec![];
+    let mut reader = BufReader::new(response).lines();
+    while let Ok(Some(line)) = reader.next_line().await {
+        if line.starts_with("id:") {
+            replayed.push(line[3..].parse::<usize>().expect("numeric id"));
+        } else if line.starts_with("data:") {
+            let data: Message = json::from_str(&line[5..]).expect("message JSON");
+            assert_eq!(data, messages[*replayed.last().unwrap()]);
+            if replayed.len() == 3 {
+                client.rocket().shutdown().notify();
+            }
+        }
+    }
+
+    assert_eq!(replayed, vec![2, 3, 4]);
+}
@@ -15,6 +15,7 @@ var STATE = {
   room: "lobby",
   rooms: {},
   connected: false,
+  socket: null,
 }
 
 // Generate a color from a "hash" of a string. Thanks, internet.
@@ -83,39 +84,37 @@ function addMessage(room, username, message, push = false) {
   }
 }
 
-// Subscribe to the event source at `uri` with exponential backoff reconnect.
-function subscribe(uri) {
+// Connect to the WebSocket at `uri` with exponential backoff reconnect.
+function connect(uri) {
   var retryTime = 1;
 
-  function connect(uri) {
-    const events = new EventSource(uri);
+  function open(uri) {
+    const socket = new WebSocket(uri);
 
-    events.addEventListener("message", (ev) => {
-      console.log("raw data", JSON.stringify(ev.data));
-      console.log("decoded data", JSON.stringify(JSON.parse(ev.data)));
+    socket.addEventListener("message", (ev) => {
       const msg = JSON.parse(ev.data);
       if (!"message" in msg || !"room" in msg || !"username" in msg) return;
       addMessage(msg.room, msg.username, msg.message, true);
     });
 
-    events.addEventListener("open", () => {
+    socket.addEventListener("open", () => {
+      STATE.socket = socket;
       setConnectedStatus(true);
-      console.log(`connected to event stream at ${uri}`);
+      console.log(`connected to websocket at ${uri}`);
       retryTime = 1;
     });
 
-    events.addEventListener("error", () => {
+    socket.addEventListener("close", () => {
       setConnectedStatus(false);
-      events.close();
 
       let timeout = retryTime;
       retryTime = Math.min(64, retryTime * 2);
       console.log(`connection lost. attempting to reconnect in ${timeout}s`);
-      setTimeout(() => connect(uri), (() => timeout * 1000)());
+      setTimeout(() => open(uri), (() => timeout * 1000)());
     });
   }
 
-  connect(uri);
+  open(uri);
 }
 
 // Set the connection status: `true` for connected, `false` for disconnected.
@@ -143,12 +142,8 @@ function init() {
     if (!message || !username) return;
 
     if (STATE.connected) {
-      fetch("/message", {
-        method: "POST",
-        body: new URLSearchParams({ room, username, message }),
-      }).then((response) => {
-        if (response.ok) messageField.value = "";
-      });
+      STATE.socket.send(new URLSearchParams({ room, username, message }).toString());
+      messageField.value = "";
     }
   })
 
@@ -165,8 +160,9 @@ function init() {
     addMessage(room, "Rocket", `Look, your own "${room}" room! Nice.`, true);
   })
 
-  // Subscribe to server-sent events.
-  subscribe("/events");
+  // Connect to the chat's WebSocket.
+  const scheme = (location.protocol == "https:") ? "wss" : "ws";
+  connect(`${scheme}://${location.host}/chat`);
 }
 
 init();
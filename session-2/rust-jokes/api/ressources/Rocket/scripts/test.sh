@@ -80,11 +80,13 @@ function test_contrib() {
     postgres_pool
     sqlite_pool
     memcache_pool
+    health
   )
 
   DYN_TEMPLATES_FEATURES=(
     tera
     handlebars
+    tera,health
   )
 
   for feature in "${SYNC_DB_POOLS_FEATURES[@]}"; do
@@ -102,9 +104,21 @@ function test_core() {
   FEATURES=(
     secrets
     tls
+    mtls
     json
     msgpack
     uuid
+    compression
+    ratelimit
+    cors
+    sessions
+    csrf
+    openapi
+    websocket
+    upload
+    metrics
+    access-log
+    health
   )
 
   echo ":: Building and testing core [no features]..."